@@ -9,7 +9,7 @@ use andromeda_std::common::actions::call_action;
 use andromeda_std::common::context::ExecuteContext;
 use andromeda_std::common::Milliseconds;
 use andromeda_std::{
-    ado_base::{hooks::AndromedaHook, InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
+    ado_base::{InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
     common::encode_binary,
     error::ContractError,
 };
@@ -98,26 +98,28 @@ pub fn execute(
 
     let ctx = ExecuteContext::new(deps, info, env);
 
-    if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
+    let hook_response = if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(
             msg,
             ExecuteMsg::Ownership(OwnershipMessage::UpdateOwner { .. })
-        )
-    {
-        contract.module_hook::<Response>(
+        ) {
+        contract.on_execute(
             &ctx.deps.as_ref(),
-            AndromedaHook::OnExecute {
-                sender: ctx.info.sender.to_string(),
-                payload: encode_binary(&msg)?,
-            },
-        )?;
-    }
-    match msg {
+            ctx.info.sender.to_string(),
+            encode_binary(&msg)?,
+        )?
+    } else {
+        Response::new()
+    };
+    let res = match msg {
         ExecuteMsg::AMPReceive(pkt) => {
             ADOContract::default().execute_amp_receive(ctx, pkt, handle_execute)
         }
         _ => handle_execute(ctx, msg),
-    }
+    }?;
+    Ok(res
+        .add_submessages(hook_response.messages)
+        .add_events(hook_response.events))
 }
 
 pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response, ContractError> {
@@ -1,10 +1,7 @@
 use std::str::FromStr;
 
 use andromeda_std::{
-    ado_base::{
-        hooks::AndromedaHook, ownership::OwnershipMessage, InstantiateMsg as BaseInstantiateMsg,
-        MigrateMsg,
-    },
+    ado_base::{ownership::OwnershipMessage, InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
     ado_contract::ADOContract,
     common::{actions::call_action, context::ExecuteContext, encode_binary, Milliseconds},
     error::ContractError,
@@ -137,20 +134,19 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
         &ctx.amp_ctx,
         msg.as_ref(),
     )?;
-    if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
+    let hook_response = if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(
             msg,
             ExecuteMsg::Ownership(OwnershipMessage::UpdateOwner { .. })
-        )
-    {
-        contract.module_hook::<Response>(
+        ) {
+        contract.on_execute(
             &ctx.deps.as_ref(),
-            AndromedaHook::OnExecute {
-                sender: ctx.info.sender.to_string(),
-                payload: encode_binary(&msg)?,
-            },
-        )?;
-    }
+            ctx.info.sender.to_string(),
+            encode_binary(&msg)?,
+        )?
+    } else {
+        Response::new()
+    };
     let res = match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(ctx, msg),
         ExecuteMsg::AddRewardToken { reward_token } => execute_add_reward_token(ctx, reward_token),
@@ -183,7 +179,9 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
     Ok(res
         .add_submessages(action_response.messages)
         .add_attributes(action_response.attributes)
-        .add_events(action_response.events))
+        .add_events(action_response.events)
+        .add_submessages(hook_response.messages)
+        .add_events(hook_response.events))
 }
 
 fn receive_cw20(ctx: ExecuteContext, msg: Cw20ReceiveMsg) -> Result<Response, ContractError> {
@@ -1,7 +1,7 @@
 use andromeda_fungible_tokens::cw20::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use andromeda_std::{
     ado_base::{
-        hooks::AndromedaHook, ownership::OwnershipMessage, AndromedaMsg, AndromedaQuery,
+        ownership::OwnershipMessage, AndromedaMsg, AndromedaQuery,
         InstantiateMsg as BaseInstantiateMsg, MigrateMsg,
     },
     ado_contract::ADOContract,
@@ -83,20 +83,19 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
         &ctx.amp_ctx,
         msg.as_ref(),
     )?;
-    if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
+    let hook_response = if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(
             msg,
             ExecuteMsg::Ownership(OwnershipMessage::UpdateOwner { .. })
-        )
-    {
-        contract.module_hook::<Response>(
+        ) {
+        contract.on_execute(
             &ctx.deps.as_ref(),
-            AndromedaHook::OnExecute {
-                sender: ctx.info.sender.to_string(),
-                payload: encode_binary(&msg)?,
-            },
-        )?;
-    }
+            ctx.info.sender.to_string(),
+            encode_binary(&msg)?,
+        )?
+    } else {
+        Response::new()
+    };
     let res = match msg {
         ExecuteMsg::Transfer { recipient, amount } => execute_transfer(ctx, recipient, amount),
         ExecuteMsg::Burn { amount } => execute_burn(ctx, amount),
@@ -145,7 +144,9 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
     Ok(res
         .add_submessages(action_response.messages)
         .add_attributes(action_response.attributes)
-        .add_events(action_response.events))
+        .add_events(action_response.events)
+        .add_submessages(hook_response.messages)
+        .add_events(hook_response.events))
 }
 
 fn execute_transfer(
@@ -6,10 +6,7 @@ use andromeda_fungible_tokens::lockdrop::{
     UserInfoResponse,
 };
 use andromeda_std::{
-    ado_base::{
-        hooks::AndromedaHook, ownership::OwnershipMessage, InstantiateMsg as BaseInstantiateMsg,
-        MigrateMsg,
-    },
+    ado_base::{ownership::OwnershipMessage, InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
     ado_contract::ADOContract,
     common::{
         actions::call_action, context::ExecuteContext, encode_binary,
@@ -118,20 +115,19 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
         &ctx.amp_ctx,
         msg.as_ref(),
     )?;
-    if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
+    let hook_response = if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(
             msg,
             ExecuteMsg::Ownership(OwnershipMessage::UpdateOwner { .. })
-        )
-    {
-        contract.module_hook::<Response>(
+        ) {
+        contract.on_execute(
             &ctx.deps.as_ref(),
-            AndromedaHook::OnExecute {
-                sender: ctx.info.sender.to_string(),
-                payload: encode_binary(&msg)?,
-            },
-        )?;
-    }
+            ctx.info.sender.to_string(),
+            encode_binary(&msg)?,
+        )?
+    } else {
+        Response::new()
+    };
 
     let res = match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(ctx, msg),
@@ -145,7 +141,9 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
     Ok(res
         .add_submessages(action_response.messages)
         .add_attributes(action_response.attributes)
-        .add_events(action_response.events))
+        .add_events(action_response.events)
+        .add_submessages(hook_response.messages)
+        .add_events(hook_response.events))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
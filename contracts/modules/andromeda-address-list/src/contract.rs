@@ -1,4 +1,6 @@
-use andromeda_modules::address_list::IncludesAddressResponse;
+use andromeda_modules::address_list::{
+    AddressEntry, AddressInfo, AddressesResponse, IncludesAddressResponse, MerkleRootResponse,
+};
 #[cfg(not(feature = "library"))]
 use andromeda_modules::address_list::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use andromeda_std::{
@@ -8,11 +10,17 @@ use andromeda_std::{
     error::ContractError,
 };
 
-use cosmwasm_std::{attr, ensure, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError};
+use cosmwasm_std::{
+    attr, ensure, Attribute, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+};
 use cosmwasm_std::{entry_point, to_json_binary};
-use cw_utils::nonpayable;
+use cw_utils::{nonpayable, Expiration};
+use sha2::Digest;
 
-use crate::state::{add_address, includes_address, remove_address, IS_INCLUSIVE};
+use crate::state::{
+    add_address, get_address_entries, get_address_entry, includes_address, remove_address,
+    ADDRESS_LIST, IS_INCLUSIVE, MERKLE_ROOT,
+};
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:andromeda-address-list";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -63,26 +71,49 @@ pub fn execute(
 
 pub fn handle_execute(ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::AddAddress { address } => execute_add_address(ctx, address),
+        ExecuteMsg::AddAddress {
+            address,
+            expiration,
+            tag,
+        } => execute_add_address(ctx, address, expiration, tag),
         ExecuteMsg::RemoveAddress { address } => execute_remove_address(ctx, address),
-        ExecuteMsg::AddAddresses { addresses } => execute_add_addresses(ctx, addresses),
+        ExecuteMsg::AddAddresses {
+            addresses,
+            expiration,
+            tag,
+        } => execute_add_addresses(ctx, addresses, expiration, tag),
+        ExecuteMsg::RemoveAddresses { addresses } => execute_remove_addresses(ctx, addresses),
+        ExecuteMsg::RegisterMerkleRoot { merkle_root } => {
+            execute_register_merkle_root(ctx, merkle_root)
+        }
+        ExecuteMsg::RegisterWithProof { proof } => execute_register_with_proof(ctx, proof),
         _ => ADOContract::default().execute(ctx, msg),
     }
 }
 
-fn execute_add_address(ctx: ExecuteContext, address: String) -> Result<Response, ContractError> {
-    let ExecuteContext { deps, info, .. } = ctx;
+fn execute_add_address(
+    ctx: ExecuteContext,
+    address: String,
+    expiration: Option<Expiration>,
+    tag: Option<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
     nonpayable(&info)?;
     ensure!(
         ADOContract::default().is_owner_or_operator(deps.storage, info.sender.as_str())?,
         ContractError::Unauthorized {}
     );
-    add_address(deps.storage, &address)?;
+    let address_info = validate_address_info(&env.block, expiration, tag)?;
+    add_address(deps.storage, &address, &address_info)?;
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "add_address"),
-        attr("address", address),
-    ]))
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "add_address"),
+            attr("address", address),
+        ])
+        .add_attributes(address_info_attributes(&address_info)))
 }
 
 fn execute_remove_address(ctx: ExecuteContext, address: String) -> Result<Response, ContractError> {
@@ -107,36 +138,177 @@ const MAX_ADDRESSES_SIZE: usize = 100;
 fn execute_add_addresses(
     ctx: ExecuteContext,
     addresses: Vec<String>,
+    expiration: Option<Expiration>,
+    tag: Option<String>,
 ) -> Result<Response, ContractError> {
-    let ExecuteContext { deps, info, .. } = ctx;
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
     nonpayable(&info)?;
 
+    validate_addresses_size(&addresses)?;
+
     ensure!(
-        !addresses.is_empty(),
-        ContractError::Std(StdError::generic_err("addresses cannot be empty"))
+        ADOContract::default().is_owner_or_operator(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
     );
+
+    let address_info = validate_address_info(&env.block, expiration, tag)?;
+    for address in addresses.clone() {
+        add_address(deps.storage, &address, &address_info)?;
+    }
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "add_addresses"),
+            attr("addresses", addresses.join(",")),
+        ])
+        .add_attributes(address_info_attributes(&address_info)))
+}
+
+fn execute_remove_addresses(
+    ctx: ExecuteContext,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    nonpayable(&info)?;
+
+    validate_addresses_size(&addresses)?;
+
     ensure!(
-        addresses.len() <= MAX_ADDRESSES_SIZE,
-        ContractError::Std(StdError::generic_err(
-            "addresses length cannot be more than 100"
-        ))
+        ADOContract::default().is_owner_or_operator(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
     );
 
+    for address in addresses.iter() {
+        remove_address(deps.storage, address);
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_addresses"),
+        attr("addresses", addresses.join(",")),
+    ]))
+}
+
+fn execute_register_merkle_root(
+    ctx: ExecuteContext,
+    merkle_root: Option<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    nonpayable(&info)?;
+
     ensure!(
         ADOContract::default().is_owner_or_operator(deps.storage, info.sender.as_str())?,
         ContractError::Unauthorized {}
     );
 
-    for address in addresses.clone() {
-        add_address(deps.storage, &address)?;
+    match merkle_root {
+        Some(merkle_root) => {
+            // check merkle root length
+            let mut root_buf: [u8; 32] = [0; 32];
+            hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+            MERKLE_ROOT.save(deps.storage, &merkle_root)?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "register_merkle_root"),
+                attr("merkle_root", merkle_root),
+            ]))
+        }
+        None => {
+            MERKLE_ROOT.remove(deps.storage);
+
+            Ok(Response::new().add_attribute("action", "remove_merkle_root"))
+        }
+    }
+}
+
+fn execute_register_with_proof(
+    ctx: ExecuteContext,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    nonpayable(&info)?;
+
+    let merkle_root = MERKLE_ROOT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::MerkleRootNotRegistered {})?;
+    verify_proof(&merkle_root, info.sender.as_str(), proof)?;
+
+    // Addresses that are already listed keep the expiration and tag set by the owner.
+    if !ADDRESS_LIST.has(deps.storage, info.sender.as_str()) {
+        add_address(deps.storage, info.sender.as_str(), &AddressInfo::default())?;
     }
 
     Ok(Response::new().add_attributes(vec![
-        attr("action", "add_addresses"),
-        attr("addresses", addresses.join(",")),
+        attr("action", "register_with_proof"),
+        attr("address", info.sender),
     ]))
 }
 
+/// Verifies that the SHA-256 hash of `leaf` is part of the tree with the given root, sorting each
+/// pair of hashes before hashing them together.
+fn verify_proof(merkle_root: &str, leaf: &str, proof: Vec<String>) -> Result<(), ContractError> {
+    let hash = sha2::Sha256::digest(leaf.as_bytes())
+        .as_slice()
+        .try_into()
+        .map_err(|_| ContractError::WrongLength {})?;
+
+    let hash = proof.into_iter().try_fold(hash, |hash, p| {
+        let mut proof_buf = [0; 32];
+        hex::decode_to_slice(p, &mut proof_buf)?;
+        let mut hashes = [hash, proof_buf];
+        hashes.sort_unstable();
+        sha2::Sha256::digest(hashes.concat())
+            .as_slice()
+            .try_into()
+            .map_err(|_| ContractError::WrongLength {})
+    })?;
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(merkle_root, &mut root_buf)?;
+    ensure!(root_buf == hash, ContractError::VerificationFailed {});
+    Ok(())
+}
+
+fn validate_addresses_size(addresses: &[String]) -> Result<(), ContractError> {
+    ensure!(
+        !addresses.is_empty(),
+        ContractError::Std(StdError::generic_err("addresses cannot be empty"))
+    );
+    ensure!(
+        addresses.len() <= MAX_ADDRESSES_SIZE,
+        ContractError::Std(StdError::generic_err(
+            "addresses length cannot be more than 100"
+        ))
+    );
+    Ok(())
+}
+
+fn validate_address_info(
+    block: &BlockInfo,
+    expiration: Option<Expiration>,
+    tag: Option<String>,
+) -> Result<AddressInfo, ContractError> {
+    if let Some(expiration) = expiration {
+        ensure!(
+            !expiration.is_expired(block),
+            ContractError::ExpirationInPast {}
+        );
+    }
+    Ok(AddressInfo { expiration, tag })
+}
+
+fn address_info_attributes(address_info: &AddressInfo) -> Vec<Attribute> {
+    let mut attrs = vec![];
+    if let Some(expiration) = address_info.expiration {
+        attrs.push(attr("expiration", expiration.to_string()));
+    }
+    if let Some(tag) = &address_info.tag {
+        attrs.push(attr("tag", tag));
+    }
+    attrs
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     ADOContract::default().migrate(deps, CONTRACT_NAME, CONTRACT_VERSION)
@@ -145,17 +317,27 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
-        QueryMsg::AndrHook(msg) => handle_andr_hook(deps, msg),
-        QueryMsg::IncludesAddress { address } => encode_binary(&query_address(deps, &address)?),
+        QueryMsg::AndrHook(msg) => handle_andr_hook(deps, env, msg),
+        QueryMsg::IncludesAddress { address } => {
+            encode_binary(&query_address(deps, env, &address)?)
+        }
         QueryMsg::IsInclusive {} => encode_binary(&handle_is_inclusive(deps)?),
+        QueryMsg::AddressInfo { address } => {
+            encode_binary(&query_address_info(deps, env, &address)?)
+        }
+        QueryMsg::Addresses { start_after, limit } => {
+            encode_binary(&query_addresses(deps, env, start_after, limit)?)
+        }
+        QueryMsg::MerkleRoot {} => encode_binary(&query_merkle_root(deps)?),
         _ => ADOContract::default().query(deps, env, msg),
     }
 }
 
-fn handle_andr_hook(deps: Deps, msg: AndromedaHook) -> Result<Binary, ContractError> {
+fn handle_andr_hook(deps: Deps, env: Env, msg: AndromedaHook) -> Result<Binary, ContractError> {
     match msg {
         AndromedaHook::OnExecute { sender, .. } => {
-            let is_included = includes_address(deps.storage, &sender)?;
+            // Expired addresses are treated as if they were not in the address list.
+            let is_included = includes_address(deps.storage, &env.block, &sender)?;
             let is_inclusive = IS_INCLUSIVE.load(deps.storage)?;
             if is_included != is_inclusive {
                 Err(ContractError::Unauthorized {})
@@ -172,8 +354,33 @@ fn handle_is_inclusive(deps: Deps) -> Result<bool, ContractError> {
     Ok(is_inclusive)
 }
 
-fn query_address(deps: Deps, address: &str) -> Result<IncludesAddressResponse, ContractError> {
+fn query_address(
+    deps: Deps,
+    env: Env,
+    address: &str,
+) -> Result<IncludesAddressResponse, ContractError> {
     Ok(IncludesAddressResponse {
-        included: includes_address(deps.storage, address)?,
+        included: includes_address(deps.storage, &env.block, address)?,
+    })
+}
+
+fn query_address_info(deps: Deps, env: Env, address: &str) -> Result<AddressEntry, ContractError> {
+    Ok(get_address_entry(deps.storage, &env.block, address)?)
+}
+
+fn query_addresses(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<AddressesResponse, ContractError> {
+    Ok(AddressesResponse {
+        addresses: get_address_entries(deps.storage, &env.block, start_after, limit)?,
+    })
+}
+
+fn query_merkle_root(deps: Deps) -> Result<MerkleRootResponse, ContractError> {
+    Ok(MerkleRootResponse {
+        merkle_root: MERKLE_ROOT.may_load(deps.storage)?,
     })
 }
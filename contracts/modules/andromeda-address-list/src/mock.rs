@@ -68,6 +68,8 @@ pub fn mock_address_list_instantiate_msg(
 pub fn mock_add_address_msg(address: impl Into<String>) -> ExecuteMsg {
     ExecuteMsg::AddAddress {
         address: address.into(),
+        expiration: None,
+        tag: None,
     }
 }
 
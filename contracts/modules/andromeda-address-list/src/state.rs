@@ -1,11 +1,24 @@
-use cosmwasm_std::{StdResult, Storage};
-use cw_storage_plus::{Item, Map};
+use andromeda_modules::address_list::{AddressEntry, AddressInfo};
+use cosmwasm_std::{BlockInfo, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 pub const ADDRESS_LIST: Map<&str, bool> = Map::new("addresslist");
+/// The expiration and tag of the addresses in `ADDRESS_LIST`, only stored if any are set.
+pub const ADDRESS_INFO: Map<&str, AddressInfo> = Map::new("address_info");
 pub const IS_INCLUSIVE: Item<bool> = Item::new("is_inclusive");
+/// The hex encoded Merkle root of the addresses that are able to register with a proof.
+pub const MERKLE_ROOT: Item<String> = Item::new("merkle_root");
 
-/// Add an address to the address list.
-pub fn add_address(storage: &mut dyn Storage, addr: &str) -> StdResult<()> {
+/// Add an address to the address list, replacing any previous expiration and tag.
+pub fn add_address(storage: &mut dyn Storage, addr: &str, info: &AddressInfo) -> StdResult<()> {
+    if info.expiration.is_none() && info.tag.is_none() {
+        ADDRESS_INFO.remove(storage, addr);
+    } else {
+        ADDRESS_INFO.save(storage, addr, info)?;
+    }
     ADDRESS_LIST.save(storage, addr, &true)
 }
 /// Remove an address from the address list. Errors if the address is not currently included.
@@ -13,9 +26,58 @@ pub fn remove_address(storage: &mut dyn Storage, addr: &str) {
     // Check if the address is included in the address list before removing
     if ADDRESS_LIST.has(storage, addr) {
         ADDRESS_LIST.remove(storage, addr);
+        ADDRESS_INFO.remove(storage, addr);
     };
 }
-/// Query if a given address is included in the address list.
-pub fn includes_address(storage: &dyn Storage, addr: &str) -> StdResult<bool> {
-    Ok(ADDRESS_LIST.may_load(storage, addr)?.unwrap_or(false))
+/// Query if a given address is included in the address list and has not expired.
+pub fn includes_address(storage: &dyn Storage, block: &BlockInfo, addr: &str) -> StdResult<bool> {
+    Ok(get_address_entry(storage, block, addr)?.included)
+}
+
+/// Returns the entry of the given address, which is not included if it is missing or expired.
+pub fn get_address_entry(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    addr: &str,
+) -> StdResult<AddressEntry> {
+    let is_listed = ADDRESS_LIST.may_load(storage, addr)?.unwrap_or(false);
+    let info = ADDRESS_INFO.may_load(storage, addr)?.unwrap_or_default();
+    Ok(to_entry(block, addr.to_string(), is_listed, info))
+}
+
+pub fn get_address_entries(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AddressEntry>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    ADDRESS_LIST
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (addr, is_listed) = item?;
+            let info = ADDRESS_INFO.may_load(storage, &addr)?.unwrap_or_default();
+            Ok(to_entry(block, addr, is_listed, info))
+        })
+        .collect()
+}
+
+fn to_entry(
+    block: &BlockInfo,
+    address: String,
+    is_listed: bool,
+    info: AddressInfo,
+) -> AddressEntry {
+    let is_expired = info
+        .expiration
+        .map_or(false, |expiration| expiration.is_expired(block));
+    AddressEntry {
+        address,
+        included: is_listed && !is_expired,
+        expiration: info.expiration,
+        tag: info.tag,
+    }
 }
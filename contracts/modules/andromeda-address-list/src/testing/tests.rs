@@ -1,19 +1,21 @@
 use crate::contract::{execute, instantiate, query};
-use crate::state::{ADDRESS_LIST, IS_INCLUSIVE};
+use crate::state::{ADDRESS_INFO, ADDRESS_LIST, IS_INCLUSIVE};
 use crate::testing::mock_querier::{mock_dependencies_custom, MOCK_KERNEL_CONTRACT};
 use andromeda_modules::address_list::{
-    ExecuteMsg, IncludesAddressResponse, InstantiateMsg, QueryMsg,
+    AddressEntry, AddressesResponse, ExecuteMsg, IncludesAddressResponse, InstantiateMsg,
+    MerkleRootResponse, QueryMsg,
 };
 use andromeda_std::ado_base::hooks::AndromedaHook;
 
 use andromeda_std::common::encode_binary;
 use andromeda_std::error::ContractError;
 
-use cosmwasm_std::{attr, from_json, DepsMut, MessageInfo, StdError};
+use cosmwasm_std::{attr, from_json, Deps, DepsMut, Env, MessageInfo, StdError};
 use cosmwasm_std::{
     testing::{mock_env, mock_info},
     Response,
 };
+use cw_utils::Expiration;
 
 fn init(deps: DepsMut, info: MessageInfo) {
     instantiate(
@@ -51,6 +53,8 @@ fn test_add_address() {
 
     let msg = ExecuteMsg::AddAddress {
         address: address.to_string(),
+        expiration: None,
+        tag: None,
     };
 
     //add address for registered operator
@@ -93,7 +97,11 @@ fn test_add_addresses() {
 
     init(deps.as_mut(), info.clone());
 
-    let msg = ExecuteMsg::AddAddresses { addresses: vec![] };
+    let msg = ExecuteMsg::AddAddresses {
+        addresses: vec![],
+        expiration: None,
+        tag: None,
+    };
 
     //add address for registered operator
 
@@ -106,6 +114,8 @@ fn test_add_addresses() {
     let addresses = vec![address.to_string(), address_two.to_string()];
     let msg = ExecuteMsg::AddAddresses {
         addresses: addresses.clone(),
+        expiration: None,
+        tag: None,
     };
 
     //add address for registered operator
@@ -188,6 +198,8 @@ fn test_execute_hook_whitelist() {
 
     let msg = ExecuteMsg::AddAddress {
         address: address.to_string(),
+        expiration: None,
+        tag: None,
     };
     let _res = execute(deps.as_mut(), env, info, msg).unwrap();
 
@@ -224,6 +236,8 @@ fn test_execute_hook_blacklist() {
 
     let msg = ExecuteMsg::AddAddress {
         address: address.to_string(),
+        expiration: None,
+        tag: None,
     };
     let _res = execute(deps.as_mut(), env, info, msg).unwrap();
 
@@ -263,3 +277,417 @@ fn test_andr_get_query() {
 
     assert_eq!(IncludesAddressResponse { included: true }, res);
 }
+
+fn query_includes_address(deps: Deps, env: Env, address: &str) -> bool {
+    let msg = QueryMsg::IncludesAddress {
+        address: address.to_owned(),
+    };
+    let res: IncludesAddressResponse = from_json(query(deps, env, msg).unwrap()).unwrap();
+    res.included
+}
+
+#[test]
+fn test_add_address_with_expiration_and_tag() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    let info = mock_info("creator", &[]);
+    init(deps.as_mut(), info.clone());
+
+    let address = "kyc_user";
+    let expiration = Expiration::AtHeight(env.block.height + 10);
+    let msg = ExecuteMsg::AddAddress {
+        address: address.to_string(),
+        expiration: Some(expiration),
+        tag: Some("kyc".to_string()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let expected = Response::default().add_attributes(vec![
+        attr("action", "add_address"),
+        attr("address", address),
+        attr("expiration", expiration.to_string()),
+        attr("tag", "kyc"),
+    ]);
+    assert_eq!(expected, res);
+
+    let res: AddressEntry = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AddressInfo {
+                address: address.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        AddressEntry {
+            address: address.to_string(),
+            included: true,
+            expiration: Some(expiration),
+            tag: Some("kyc".to_string()),
+        },
+        res
+    );
+    assert!(query_includes_address(deps.as_ref(), env.clone(), address));
+
+    // The address is no longer included once it has expired.
+    env.block.height += 10;
+    assert!(!query_includes_address(deps.as_ref(), env.clone(), address));
+
+    // Re-adding the address without metadata clears the expiration and tag.
+    let msg = ExecuteMsg::AddAddress {
+        address: address.to_string(),
+        expiration: None,
+        tag: None,
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert!(query_includes_address(deps.as_ref(), env, address));
+    assert!(!ADDRESS_INFO.has(deps.as_ref().storage, address));
+}
+
+#[test]
+fn test_add_address_expiration_in_past() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
+    init(deps.as_mut(), info.clone());
+
+    let msg = ExecuteMsg::AddAddresses {
+        addresses: vec!["user_one".to_string()],
+        expiration: Some(Expiration::AtHeight(env.block.height)),
+        tag: None,
+    };
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(ContractError::ExpirationInPast {}, err);
+}
+
+#[test]
+fn test_remove_addresses() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
+    init(deps.as_mut(), info.clone());
+
+    let addresses = vec![
+        "user_one".to_string(),
+        "user_two".to_string(),
+        "user_three".to_string(),
+    ];
+    let msg = ExecuteMsg::AddAddresses {
+        addresses: addresses.clone(),
+        expiration: None,
+        tag: Some("kyc".to_string()),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::RemoveAddresses { addresses: vec![] };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err("addresses cannot be empty")),
+        err
+    );
+
+    let removed = vec!["user_one".to_string(), "user_two".to_string()];
+    let msg = ExecuteMsg::RemoveAddresses {
+        addresses: removed.clone(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let expected = Response::default().add_attributes(vec![
+        attr("action", "remove_addresses"),
+        attr("addresses", removed.join(",")),
+    ]);
+    assert_eq!(expected, res);
+
+    assert!(!query_includes_address(
+        deps.as_ref(),
+        env.clone(),
+        "user_one"
+    ));
+    assert!(!query_includes_address(
+        deps.as_ref(),
+        env.clone(),
+        "user_two"
+    ));
+    assert!(!ADDRESS_INFO.has(deps.as_ref().storage, "user_one"));
+    assert!(query_includes_address(deps.as_ref(), env, "user_three"));
+}
+
+#[test]
+fn test_query_addresses() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    let info = mock_info("creator", &[]);
+    init(deps.as_mut(), info.clone());
+
+    let expiration = Expiration::AtHeight(env.block.height + 1);
+    let msg = ExecuteMsg::AddAddresses {
+        addresses: vec!["user_a".to_string(), "user_b".to_string()],
+        expiration: Some(expiration),
+        tag: Some("kyc".to_string()),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::AddAddress {
+        address: "user_c".to_string(),
+        expiration: None,
+        tag: None,
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    env.block.height += 1;
+    let msg = QueryMsg::Addresses {
+        start_after: None,
+        limit: Some(2),
+    };
+    let res: AddressesResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        vec![
+            AddressEntry {
+                address: "user_a".to_string(),
+                included: false,
+                expiration: Some(expiration),
+                tag: Some("kyc".to_string()),
+            },
+            AddressEntry {
+                address: "user_b".to_string(),
+                included: false,
+                expiration: Some(expiration),
+                tag: Some("kyc".to_string()),
+            },
+        ],
+        res.addresses
+    );
+
+    let msg = QueryMsg::Addresses {
+        start_after: Some("user_b".to_string()),
+        limit: None,
+    };
+    let res: AddressesResponse = from_json(query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(
+        vec![AddressEntry {
+            address: "user_c".to_string(),
+            included: true,
+            expiration: None,
+            tag: None,
+        }],
+        res.addresses
+    );
+}
+
+#[test]
+fn test_execute_hook_whitelist_expired() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    let info = mock_info("creator", &[]);
+    init(deps.as_mut(), info.clone());
+
+    let address = "whitelistee";
+    let msg = ExecuteMsg::AddAddress {
+        address: address.to_string(),
+        expiration: Some(Expiration::AtTime(env.block.time.plus_seconds(100))),
+        tag: None,
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = QueryMsg::AndrHook(AndromedaHook::OnExecute {
+        sender: address.to_string(),
+        payload: encode_binary(&"".to_string()).unwrap(),
+    });
+    let res: Option<Response> =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(None, res);
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let err: ContractError = query(deps.as_ref(), env, msg).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+}
+
+// The Merkle tree of the SHA-256 hashes of "user_one", "user_two" and "user_three".
+const MERKLE_ROOT: &str = "14dfa22c7db83b6149b682098f71e5c194c06fdc4204fbb4ed09710428498471";
+const USER_ONE_PROOF: [&str; 2] = [
+    "572efdf5412aa2dba16f95da964ef552475273efc023e22a340b92f4b1c1d285",
+    "557762e7df23619207e0db03f72060ce6197010b67c99a90f927fd4028a6c8e1",
+];
+const USER_THREE_PROOF: [&str; 1] =
+    ["2febc59610803b8210fb889c2f005ecec8fd1e795637db89a6c458e311c4f8f2"];
+
+fn register_merkle_root(deps: DepsMut) {
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: Some(MERKLE_ROOT.to_string()),
+    };
+    execute(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+}
+
+fn to_proof(proof: &[&str]) -> Vec<String> {
+    proof.iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn test_register_merkle_root() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
+    init(deps.as_mut(), info.clone());
+
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: Some(MERKLE_ROOT.to_string()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    let invalid_msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: Some("invalid".to_string()),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), invalid_msg).unwrap_err();
+    assert!(matches!(err, ContractError::Hex(_)));
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let expected = Response::default().add_attributes(vec![
+        attr("action", "register_merkle_root"),
+        attr("merkle_root", MERKLE_ROOT),
+    ]);
+    assert_eq!(expected, res);
+
+    let res: MerkleRootResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::MerkleRoot {}).unwrap()).unwrap();
+    assert_eq!(Some(MERKLE_ROOT.to_string()), res.merkle_root);
+
+    let msg = ExecuteMsg::RegisterMerkleRoot { merkle_root: None };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        Response::default().add_attribute("action", "remove_merkle_root"),
+        res
+    );
+
+    let res: MerkleRootResponse =
+        from_json(query(deps.as_ref(), env, QueryMsg::MerkleRoot {}).unwrap()).unwrap();
+    assert_eq!(None, res.merkle_root);
+}
+
+#[test]
+fn test_register_with_proof() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    init(deps.as_mut(), mock_info("creator", &[]));
+
+    let msg = ExecuteMsg::RegisterWithProof {
+        proof: to_proof(&USER_ONE_PROOF),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user_one", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::MerkleRootNotRegistered {}, err);
+
+    register_merkle_root(deps.as_mut());
+
+    // The proof of another address is rejected.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user_two", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::VerificationFailed {}, err);
+    assert!(!query_includes_address(
+        deps.as_ref(),
+        env.clone(),
+        "user_two"
+    ));
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user_one", &[]), msg).unwrap();
+    let expected = Response::default().add_attributes(vec![
+        attr("action", "register_with_proof"),
+        attr("address", "user_one"),
+    ]);
+    assert_eq!(expected, res);
+    assert!(query_includes_address(
+        deps.as_ref(),
+        env.clone(),
+        "user_one"
+    ));
+
+    let msg = ExecuteMsg::RegisterWithProof {
+        proof: to_proof(&USER_THREE_PROOF),
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user_three", &[]),
+        msg,
+    )
+    .unwrap();
+    assert!(query_includes_address(
+        deps.as_ref(),
+        env.clone(),
+        "user_three"
+    ));
+
+    // Registering again does not clear the expiration and tag set by the owner.
+    let expiration = Expiration::AtHeight(env.block.height + 10);
+    let msg = ExecuteMsg::AddAddress {
+        address: "user_one".to_string(),
+        expiration: Some(expiration),
+        tag: Some("kyc".to_string()),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+    let msg = ExecuteMsg::RegisterWithProof {
+        proof: to_proof(&USER_ONE_PROOF),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("user_one", &[]), msg).unwrap();
+    let res: AddressEntry = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AddressInfo {
+                address: "user_one".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        AddressEntry {
+            address: "user_one".to_string(),
+            included: true,
+            expiration: Some(expiration),
+            tag: Some("kyc".to_string()),
+        },
+        res
+    );
+
+    // Registered addresses are recognised by the hook.
+    let msg = QueryMsg::AndrHook(AndromedaHook::OnExecute {
+        sender: "user_three".to_string(),
+        payload: encode_binary(&"".to_string()).unwrap(),
+    });
+    let res: Option<Response> = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(None, res);
+
+    let msg = QueryMsg::AndrHook(AndromedaHook::OnExecute {
+        sender: "user_two".to_string(),
+        payload: encode_binary(&"".to_string()).unwrap(),
+    });
+    let err: ContractError = query(deps.as_ref(), env, msg).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+}
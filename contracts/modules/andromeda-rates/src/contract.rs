@@ -31,6 +31,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    validate_rates(&deps.as_ref(), &msg.rates)?;
     let config = Config { rates: msg.rates };
     CONFIG.save(deps.storage, &config)?;
 
@@ -86,6 +87,7 @@ fn execute_update_rates(
         ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?,
         ContractError::Unauthorized {}
     );
+    validate_rates(&deps.as_ref(), &rates)?;
     let mut config = CONFIG.load(deps.storage)?;
     config.rates = rates;
     CONFIG.save(deps.storage, &config)?;
@@ -93,6 +95,13 @@ fn execute_update_rates(
     Ok(Response::new().add_attributes(vec![attr("action", "update_rates")]))
 }
 
+fn validate_rates(deps: &Deps, rates: &[RateInfo]) -> Result<(), ContractError> {
+    for rate_info in rates {
+        rate_info.rate.validate(deps)?;
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     ADOContract::default().migrate(deps, CONTRACT_NAME, CONTRACT_VERSION)
@@ -146,8 +155,25 @@ pub fn query_deducted_funds(
         if let Some(desc) = &rate_info.description {
             event = event.add_attribute("description", desc);
         }
-        let rate = rate_info.rate.validate(&deps.querier)?;
+        let rate = rate_info.rate.validate(&deps)?;
         let fee = calculate_fee(rate, &coin)?;
+        // Tiered rates do not charge payments below their lowest bracket.
+        if fee.amount.is_zero() {
+            continue;
+        }
+        // The host only holds the funds it was paid in, so a flat fee in another denom can only
+        // be paid with native funds when the payment itself is native.
+        if fee.denom != coin.denom {
+            ensure!(
+                is_native && deps.querier.query_wasm_contract_info(&fee.denom).is_err(),
+                ContractError::InvalidFunds {
+                    msg: format!(
+                        "Flat fee in {} cannot be paid from a payment in {}",
+                        fee.denom, coin.denom
+                    ),
+                }
+            );
+        }
         for receiver in rate_info.recipients.iter() {
             if !rate_info.is_additive {
                 deduct_funds(&mut leftover_funds, &fee)?;
@@ -1,3 +1,6 @@
+use andromeda_data_storage::primitive::{
+    GetValueResponse, Primitive, QueryMsg as PrimitiveQueryMsg,
+};
 use andromeda_std::ado_base::hooks::{AndromedaHook, HookMsg, OnFundsTransferResponse};
 use andromeda_std::ado_base::InstantiateMsg;
 use andromeda_std::ado_contract::ADOContract;
@@ -5,15 +8,15 @@ use andromeda_std::common::Funds;
 use andromeda_std::testing::mock_querier::MockAndromedaQuerier;
 use cosmwasm_std::testing::mock_info;
 use cosmwasm_std::{
-    from_json,
+    coin, from_json,
     testing::{mock_env, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR},
-    to_json_binary, Binary, Coin, ContractResult, OwnedDeps, Querier, QuerierResult, QueryRequest,
-    SystemError, SystemResult, WasmQuery,
+    to_json_binary, Binary, Coin, ContractResult, Decimal, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, WasmQuery,
 };
 use cosmwasm_std::{BankMsg, CosmosMsg, QuerierWrapper, Response, SubMsg, Uint128};
 
 pub use andromeda_std::testing::mock_querier::{
-    MOCK_APP_CONTRACT, MOCK_KERNEL_CONTRACT, MOCK_RATES_CONTRACT,
+    MOCK_KERNEL_CONTRACT, MOCK_PRIMITIVE_CONTRACT, MOCK_RATES_CONTRACT,
 };
 pub const MOCK_TAX_RECIPIENT: &str = "tax_recipient";
 pub const MOCK_ROYALTY_RECIPIENT: &str = "royalty_recipient";
@@ -83,6 +86,7 @@ impl WasmMockQuerier {
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
                 match contract_addr.as_str() {
                     MOCK_RATES_CONTRACT => self.handle_rates_query(msg),
+                    MOCK_PRIMITIVE_CONTRACT => self.handle_primitive_query(msg),
                     _ => MockAndromedaQuerier::default().handle_query(&self.base, request),
                 }
             }
@@ -145,6 +149,24 @@ impl WasmMockQuerier {
         }
     }
 
+    fn handle_primitive_query(&self, msg: &Binary) -> QuerierResult {
+        match from_json(msg).unwrap() {
+            PrimitiveQueryMsg::GetValue { key } => {
+                let key = key.unwrap_or_else(|| "default".to_string());
+                let value = match key.as_str() {
+                    "percent" => Primitive::Decimal(Decimal::percent(10)),
+                    "flat" => Primitive::Coin(coin(20, "uusd")),
+                    "String" => Primitive::String("Value".to_string()),
+                    _ => return SystemResult::Ok(ContractResult::Err("Not Found".to_string())),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&GetValueResponse { key, value }).unwrap(),
+                ))
+            }
+            _ => panic!("Unsupported Query"),
+        }
+    }
+
     pub fn new(base: MockQuerier) -> Self {
         WasmMockQuerier {
             base,
@@ -1,11 +1,16 @@
 use crate::contract::{execute, instantiate, query, query_deducted_funds};
 use crate::testing::mock_querier::{
-    mock_dependencies_custom, MOCK_KERNEL_CONTRACT, MOCK_OWNER, MOCK_RECIPIENT1, MOCK_RECIPIENT2,
+    mock_dependencies_custom, MOCK_KERNEL_CONTRACT, MOCK_OWNER, MOCK_PRIMITIVE_CONTRACT,
+    MOCK_RECIPIENT1, MOCK_RECIPIENT2,
 };
 use andromeda_modules::rates::{ExecuteMsg, InstantiateMsg, QueryMsg, RateInfo};
-use andromeda_modules::rates::{PaymentsResponse, Rate};
+use andromeda_modules::rates::{
+    PaymentsResponse, PercentRate, PrimitivePointer, Rate, RateBracket, TieredRate,
+};
 use andromeda_std::ado_base::hooks::OnFundsTransferResponse;
+use andromeda_std::amp::AndrAddr;
 use andromeda_std::common::Funds;
+use andromeda_std::error::ContractError;
 use andromeda_std::{amp::recipient::Recipient, common::encode_binary};
 
 use cosmwasm_std::{attr, Decimal, Event};
@@ -241,3 +246,267 @@ fn test_query_deducted_funds_cw20() {
         res
     );
 }
+
+#[test]
+fn test_query_deducted_funds_mismatched_flat_rate() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info(MOCK_OWNER, &[]);
+    let cw20_address = "cw20_address";
+    let msg = InstantiateMsg {
+        rates: vec![RateInfo {
+            rate: Rate::Flat(coin(5, "uusd")),
+            is_additive: true,
+            description: None,
+            recipients: vec![Recipient::from_string(MOCK_RECIPIENT1)],
+        }],
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        owner: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // A CW20 payment cannot pay a native flat fee
+    let err = query_deducted_funds(
+        deps.as_ref(),
+        Funds::Cw20(Cw20Coin {
+            amount: 100u128.into(),
+            address: cw20_address.to_string(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidFunds {
+            msg: format!("Flat fee in uusd cannot be paid from a payment in {cw20_address}"),
+        },
+        err
+    );
+
+    let msg = ExecuteMsg::UpdateRates {
+        rates: vec![RateInfo {
+            rate: Rate::Flat(coin(15, cw20_address)),
+            is_additive: false,
+            description: None,
+            recipients: vec![Recipient::from_string(MOCK_RECIPIENT2)],
+        }],
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // Nor can a native payment pay a CW20 flat fee
+    let err = query_deducted_funds(deps.as_ref(), Funds::Native(coin(100, "uusd"))).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidFunds {
+            msg: format!("Flat fee in {cw20_address} cannot be paid from a payment in uusd"),
+        },
+        err
+    );
+}
+
+#[test]
+fn test_instantiate_invalid_rate() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info(MOCK_OWNER, &[]);
+    let rates = vec![RateInfo {
+        rate: Rate::Percent(PercentRate {
+            percent: Decimal::percent(10),
+            min: Some(Uint128::new(20)),
+            max: Some(Uint128::new(10)),
+        }),
+        is_additive: false,
+        description: None,
+        recipients: vec![Recipient::from_string(MOCK_RECIPIENT1)],
+    }];
+    let msg = InstantiateMsg {
+        rates: rates.clone(),
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        owner: None,
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+    assert_eq!(ContractError::InvalidRate {}, err);
+
+    let msg = InstantiateMsg {
+        rates: vec![],
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        owner: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // Tier thresholds must be ascending.
+    let rates = vec![RateInfo {
+        rate: Rate::Tiered(TieredRate {
+            brackets: vec![
+                RateBracket {
+                    threshold: Uint128::new(100),
+                    percent: Decimal::percent(5),
+                },
+                RateBracket {
+                    threshold: Uint128::new(50),
+                    percent: Decimal::percent(10),
+                },
+            ],
+            min: None,
+            max: None,
+        }),
+        is_additive: false,
+        description: None,
+        recipients: vec![Recipient::from_string(MOCK_RECIPIENT1)],
+    }];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdateRates { rates },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::InvalidRate {}, err);
+
+    // External rates must resolve to a coin or a decimal.
+    let rates = vec![RateInfo {
+        rate: Rate::External(PrimitivePointer {
+            address: AndrAddr::from_string(MOCK_PRIMITIVE_CONTRACT),
+            key: Some("String".to_string()),
+        }),
+        is_additive: false,
+        description: None,
+        recipients: vec![Recipient::from_string(MOCK_RECIPIENT1)],
+    }];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdateRates { rates },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::ParsingError {
+            err: "Stored rate is not a coin or Decimal".to_string()
+        },
+        err
+    );
+}
+
+#[test]
+fn test_query_deducted_funds_external() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info(MOCK_OWNER, &[]);
+    let rates = vec![
+        RateInfo {
+            rate: Rate::External(PrimitivePointer {
+                address: AndrAddr::from_string(MOCK_PRIMITIVE_CONTRACT),
+                key: Some("flat".to_string()),
+            }),
+            is_additive: true,
+            description: None,
+            recipients: vec![Recipient::from_string(MOCK_RECIPIENT1)],
+        },
+        RateInfo {
+            rate: Rate::External(PrimitivePointer {
+                address: AndrAddr::from_string(MOCK_PRIMITIVE_CONTRACT),
+                key: Some("percent".to_string()),
+            }),
+            is_additive: false,
+            description: None,
+            recipients: vec![Recipient::from_string(MOCK_RECIPIENT2)],
+        },
+    ];
+    let msg = InstantiateMsg {
+        rates,
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        owner: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query_deducted_funds(deps.as_ref(), Funds::Native(coin(100, "uusd"))).unwrap();
+
+    assert_eq!(
+        OnFundsTransferResponse {
+            msgs: vec![
+                SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: MOCK_RECIPIENT1.into(),
+                    amount: coins(20, "uusd"),
+                })),
+                SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: MOCK_RECIPIENT2.into(),
+                    amount: coins(10, "uusd"),
+                })),
+            ],
+            leftover_funds: Funds::Native(coin(90, "uusd")),
+            events: vec![
+                Event::new("tax").add_attribute("payment", "recipient1<20uusd"),
+                Event::new("royalty")
+                    .add_attribute("deducted", "10uusd")
+                    .add_attribute("payment", "recipient2<10uusd"),
+            ]
+        },
+        res
+    );
+}
+
+#[test]
+fn test_query_deducted_funds_tiered_capped() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info(MOCK_OWNER, &[]);
+    let rates = vec![
+        RateInfo {
+            rate: Rate::Tiered(TieredRate {
+                brackets: vec![
+                    RateBracket {
+                        threshold: Uint128::new(100),
+                        percent: Decimal::percent(5),
+                    },
+                    RateBracket {
+                        threshold: Uint128::new(1000),
+                        percent: Decimal::percent(10),
+                    },
+                ],
+                min: None,
+                max: None,
+            }),
+            is_additive: false,
+            description: None,
+            recipients: vec![Recipient::from_string(MOCK_RECIPIENT1)],
+        },
+        RateInfo {
+            rate: Rate::Percent(PercentRate {
+                percent: Decimal::percent(10),
+                min: Some(Uint128::new(5)),
+                max: Some(Uint128::new(50)),
+            }),
+            is_additive: false,
+            description: None,
+            recipients: vec![Recipient::from_string(MOCK_RECIPIENT2)],
+        },
+    ];
+    let msg = InstantiateMsg {
+        rates,
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        owner: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // Below the lowest bracket only the minimum of the percent rate is charged.
+    let res = query_deducted_funds(deps.as_ref(), Funds::Native(coin(20, "uusd"))).unwrap();
+    assert_eq!(
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: MOCK_RECIPIENT2.into(),
+            amount: coins(5, "uusd"),
+        }))],
+        res.msgs
+    );
+    assert_eq!(Funds::Native(coin(15, "uusd")), res.leftover_funds);
+
+    // The highest bracket applies and the percent rate is capped at its maximum.
+    let res = query_deducted_funds(deps.as_ref(), Funds::Native(coin(2000, "uusd"))).unwrap();
+    assert_eq!(
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: MOCK_RECIPIENT1.into(),
+                amount: coins(200, "uusd"),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: MOCK_RECIPIENT2.into(),
+                amount: coins(50, "uusd"),
+            })),
+        ],
+        res.msgs
+    );
+    assert_eq!(Funds::Native(coin(1750, "uusd")), res.leftover_funds);
+}
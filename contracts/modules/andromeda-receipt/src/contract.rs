@@ -0,0 +1,217 @@
+use crate::state::{get_receipts, get_receipts_by_sender, receipts, store_receipt};
+use andromeda_modules::receipt::{
+    ExecuteMsg, InstantiateMsg, QueryMsg, Receipt, ReceiptResponse, ReceiptsResponse,
+};
+use andromeda_std::{
+    ado_base::{
+        hooks::{AndromedaHook, OnFundsTransferResponse},
+        permissioning::Permission,
+        InstantiateMsg as BaseInstantiateMsg, MigrateMsg,
+    },
+    ado_contract::ADOContract,
+    common::{context::ExecuteContext, encode_binary, Funds},
+    error::ContractError,
+};
+use std::collections::BTreeMap;
+
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    attr, from_json, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, SubMsg, Uint64,
+    WasmMsg,
+};
+use cw_utils::nonpayable;
+use serde::de::IgnoredAny;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:andromeda-receipt";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const STORE_RECEIPT_ACTION: &str = "STORE_RECEIPT";
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let inst_resp = ADOContract::default().instantiate(
+        deps.storage,
+        env,
+        deps.api,
+        &deps.querier,
+        info,
+        BaseInstantiateMsg {
+            ado_type: CONTRACT_NAME.to_string(),
+            ado_version: CONTRACT_VERSION.to_string(),
+            kernel_address: msg.kernel_address,
+            owner: msg.owner,
+        },
+    )?;
+
+    for host in msg.authorized_hosts.unwrap_or_default() {
+        let addr = host.get_raw_address(&deps.as_ref())?;
+        ADOContract::set_permission(
+            deps.storage,
+            STORE_RECEIPT_ACTION,
+            addr,
+            Permission::Whitelisted(None),
+        )?;
+    }
+
+    Ok(inst_resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let ctx = ExecuteContext::new(deps, info, env);
+
+    match msg {
+        ExecuteMsg::AMPReceive(pkt) => {
+            ADOContract::default().execute_amp_receive(ctx, pkt, handle_execute)
+        }
+        _ => handle_execute(ctx, msg),
+    }
+}
+
+pub fn handle_execute(ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::StoreReceipt {
+            sender,
+            amount,
+            events,
+        } => execute_store_receipt(ctx, sender, amount, events),
+        _ => ADOContract::default().execute(ctx, msg),
+    }
+}
+
+fn execute_store_receipt(
+    ctx: ExecuteContext,
+    sender: String,
+    amount: Option<Funds>,
+    events: Vec<Event>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
+    nonpayable(&info)?;
+
+    // Only the owner and the authorised hosts are able to store receipts.
+    ADOContract::default().is_permissioned_strict(
+        deps.storage,
+        env.clone(),
+        STORE_RECEIPT_ACTION,
+        info.sender.clone(),
+    )?;
+
+    let receipt_id = store_receipt(
+        deps.storage,
+        Receipt {
+            id: Uint64::zero(),
+            host: info.sender.clone(),
+            sender: sender.clone(),
+            amount,
+            events,
+            block_height: env.block.height,
+            block_time: env.block.time,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "store_receipt"),
+        attr("receipt_id", receipt_id.to_string()),
+        attr("host", info.sender),
+        attr("sender", sender),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    ADOContract::default().migrate(deps, CONTRACT_NAME, CONTRACT_VERSION)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::AndrHook(msg) => handle_andromeda_hook(env, msg),
+        QueryMsg::Receipt { receipt_id } => encode_binary(&query_receipt(deps, receipt_id)?),
+        QueryMsg::Receipts { start_after, limit } => encode_binary(&ReceiptsResponse {
+            receipts: get_receipts(deps.storage, start_after.map(|id| id.u64()), limit)?,
+        }),
+        QueryMsg::ReceiptsBySender {
+            sender,
+            start_after,
+            limit,
+        } => encode_binary(&ReceiptsResponse {
+            receipts: get_receipts_by_sender(
+                deps.storage,
+                sender,
+                start_after.map(|id| id.u64()),
+                limit,
+            )?,
+        }),
+        _ => ADOContract::default().query(deps, env, msg),
+    }
+}
+
+/// Hooks are queries and cannot store the receipt themselves, so they return a `StoreReceipt`
+/// message for the host ADO to dispatch.
+fn handle_andromeda_hook(env: Env, msg: AndromedaHook) -> Result<Binary, ContractError> {
+    match msg {
+        AndromedaHook::OnExecute { sender, payload } => {
+            let msg = store_receipt_msg(&env, sender, None, &payload)?;
+            encode_binary(&Some(Response::new().add_submessage(msg)))
+        }
+        AndromedaHook::OnFundsTransfer {
+            sender,
+            payload,
+            amount,
+        } => {
+            let msg = store_receipt_msg(&env, sender, Some(amount.clone()), &payload)?;
+            encode_binary(&OnFundsTransferResponse {
+                msgs: vec![msg],
+                events: vec![],
+                leftover_funds: amount,
+            })
+        }
+        _ => Ok(encode_binary(&None::<Response>)?),
+    }
+}
+
+fn store_receipt_msg(
+    env: &Env,
+    sender: String,
+    amount: Option<Funds>,
+    payload: &Binary,
+) -> Result<SubMsg, ContractError> {
+    let mut event = Event::new("receipt");
+    if let Some(action) = get_action(payload) {
+        event = event.add_attribute("action", action);
+    }
+    Ok(SubMsg::new(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: encode_binary(&ExecuteMsg::StoreReceipt {
+            sender,
+            amount,
+            events: vec![event],
+        })?,
+        funds: vec![],
+    }))
+}
+
+/// Returns the name of the executed message, e.g. `transfer_nft`, if the payload is an execute
+/// message.
+fn get_action(payload: &Binary) -> Option<String> {
+    let msg: BTreeMap<String, IgnoredAny> = from_json(payload).ok()?;
+    msg.into_keys().next()
+}
+
+fn query_receipt(deps: Deps, receipt_id: Uint64) -> Result<ReceiptResponse, ContractError> {
+    let receipt = receipts().load(deps.storage, receipt_id.u64())?;
+    Ok(ReceiptResponse { receipt })
+}
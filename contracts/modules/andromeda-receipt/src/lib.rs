@@ -0,0 +1,7 @@
+pub mod contract;
+pub mod state;
+#[cfg(test)]
+pub mod testing;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "testing"))]
+pub mod mock;
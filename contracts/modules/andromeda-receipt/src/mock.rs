@@ -0,0 +1,36 @@
+#![cfg(all(not(target_arch = "wasm32"), feature = "testing"))]
+
+use crate::contract::{execute, instantiate, query};
+use andromeda_modules::receipt::{InstantiateMsg, QueryMsg};
+use andromeda_std::amp::AndrAddr;
+use cosmwasm_std::{Empty, Uint64};
+use cw_multi_test::{Contract, ContractWrapper};
+
+pub fn mock_andromeda_receipt() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
+
+pub fn mock_receipt_instantiate_msg(
+    authorized_hosts: Option<Vec<AndrAddr>>,
+    kernel_address: impl Into<String>,
+    owner: Option<String>,
+) -> InstantiateMsg {
+    InstantiateMsg {
+        authorized_hosts,
+        kernel_address: kernel_address.into(),
+        owner,
+    }
+}
+
+pub fn mock_receipts_by_sender_msg(
+    sender: impl Into<String>,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> QueryMsg {
+    QueryMsg::ReceiptsBySender {
+        sender: sender.into(),
+        start_after,
+        limit,
+    }
+}
@@ -0,0 +1,75 @@
+use andromeda_modules::receipt::Receipt;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, MultiIndex};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// The id of the next receipt to be stored.
+pub const NEXT_RECEIPT_ID: Item<u64> = Item::new("next_receipt_id");
+
+pub struct ReceiptIndexes<'a> {
+    /// (sender, id)
+    pub sender: MultiIndex<'a, String, Receipt, u64>,
+}
+
+impl<'a> IndexList<Receipt> for ReceiptIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Receipt>> + '_> {
+        let v: Vec<&dyn Index<Receipt>> = vec![&self.sender];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn receipts<'a>() -> IndexedMap<'a, u64, Receipt, ReceiptIndexes<'a>> {
+    let indexes = ReceiptIndexes {
+        sender: MultiIndex::new(
+            |_pk: &[u8], r| r.sender.clone(),
+            "receipts",
+            "receipts__sender",
+        ),
+    };
+    IndexedMap::new("receipts", indexes)
+}
+
+/// Stores the receipt under the next id and returns the id.
+pub fn store_receipt(storage: &mut dyn Storage, mut receipt: Receipt) -> StdResult<u64> {
+    let id = NEXT_RECEIPT_ID.may_load(storage)?.unwrap_or(1);
+    receipt.id = id.into();
+    receipts().save(storage, id, &receipt)?;
+    NEXT_RECEIPT_ID.save(storage, &(id + 1))?;
+    Ok(id)
+}
+
+pub fn get_receipts(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Receipt>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    receipts()
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub fn get_receipts_by_sender(
+    storage: &dyn Storage,
+    sender: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Receipt>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    receipts()
+        .idx
+        .sender
+        .prefix(sender)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
@@ -0,0 +1,175 @@
+use crate::contract::{execute, instantiate, query};
+use andromeda_modules::receipt::{
+    ExecuteMsg, InstantiateMsg, QueryMsg, Receipt, ReceiptResponse, ReceiptsResponse,
+};
+use andromeda_std::{
+    ado_base::hooks::{AndromedaHook, OnFundsTransferResponse},
+    amp::AndrAddr,
+    common::{encode_binary, Funds},
+    error::ContractError,
+    testing::mock_querier::{mock_dependencies_custom, MOCK_KERNEL_CONTRACT},
+};
+use cosmwasm_std::{
+    attr, coin, from_json,
+    testing::{mock_env, mock_info},
+    Addr, DepsMut, Event, Response, SubMsg, Uint64, WasmMsg,
+};
+
+const MOCK_HOST: &str = "host";
+
+fn init(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        authorized_hosts: Some(vec![AndrAddr::from_string(MOCK_HOST)]),
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        owner: None,
+    };
+    instantiate(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+}
+
+fn store_receipt_msg(sender: &str, amount: Option<Funds>, action: &str) -> ExecuteMsg {
+    ExecuteMsg::StoreReceipt {
+        sender: sender.to_string(),
+        amount,
+        events: vec![Event::new("receipt").add_attribute("action", action)],
+    }
+}
+
+#[test]
+fn test_hooks() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut());
+    let env = mock_env();
+    let payload = encode_binary(&ExecuteMsg::StoreReceipt {
+        sender: "sender".to_string(),
+        amount: None,
+        events: vec![],
+    })
+    .unwrap();
+
+    let msg = QueryMsg::AndrHook(AndromedaHook::OnExecute {
+        sender: "sender".to_string(),
+        payload: payload.clone(),
+    });
+    let res: Option<Response> = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    let expected_msg = SubMsg::new(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: encode_binary(&store_receipt_msg("sender", None, "store_receipt")).unwrap(),
+        funds: vec![],
+    });
+    assert_eq!(Some(Response::new().add_submessage(expected_msg)), res);
+
+    let amount = Funds::Native(coin(100, "uandr"));
+    let msg = QueryMsg::AndrHook(AndromedaHook::OnFundsTransfer {
+        sender: "sender".to_string(),
+        payload,
+        amount: amount.clone(),
+    });
+    let res: OnFundsTransferResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    let expected_msg = SubMsg::new(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: encode_binary(&store_receipt_msg(
+            "sender",
+            Some(amount.clone()),
+            "store_receipt",
+        ))
+        .unwrap(),
+        funds: vec![],
+    });
+    assert_eq!(
+        OnFundsTransferResponse {
+            msgs: vec![expected_msg],
+            events: vec![],
+            leftover_funds: amount,
+        },
+        res
+    );
+}
+
+#[test]
+fn test_store_receipt() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut());
+    let env = mock_env();
+    let amount = Funds::Native(coin(100, "uandr"));
+    let msg = store_receipt_msg("sender", Some(amount.clone()), "buy");
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(MOCK_HOST, &[]), msg).unwrap();
+    assert_eq!(
+        Response::new().add_attributes(vec![
+            attr("action", "store_receipt"),
+            attr("receipt_id", "1"),
+            attr("host", MOCK_HOST),
+            attr("sender", "sender"),
+        ]),
+        res
+    );
+
+    let msg = QueryMsg::Receipt {
+        receipt_id: Uint64::one(),
+    };
+    let res: ReceiptResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        Receipt {
+            id: Uint64::one(),
+            host: Addr::unchecked(MOCK_HOST),
+            sender: "sender".to_string(),
+            amount: Some(amount),
+            events: vec![Event::new("receipt").add_attribute("action", "buy")],
+            block_height: env.block.height,
+            block_time: env.block.time,
+        },
+        res.receipt
+    );
+}
+
+#[test]
+fn test_query_receipts() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut());
+    let env = mock_env();
+
+    for sender in ["sender_one", "sender_two", "sender_one", "sender_one"] {
+        let msg = store_receipt_msg(sender, None, "buy");
+        execute(deps.as_mut(), env.clone(), mock_info(MOCK_HOST, &[]), msg).unwrap();
+    }
+    // The owner is able to store receipts as well.
+    let msg = store_receipt_msg("sender_two", None, "buy");
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let msg = QueryMsg::Receipts {
+        start_after: Some(Uint64::new(2)),
+        limit: Some(2),
+    };
+    let res: ReceiptsResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    let ids: Vec<u64> = res.receipts.iter().map(|r| r.id.u64()).collect();
+    assert_eq!(vec![3, 4], ids);
+
+    let msg = QueryMsg::ReceiptsBySender {
+        sender: "sender_one".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res: ReceiptsResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    let ids: Vec<u64> = res.receipts.iter().map(|r| r.id.u64()).collect();
+    assert_eq!(vec![1, 3, 4], ids);
+
+    let msg = QueryMsg::ReceiptsBySender {
+        sender: "sender_two".to_string(),
+        start_after: Some(Uint64::new(2)),
+        limit: None,
+    };
+    let res: ReceiptsResponse = from_json(query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(1, res.receipts.len());
+    assert_eq!(Uint64::new(5), res.receipts[0].id);
+    assert_eq!(Addr::unchecked("owner"), res.receipts[0].host);
+}
@@ -3,11 +3,12 @@ use crate::state::{
 };
 use andromeda_non_fungible_tokens::auction::{
     AuctionIdsResponse, AuctionInfo, AuctionStateResponse, AuthorizedAddressesResponse, Bid,
-    BidsResponse, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, TokenAuctionState,
+    BidsResponse, Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    TokenAuctionState,
 };
 use andromeda_std::{
     ado_base::{
-        hooks::AndromedaHook, ownership::OwnershipMessage, permissioning::Permission,
+        ownership::OwnershipMessage, permissioning::Permission,
         InstantiateMsg as BaseInstantiateMsg, MigrateMsg,
     },
     amp::{AndrAddr, Recipient},
@@ -26,6 +27,7 @@ use cosmwasm_std::{
     DepsMut, Env, MessageInfo, QuerierWrapper, QueryRequest, Response, Storage, SubMsg, Uint128,
     WasmMsg, WasmQuery,
 };
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg, Expiration, OwnerOfResponse};
 use cw_utils::nonpayable;
 
@@ -108,28 +110,30 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
         msg.as_ref(),
     )?;
 
-    if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
+    let hook_response = if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(
             msg,
             ExecuteMsg::Ownership(OwnershipMessage::UpdateOwner { .. })
-        )
-    {
-        contract.module_hook::<Response>(
-            &ctx.deps.as_ref(),
-            AndromedaHook::OnExecute {
-                sender: ctx.info.sender.to_string(),
-                payload: encode_binary(&msg)?,
-            },
-        )?;
-    }
+        ) {
+        // CW20 bids are checked against the bidder rather than the CW20 contract.
+        let sender = match &msg {
+            ExecuteMsg::Receive(receive_msg) => receive_msg.sender.clone(),
+            _ => ctx.info.sender.to_string(),
+        };
+        contract.on_execute(&ctx.deps.as_ref(), sender, encode_binary(&msg)?)?
+    } else {
+        Response::new()
+    };
     let res = match msg {
         ExecuteMsg::ReceiveNft(msg) => handle_receive_cw721(ctx, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(ctx, msg),
         ExecuteMsg::UpdateAuction {
             token_id,
             token_address,
             start_time,
             end_time,
             coin_denom,
+            uses_cw20,
             whitelist,
             min_bid,
             recipient,
@@ -140,6 +144,7 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
             start_time,
             end_time,
             coin_denom,
+            uses_cw20.unwrap_or(false),
             whitelist,
             min_bid,
             recipient,
@@ -167,7 +172,9 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
     Ok(res
         .add_submessages(action_response.messages)
         .add_attributes(action_response.attributes)
-        .add_events(action_response.events))
+        .add_events(action_response.events)
+        .add_submessages(hook_response.messages)
+        .add_events(hook_response.events))
 }
 
 fn handle_receive_cw721(
@@ -185,6 +192,7 @@ fn handle_receive_cw721(
             start_time,
             end_time,
             coin_denom,
+            uses_cw20,
             whitelist,
             min_bid,
             recipient,
@@ -195,6 +203,7 @@ fn handle_receive_cw721(
             start_time,
             end_time,
             coin_denom,
+            uses_cw20.unwrap_or(false),
             whitelist,
             min_bid,
             recipient,
@@ -202,12 +211,16 @@ fn handle_receive_cw721(
     }
 }
 
-fn validate_denom(querier: &QuerierWrapper, denom: String) -> Result<(), ContractError> {
+fn validate_denom(deps: &Deps, denom: String, uses_cw20: bool) -> Result<(), ContractError> {
     ensure!(
         !denom.is_empty(),
         ContractError::InvalidAsset { asset: denom }
     );
-    let potential_supply = querier.query_supply(denom.clone())?;
+    if uses_cw20 {
+        deps.api.addr_validate(&denom)?;
+        return Ok(());
+    }
+    let potential_supply = deps.querier.query_supply(denom.clone())?;
     ensure!(
         !potential_supply.amount.is_zero(),
         ContractError::InvalidAsset { asset: denom }
@@ -223,11 +236,12 @@ fn execute_start_auction(
     start_time: Option<Milliseconds>,
     end_time: Milliseconds,
     coin_denom: String,
+    uses_cw20: bool,
     whitelist: Option<Vec<Addr>>,
     min_bid: Option<Uint128>,
     recipient: Option<Recipient>,
 ) -> Result<Response, ContractError> {
-    validate_denom(&ctx.deps.querier, coin_denom.clone())?;
+    validate_denom(&ctx.deps.as_ref(), coin_denom.clone(), uses_cw20)?;
     let ExecuteContext {
         deps, info, env, ..
     } = ctx;
@@ -257,6 +271,7 @@ fn execute_start_auction(
             high_bidder_addr: Addr::unchecked(""),
             high_bidder_amount: Uint128::zero(),
             coin_denom: coin_denom.clone(),
+            uses_cw20,
             auction_id,
             whitelist,
             min_bid,
@@ -272,6 +287,7 @@ fn execute_start_auction(
         attr("start_time", start_expiration.to_string()),
         attr("end_time", end_expiration.to_string()),
         attr("coin_denom", coin_denom),
+        attr("uses_cw20", uses_cw20.to_string()),
         attr("auction_id", auction_id.to_string()),
         attr("whitelist", whitelist_str),
     ]))
@@ -285,6 +301,7 @@ fn execute_update_auction(
     start_time: Option<Milliseconds>,
     end_time: Milliseconds,
     coin_denom: String,
+    uses_cw20: bool,
     whitelist: Option<Vec<Addr>>,
     min_bid: Option<Uint128>,
     recipient: Option<Recipient>,
@@ -293,7 +310,7 @@ fn execute_update_auction(
         deps, info, env, ..
     } = ctx;
     nonpayable(&info)?;
-    validate_denom(&deps.querier, coin_denom.clone())?;
+    validate_denom(&deps.as_ref(), coin_denom.clone(), uses_cw20)?;
     let mut token_auction_state =
         get_existing_token_auction_state(deps.storage, &token_id, &token_address)?;
     ensure!(
@@ -319,6 +336,7 @@ fn execute_update_auction(
     token_auction_state.end_time = end_expiration;
     token_auction_state.whitelist = whitelist.clone();
     token_auction_state.coin_denom = coin_denom.clone();
+    token_auction_state.uses_cw20 = uses_cw20;
     token_auction_state.min_bid = min_bid;
     token_auction_state.recipient = recipient;
     TOKEN_AUCTION_STATE.save(
@@ -331,6 +349,7 @@ fn execute_update_auction(
         attr("start_time", start_expiration.to_string()),
         attr("end_time", end_expiration.to_string()),
         attr("coin_denom", coin_denom),
+        attr("uses_cw20", uses_cw20.to_string()),
         attr("auction_id", token_auction_state.auction_id.to_string()),
         attr("whitelist", format!("{:?}", &whitelist)),
         attr("min_bid", format!("{:?}", &min_bid)),
@@ -345,6 +364,43 @@ fn execute_place_bid(
     let ExecuteContext {
         deps, info, env, ..
     } = ctx;
+    let payment: Vec<Funds> = info.funds.into_iter().map(Funds::Native).collect();
+
+    place_bid(deps, env, info.sender, payment, token_id, token_address)
+}
+
+fn execute_receive(
+    ctx: ExecuteContext,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
+    nonpayable(&info)?;
+
+    match from_json(&receive_msg.msg)? {
+        Cw20HookMsg::PlaceBid {
+            token_id,
+            token_address,
+        } => {
+            let bidder = deps.api.addr_validate(&receive_msg.sender)?;
+            let payment = Funds::Cw20(Cw20Coin {
+                address: info.sender.to_string(),
+                amount: receive_msg.amount,
+            });
+            place_bid(deps, env, bidder, vec![payment], token_id, token_address)
+        }
+    }
+}
+
+fn place_bid(
+    deps: DepsMut,
+    env: Env,
+    bidder: Addr,
+    payment: Vec<Funds>,
+    token_id: String,
+    token_address: String,
+) -> Result<Response, ContractError> {
     let mut token_auction_state =
         get_existing_token_auction_state(deps.storage, &token_id, &token_address)?;
 
@@ -363,31 +419,37 @@ fn execute_place_bid(
     );
 
     ensure!(
-        token_auction_state.owner != info.sender,
+        token_auction_state.owner != bidder,
         ContractError::TokenOwnerCannotBid {}
     );
 
     ensure!(
-        info.funds.len() == 1,
+        payment.len() == 1,
         ContractError::InvalidFunds {
             msg: "Auctions ensure! exactly one coin to be sent.".to_string(),
         }
     );
     if let Some(ref whitelist) = token_auction_state.whitelist {
-        ensure!(
-            whitelist.iter().any(|x| x == info.sender),
-            ContractError::Unauthorized {}
-        );
+        ensure!(whitelist.contains(&bidder), ContractError::Unauthorized {});
     }
 
     ensure!(
-        token_auction_state.high_bidder_addr != info.sender,
+        token_auction_state.high_bidder_addr != bidder,
         ContractError::HighestBidderCannotOutBid {}
     );
 
-    let payment: &Coin = &info.funds[0];
+    let (is_valid_denom, amount) = match &payment[0] {
+        Funds::Native(coin) => (
+            !token_auction_state.uses_cw20 && coin.denom == token_auction_state.coin_denom,
+            coin.amount,
+        ),
+        Funds::Cw20(cw20_coin) => (
+            token_auction_state.uses_cw20 && cw20_coin.address == token_auction_state.coin_denom,
+            cw20_coin.amount,
+        ),
+    };
     ensure!(
-        payment.denom == token_auction_state.coin_denom && payment.amount > Uint128::zero(),
+        is_valid_denom && amount > Uint128::zero(),
         ContractError::InvalidFunds {
             msg: format!(
                 "No {} assets are provided to auction",
@@ -397,7 +459,7 @@ fn execute_place_bid(
     );
     let min_bid = token_auction_state.min_bid.unwrap_or(Uint128::zero());
     ensure!(
-        payment.amount >= min_bid,
+        amount >= min_bid,
         ContractError::InvalidFunds {
             msg: format!(
                 "Must provide at least {min_bid} {} to bid",
@@ -406,40 +468,37 @@ fn execute_place_bid(
         }
     );
     ensure!(
-        token_auction_state.high_bidder_amount < payment.amount,
+        token_auction_state.high_bidder_amount < amount,
         ContractError::BidSmallerThanHighestBid {}
     );
 
     let mut messages: Vec<CosmosMsg> = vec![];
     // Send back previous bid unless there was no previous bid.
     if token_auction_state.high_bidder_amount > Uint128::zero() {
-        let bank_msg = BankMsg::Send {
-            to_address: token_auction_state.high_bidder_addr.to_string(),
-            amount: coins(
-                token_auction_state.high_bidder_amount.u128(),
-                token_auction_state.coin_denom.clone(),
-            ),
-        };
-        messages.push(CosmosMsg::Bank(bank_msg));
+        messages.push(get_funds_msg(
+            &token_auction_state,
+            token_auction_state.high_bidder_addr.to_string(),
+            token_auction_state.high_bidder_amount,
+        )?);
     }
 
-    token_auction_state.high_bidder_addr = info.sender.clone();
-    token_auction_state.high_bidder_amount = payment.amount;
+    token_auction_state.high_bidder_addr = bidder.clone();
+    token_auction_state.high_bidder_amount = amount;
 
     let key = token_auction_state.auction_id.u128();
     TOKEN_AUCTION_STATE.save(deps.storage, key, &token_auction_state)?;
     let mut bids_for_auction = BIDS.load(deps.storage, key)?;
     bids_for_auction.push(Bid {
-        bidder: info.sender.to_string(),
-        amount: payment.amount,
+        bidder: bidder.to_string(),
+        amount,
         timestamp: env.block.time,
     });
     BIDS.save(deps.storage, key, &bids_for_auction)?;
     Ok(Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "bid"),
         attr("token_id", token_id),
-        attr("bider", info.sender.to_string()),
-        attr("amount", payment.amount.to_string()),
+        attr("bider", bidder.to_string()),
+        attr("amount", amount.to_string()),
     ]))
 }
 
@@ -474,13 +533,11 @@ fn execute_cancel(
 
     // Refund highest bid, if it exists.
     if !token_auction_state.high_bidder_amount.is_zero() {
-        messages.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: token_auction_state.high_bidder_addr.to_string(),
-            amount: coins(
-                token_auction_state.high_bidder_amount.u128(),
-                token_auction_state.coin_denom.clone(),
-            ),
-        }));
+        messages.push(get_funds_msg(
+            &token_auction_state,
+            token_auction_state.high_bidder_addr.to_string(),
+            token_auction_state.high_bidder_amount,
+        )?);
     }
 
     token_auction_state.is_cancelled = true;
@@ -567,7 +624,17 @@ fn execute_claim(
         let recipient = token_auction_state
             .recipient
             .unwrap_or(Recipient::from_string(token_auction_state.owner));
-        let msg = recipient.generate_direct_msg(&deps.as_ref(), vec![after_tax_payment.0])?;
+        let msg = if token_auction_state.uses_cw20 {
+            recipient.generate_msg_cw20(
+                &deps.as_ref(),
+                Cw20Coin {
+                    address: after_tax_payment.0.denom,
+                    amount: after_tax_payment.0.amount,
+                },
+            )?
+        } else {
+            recipient.generate_direct_msg(&deps.as_ref(), vec![after_tax_payment.0])?
+        };
         // Send funds to the specified recipient
         response = response.add_submessage(msg);
     }
@@ -627,16 +694,29 @@ fn purchase_token(
     info: &MessageInfo,
     state: TokenAuctionState,
 ) -> Result<(Coin, Vec<SubMsg>), ContractError> {
-    let total_cost = Coin::new(state.high_bidder_amount.u128(), state.coin_denom.clone());
+    let total_cost = if state.uses_cw20 {
+        Funds::Cw20(Cw20Coin {
+            address: state.coin_denom.clone(),
+            amount: state.high_bidder_amount,
+        })
+    } else {
+        Funds::Native(Coin::new(
+            state.high_bidder_amount.u128(),
+            state.coin_denom.clone(),
+        ))
+    };
 
     let (msgs, _events, remainder) = ADOContract::default().on_funds_transfer(
         &deps,
         info.sender.to_string(),
-        Funds::Native(total_cost),
+        total_cost,
         encode_binary(&"")?,
     )?;
 
-    let remaining_amount = remainder.try_get_coin()?;
+    let remaining_amount = match remainder {
+        Funds::Native(coin) => coin,
+        Funds::Cw20(cw20_coin) => Coin::new(cw20_coin.amount.u128(), cw20_coin.address),
+    };
 
     // Calculate total tax
     // total_tax_amount = total_tax_amount.checked_add(tax_amount)?;
@@ -648,6 +728,26 @@ fn purchase_token(
     Ok((after_tax_payment, msgs))
 }
 
+/// Generates a message sending `amount` of the auction's bidding asset to `recipient`.
+fn get_funds_msg(
+    state: &TokenAuctionState,
+    recipient: String,
+    amount: Uint128,
+) -> Result<CosmosMsg, ContractError> {
+    if state.uses_cw20 {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: state.coin_denom.clone(),
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+            funds: vec![],
+        }))
+    } else {
+        Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount: coins(amount.u128(), state.coin_denom.clone()),
+        }))
+    }
+}
+
 fn get_existing_token_auction_state(
     storage: &dyn Storage,
     token_id: &str,
@@ -2,8 +2,8 @@
 
 use crate::contract::{execute, instantiate, query};
 use andromeda_non_fungible_tokens::auction::{
-    AuctionIdsResponse, AuctionStateResponse, Bid, BidsResponse, Cw721HookMsg, ExecuteMsg,
-    InstantiateMsg, QueryMsg,
+    AuctionIdsResponse, AuctionStateResponse, Bid, BidsResponse, Cw20HookMsg, Cw721HookMsg,
+    ExecuteMsg, InstantiateMsg, QueryMsg,
 };
 use andromeda_std::ado_base::permissioning::{Permission, PermissioningMessage};
 use andromeda_std::amp::messages::AMPPkt;
@@ -53,12 +53,13 @@ impl MockAuction {
         start_time: Option<Milliseconds>,
         end_time: Milliseconds,
         coin_denom: String,
+        uses_cw20: bool,
         min_bid: Option<Uint128>,
         whitelist: Option<Vec<Addr>>,
         recipient: Option<Recipient>,
     ) -> AppResponse {
         let msg = mock_start_auction(
-            start_time, end_time, coin_denom, min_bid, whitelist, recipient,
+            start_time, end_time, coin_denom, uses_cw20, min_bid, whitelist, recipient,
         );
         app.execute_contract(sender, self.addr().clone(), &msg, &[])
             .unwrap()
@@ -138,6 +139,7 @@ pub fn mock_start_auction(
     start_time: Option<Milliseconds>,
     end_time: Milliseconds,
     coin_denom: String,
+    uses_cw20: bool,
     min_bid: Option<Uint128>,
     whitelist: Option<Vec<Addr>>,
     recipient: Option<Recipient>,
@@ -146,6 +148,7 @@ pub fn mock_start_auction(
         start_time,
         end_time,
         coin_denom,
+        uses_cw20: Some(uses_cw20),
         min_bid,
         whitelist,
         recipient,
@@ -188,6 +191,13 @@ pub fn mock_place_bid(token_id: String, token_address: String) -> ExecuteMsg {
     }
 }
 
+pub fn mock_place_bid_cw20(token_id: String, token_address: String) -> Cw20HookMsg {
+    Cw20HookMsg::PlaceBid {
+        token_id,
+        token_address,
+    }
+}
+
 pub fn mock_get_bids(auction_id: Uint128) -> QueryMsg {
     QueryMsg::Bids {
         auction_id,
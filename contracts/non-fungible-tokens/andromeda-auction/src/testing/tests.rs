@@ -2,14 +2,15 @@ use crate::{
     contract::{execute, instantiate, query},
     state::{auction_infos, TOKEN_AUCTION_STATE},
     testing::mock_querier::{
-        mock_dependencies_custom, MOCK_TOKEN_ADDR, MOCK_TOKEN_OWNER, MOCK_UNCLAIMED_TOKEN,
+        mock_dependencies_custom, MOCK_CW20_CONTRACT, MOCK_TOKEN_ADDR, MOCK_TOKEN_OWNER,
+        MOCK_UNCLAIMED_TOKEN,
     },
 };
 
 use andromeda_non_fungible_tokens::{
     auction::{
-        AuctionInfo, AuctionStateResponse, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
-        TokenAuctionState,
+        AuctionInfo, AuctionStateResponse, Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg,
+        QueryMsg, TokenAuctionState,
     },
     cw721::ExecuteMsg as Cw721ExecuteMsg,
 };
@@ -29,6 +30,7 @@ use cosmwasm_std::{
     to_json_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Env, Response, SubMsg, Timestamp,
     Uint128, WasmMsg,
 };
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw721::Cw721ReceiveMsg;
 use cw_utils::Expiration;
 
@@ -61,6 +63,7 @@ fn start_auction(deps: DepsMut, whitelist: Option<Vec<Addr>>, min_bid: Option<Ui
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time() + 20_000_000) * 1_000_000),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist,
         min_bid,
         recipient: None,
@@ -76,6 +79,38 @@ fn start_auction(deps: DepsMut, whitelist: Option<Vec<Addr>>, min_bid: Option<Ui
     let _res = execute(deps, env, info, msg).unwrap();
 }
 
+fn start_auction_cw20(deps: DepsMut) {
+    let hook_msg = Cw721HookMsg::StartAuction {
+        start_time: None,
+        end_time: Milliseconds::from_nanos((current_time() + 20_000_000) * 1_000_000),
+        coin_denom: MOCK_CW20_CONTRACT.to_string(),
+        uses_cw20: Some(true),
+        whitelist: None,
+        min_bid: None,
+        recipient: None,
+    };
+    let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: MOCK_TOKEN_OWNER.to_owned(),
+        token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+        msg: encode_binary(&hook_msg).unwrap(),
+    });
+
+    let info = mock_info(MOCK_TOKEN_ADDR, &[]);
+    let _res = execute(deps, mock_env(), info, msg).unwrap();
+}
+
+fn cw20_bid_msg(bidder: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: bidder.to_string(),
+        amount: Uint128::new(amount),
+        msg: encode_binary(&Cw20HookMsg::PlaceBid {
+            token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+            token_address: MOCK_TOKEN_ADDR.to_string(),
+        })
+        .unwrap(),
+    })
+}
+
 fn assert_auction_created(deps: Deps, whitelist: Option<Vec<Addr>>, min_bid: Option<Uint128>) {
     let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
     let duration = 20_000_000;
@@ -88,6 +123,7 @@ fn assert_auction_created(deps: Deps, whitelist: Option<Vec<Addr>>, min_bid: Opt
             high_bidder_addr: Addr::unchecked(""),
             high_bidder_amount: Uint128::zero(),
             coin_denom: "uusd".to_string(),
+            uses_cw20: false,
             auction_id: 1u128.into(),
             whitelist,
             owner: MOCK_TOKEN_OWNER.to_string(),
@@ -361,6 +397,7 @@ fn execute_place_bid_multiple_bids() {
         high_bidder_amount: Uint128::from(100u128),
         auction_id: Uint128::from(1u128),
         coin_denom: "uusd".to_string(),
+        uses_cw20: false,
         whitelist: None,
         is_cancelled: false,
         min_bid: None,
@@ -559,6 +596,7 @@ fn execute_start_auction_start_time_in_past() {
         start_time: Some(Milliseconds(100000)),
         end_time: Milliseconds(100000),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -591,6 +629,7 @@ fn execute_start_auction_zero_start_time() {
         start_time: Some(Milliseconds::zero()),
         end_time: Milliseconds(1),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -622,6 +661,7 @@ fn execute_start_auction_start_time_not_provided() {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time() + 20_000_000) * 1_000_000),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -646,6 +686,7 @@ fn execute_start_auction_zero_duration() {
         start_time: Some(Milliseconds(100)),
         end_time: Milliseconds::zero(),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -706,6 +747,7 @@ fn execute_update_auction_zero_start() {
         start_time: Some(Milliseconds::zero()),
         end_time: Milliseconds(1),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -738,6 +780,7 @@ fn execute_update_auction_zero_duration() {
         start_time: Some(Milliseconds(100000)),
         end_time: Milliseconds::zero(),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -764,6 +807,7 @@ fn execute_update_auction_unauthorized() {
         start_time: Some(Milliseconds(100000)),
         end_time: Milliseconds(100),
         coin_denom: "uluna".to_string(),
+        uses_cw20: None,
         whitelist: Some(vec![Addr::unchecked("user")]),
         min_bid: None,
         recipient: None,
@@ -788,6 +832,7 @@ fn execute_update_auction_auction_started() {
         start_time: Some(Milliseconds(100000)),
         end_time: Milliseconds(100),
         coin_denom: "uluna".to_string(),
+        uses_cw20: None,
         whitelist: Some(vec![Addr::unchecked("user")]),
         min_bid: None,
         recipient: None,
@@ -814,6 +859,7 @@ fn execute_update_auction() {
         start_time: Some(Milliseconds(1571711019879 + 1)),
         end_time: Milliseconds(1571711019879 + 2),
         coin_denom: "uluna".to_string(),
+        uses_cw20: None,
         whitelist: Some(vec![Addr::unchecked("user")]),
         min_bid: None,
         recipient: None,
@@ -831,6 +877,7 @@ fn execute_update_auction() {
             high_bidder_addr: Addr::unchecked(""),
             high_bidder_amount: Uint128::zero(),
             coin_denom: "uluna".to_string(),
+            uses_cw20: false,
             auction_id: 1u128.into(),
             whitelist: Some(vec![Addr::unchecked("user")]),
             owner: MOCK_TOKEN_OWNER.to_string(),
@@ -858,6 +905,7 @@ fn execute_start_auction_after_previous_finished() {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time() + 20_000_000) * 1_000_000),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -880,6 +928,7 @@ fn execute_start_auction_after_previous_finished() {
                 attr("start_time", "expiration time: 1571801019.880000000"),
                 attr("end_time", "expiration time: 1571817419.879000000"),
                 attr("coin_denom", "uusd"),
+                attr("uses_cw20", "false"),
                 attr("auction_id", "2"),
                 attr("whitelist", "None"),
             ]) // Economics message
@@ -1054,6 +1103,7 @@ fn execute_claim_auction_already_claimed() {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time() + 20_000_000) * 1_000_000),
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         whitelist: None,
         min_bid: None,
         recipient: None,
@@ -1263,3 +1313,108 @@ fn execute_bid_below_min_price() {
     //Will error if invalid
     execute(deps.as_mut(), env, info, msg).unwrap();
 }
+
+#[test]
+fn execute_place_bid_cw20() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    let _res = init(deps.as_mut(), None);
+
+    start_auction_cw20(deps.as_mut());
+    env.block.time = env.block.time.plus_seconds(1);
+
+    // Native funds cannot be bid on a CW20 auction.
+    let msg = ExecuteMsg::PlaceBid {
+        token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+        token_address: MOCK_TOKEN_ADDR.to_string(),
+    };
+    let info = mock_info("sender", &coins(100, MOCK_CW20_CONTRACT));
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert!(matches!(res, ContractError::InvalidFunds { .. }));
+
+    // Nor can tokens of a different CW20.
+    let info = mock_info("other_cw20", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        cw20_bid_msg("sender", 100),
+    )
+    .unwrap_err();
+    assert!(matches!(res, ContractError::InvalidFunds { .. }));
+
+    let info = mock_info(MOCK_CW20_CONTRACT, &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        cw20_bid_msg("sender", 100),
+    )
+    .unwrap();
+    assert!(res.messages.iter().all(|msg| !matches!(
+        &msg.msg,
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == MOCK_CW20_CONTRACT
+    )));
+
+    let auction_state = query_latest_auction_state_helper(deps.as_ref(), env.clone());
+    assert_eq!(Addr::unchecked("sender"), auction_state.high_bidder_addr);
+    assert_eq!(Uint128::new(100), auction_state.high_bidder_amount);
+    assert!(auction_state.uses_cw20);
+
+    // The outbid bidder is refunded in the CW20.
+    let info = mock_info(MOCK_CW20_CONTRACT, &[]);
+    let res = execute(deps.as_mut(), env, info, cw20_bid_msg("other", 200)).unwrap();
+    assert_eq!(
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: MOCK_CW20_CONTRACT.to_string(),
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "sender".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        res.messages[0]
+    );
+}
+
+#[test]
+fn execute_claim_cw20() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    let _res = init(deps.as_mut(), None);
+
+    start_auction_cw20(deps.as_mut());
+    env.block.time = env.block.time.plus_seconds(1);
+
+    let info = mock_info(MOCK_CW20_CONTRACT, &[]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        cw20_bid_msg("sender", 100),
+    )
+    .unwrap();
+
+    // Auction ended by that time
+    env.block.time = env.block.time.plus_days(1);
+
+    let msg = ExecuteMsg::Claim {
+        token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+        token_address: MOCK_TOKEN_ADDR.to_string(),
+    };
+    let info = mock_info("any_user", &[]);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: MOCK_CW20_CONTRACT.to_string(),
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: MOCK_TOKEN_OWNER.to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        res.messages[1]
+    );
+}
@@ -1,9 +1,21 @@
 use crate::state::{
-    get_available_tokens, Purchase, AVAILABLE_TOKENS, CONFIG, NUMBER_OF_TOKENS_AVAILABLE,
-    PURCHASES, SALE_CONDUCTED, STATE,
+    all_tiers_sold_out, clear_escrow_contributions, contributors, get_available_tokens,
+    get_campaigns, get_contributors, get_escrow, get_latest_campaign_id, get_lazy_mint_response,
+    get_lazy_token_ids, get_milestones, get_referrals, get_tier_response, get_tier_token_ids,
+    get_tiers, get_top_contributors, Purchase, AVAILABLE_TOKENS, CAMPAIGNS, CAMPAIGN_PURCHASES,
+    CONFIG, ESCROW, ESCROW_CONTRIBUTIONS, FAILED_REFUNDS, LAZY_MINT, LAZY_MINT_NEXT_IDS,
+    LAZY_MINT_SALES, MILESTONES, NEXT_REFUND_REPLY_ID, NUMBER_OF_TOKENS_AVAILABLE, PURCHASES,
+    REFERRALS, REFUND_RECIPIENTS, REFUND_REPLIES, SALE_CONDUCTED, STATE, TIERS, TIER_SALES,
+    UNCLAIMED_ESCROWS,
 };
+use andromeda_modules::address_list::{IncludesAddressResponse, QueryMsg as AddressListQueryMsg};
 use andromeda_non_fungible_tokens::{
-    crowdfund::{Config, CrowdfundMintMsg, ExecuteMsg, InstantiateMsg, QueryMsg, State},
+    crowdfund::{
+        Campaign, CampaignOutcome, Cancellation, Config, Contributor, CrowdfundMintMsg,
+        Cw20HookMsg, Escrow, ExecuteMsg, InstantiateMsg, LazyMint, LazyMintResponse, Milestone,
+        MilestoneMsg, MilestoneStatus, Presale, PresaleMsg, Pricing, PurchaseResponse, QueryMsg,
+        Referral, State, SummaryResponse, Tier, TierResponse, Whitelist,
+    },
     cw721::{ExecuteMsg as Cw721ExecuteMsg, MintMsg, QueryMsg as Cw721QueryMsg},
 };
 use andromeda_std::{
@@ -18,21 +30,25 @@ use andromeda_std::{
 use andromeda_std::{ado_contract::ADOContract, common::context::ExecuteContext};
 
 use andromeda_std::{
-    ado_base::{hooks::AndromedaHook, InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
-    common::{deduct_funds, encode_binary, merge_sub_msgs, rates::get_tax_amount, Funds},
+    ado_base::{InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
+    common::{encode_binary, merge_sub_msgs, rates::get_tax_amount, Funds},
     error::ContractError,
 };
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, ensure, has_coins, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Order, QuerierWrapper, QueryRequest, Reply, Response, StdError, Storage, SubMsg, Uint128,
-    WasmMsg, WasmQuery,
+    coins, ensure, from_json, Addr, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, Event, MessageInfo, Order, QuerierWrapper, QueryRequest, Reply, Response,
+    StdError, Storage, SubMsg, SubMsgResult, Uint128, Uint64, WasmMsg, WasmQuery,
 };
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw721::{ContractInfoResponse, TokensResponse};
-use cw_utils::nonpayable;
-use std::cmp;
+use cw_utils::{nonpayable, Expiration};
+use std::{
+    cmp,
+    collections::{BTreeMap, HashSet},
+};
 
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 50;
@@ -78,8 +94,27 @@ pub fn instantiate(
         .add_submessages(mod_resp.messages))
 }
 
+/// Refunds sent to refund recipients each get their own reply id, starting from this one.
+const REFUND_REPLY_ID_START: u64 = 1000;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if let Some((purchaser, refunds)) = REFUND_REPLIES.may_load(deps.storage, msg.id)? {
+        REFUND_REPLIES.remove(deps.storage, msg.id);
+        let SubMsgResult::Err(error) = msg.result else {
+            return Ok(Response::default());
+        };
+        // The funds stay in the contract for the purchaser to claim.
+        FAILED_REFUNDS.update(deps.storage, &purchaser, |failed_refunds| {
+            let mut failed_refunds = failed_refunds.unwrap_or_default();
+            failed_refunds.extend(refunds);
+            Ok::<_, ContractError>(failed_refunds)
+        })?;
+        return Ok(Response::default()
+            .add_attribute("action", "refund_failed")
+            .add_attribute("purchaser", purchaser)
+            .add_attribute("error", error));
+    }
     if msg.result.is_err() {
         return Err(ContractError::Std(StdError::generic_err(
             msg.result.unwrap_err(),
@@ -115,49 +150,81 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
         &ctx.amp_ctx,
         msg.as_ref(),
     )?;
-    if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
+    let hook_response = if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(
             msg,
             ExecuteMsg::Ownership(OwnershipMessage::UpdateOwner { .. })
-        )
-    {
-        contract.module_hook::<Response>(
-            &ctx.deps.as_ref(),
-            AndromedaHook::OnExecute {
-                sender: ctx.info.sender.to_string(),
-                payload: encode_binary(&msg)?,
-            },
-        )?;
-    }
+        ) {
+        // For CW20 purchases the hook is run against the purchaser rather than the token contract.
+        let sender = match &msg {
+            ExecuteMsg::Receive(receive_msg) => receive_msg.sender.clone(),
+            _ => ctx.info.sender.to_string(),
+        };
+        contract.on_execute(&ctx.deps.as_ref(), sender, encode_binary(&msg)?)?
+    } else {
+        Response::new()
+    };
     let res = match msg {
         ExecuteMsg::Mint(mint_msgs) => execute_mint(ctx, mint_msgs),
+        ExecuteMsg::SetLazyMint { lazy_mint } => execute_set_lazy_mint(ctx, lazy_mint),
         ExecuteMsg::StartSale {
             start_time,
             end_time,
-            price,
-            min_tokens_sold,
+            prices,
+            pricing,
+            soft_cap,
+            hard_cap,
+            referral_rate,
             max_amount_per_wallet,
             recipient,
+            presale,
+            milestones,
         } => execute_start_sale(
             ctx,
             start_time,
             end_time,
-            price,
-            min_tokens_sold,
+            prices,
+            pricing,
+            soft_cap,
+            hard_cap,
+            referral_rate,
             max_amount_per_wallet,
             recipient,
+            presale,
+            milestones,
         ),
-        ExecuteMsg::Purchase { number_of_tokens } => execute_purchase(ctx, number_of_tokens),
-        ExecuteMsg::PurchaseByTokenId { token_id } => execute_purchase_by_token_id(ctx, token_id),
-        ExecuteMsg::ClaimRefund {} => execute_claim_refund(ctx),
+        ExecuteMsg::AddTier { tier } => execute_add_tier(ctx, tier),
+        ExecuteMsg::UpdateTier { tier } => execute_update_tier(ctx, tier),
+        ExecuteMsg::RemoveTier { tier_id } => execute_remove_tier(ctx, tier_id),
+        ExecuteMsg::Purchase {
+            number_of_tokens,
+            tier_id,
+            refund_recipient,
+            referrer,
+        } => execute_purchase(ctx, number_of_tokens, tier_id, refund_recipient, referrer),
+        ExecuteMsg::PurchaseByTokenId {
+            token_id,
+            refund_recipient,
+            referrer,
+        } => execute_purchase_by_token_id(ctx, token_id, refund_recipient, referrer),
+        ExecuteMsg::Receive(receive_msg) => execute_receive(ctx, receive_msg),
+        ExecuteMsg::ClaimRefund { campaign_id } => execute_claim_refund(ctx, campaign_id),
+        ExecuteMsg::ClaimFailedRefund {} => execute_claim_failed_refund(ctx),
         ExecuteMsg::EndSale { limit } => execute_end_sale(ctx, limit),
+        ExecuteMsg::ReleaseMilestone { milestone_id } => {
+            execute_release_milestone(ctx, milestone_id)
+        }
+        ExecuteMsg::CancelMilestones {} => execute_cancel_milestones(ctx),
+        ExecuteMsg::CancelSale { return_unsold_to } => execute_cancel_sale(ctx, return_unsold_to),
         ExecuteMsg::UpdateTokenContract { address } => execute_update_token_contract(ctx, address),
         _ => ADOContract::default().execute(ctx, msg),
     }?;
     Ok(res
         .add_submessages(action_response.messages)
         .add_attributes(action_response.attributes)
-        .add_events(action_response.events))
+        .add_events(action_response.events)
+        .add_submessages(hook_response.messages)
+        .add_events(hook_response.events))
 }
 
 fn execute_mint(
@@ -185,6 +252,12 @@ fn execute_mint(
         STATE.may_load(deps.storage)?.is_none(),
         ContractError::SaleStarted {}
     );
+    ensure!(
+        !LAZY_MINT.exists(deps.storage),
+        ContractError::InvalidLazyMint {
+            msg: "Tokens cannot be pre-minted while lazy minting".to_string(),
+        }
+    );
     let sale_conducted = SALE_CONDUCTED.load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
     ensure!(
@@ -249,6 +322,158 @@ fn mint(
         }))
 }
 
+fn execute_set_lazy_mint(
+    ctx: ExecuteContext,
+    lazy_mint: Option<LazyMint>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    ensure_can_modify_tiers(&deps, &info)?;
+    retire_lazy_mint(deps.storage)?;
+    let Some(lazy_mint) = lazy_mint else {
+        return Ok(Response::new().add_attribute("action", "remove_lazy_mint"));
+    };
+    lazy_mint.validate()?;
+    if let Some(next_id) =
+        LAZY_MINT_NEXT_IDS.may_load(deps.storage, &lazy_mint.token_id_template)?
+    {
+        ensure!(
+            lazy_mint.first_id >= next_id,
+            ContractError::InvalidLazyMint {
+                msg: format!("First id must be at least {next_id} as lower ids were already sold"),
+            }
+        );
+    }
+    ensure!(
+        NUMBER_OF_TOKENS_AVAILABLE.load(deps.storage)?.is_zero(),
+        ContractError::InvalidLazyMint {
+            msg: "Cannot lazy mint while pre-minted tokens are available".to_string(),
+        }
+    );
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.can_mint_after_sale || !SALE_CONDUCTED.load(deps.storage)?,
+        ContractError::CannotMintAfterSaleConducted {}
+    );
+    LAZY_MINT.save(deps.storage, &lazy_mint)?;
+    LAZY_MINT_SALES.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_lazy_mint")
+        .add_attribute("token_id_template", lazy_mint.token_id_template)
+        .add_attribute("first_id", lazy_mint.first_id)
+        .add_attribute("last_id", lazy_mint.last_id))
+}
+
+/// Removes the current lazy mint range, remembering which of its ids were sold.
+fn retire_lazy_mint(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let Some(lazy_mint) = LAZY_MINT.may_load(storage)? else {
+        return Ok(());
+    };
+    let sold_amount = LAZY_MINT_SALES.may_load(storage)?.unwrap_or_default();
+    if !sold_amount.is_zero() {
+        let next_id = lazy_mint.first_id.checked_add(sold_amount)?;
+        LAZY_MINT_NEXT_IDS.update(storage, &lazy_mint.token_id_template, |current| {
+            Ok::<_, ContractError>(current.map_or(next_id, |current| current.max(next_id)))
+        })?;
+    }
+    LAZY_MINT.remove(storage);
+    LAZY_MINT_SALES.remove(storage);
+    Ok(())
+}
+
+/// Tiers and the lazy mint range can only be modified by the owner while no sale is ongoing.
+fn ensure_can_modify_tiers(deps: &DepsMut, info: &MessageInfo) -> Result<(), ContractError> {
+    nonpayable(info)?;
+    ensure!(
+        ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        STATE.may_load(deps.storage)?.is_none(),
+        ContractError::SaleStarted {}
+    );
+    Ok(())
+}
+
+fn execute_add_tier(ctx: ExecuteContext, tier: Tier) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    ensure_can_modify_tiers(&deps, &info)?;
+    tier.validate()?;
+    ensure!(
+        !TIERS.has(deps.storage, tier.id.u64()),
+        ContractError::InvalidTier {
+            operation: "add".to_string(),
+            msg: format!("Tier with id {} already exists", tier.id),
+        }
+    );
+    TIERS.save(deps.storage, tier.id.u64(), &tier)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_tier")
+        .add_attribute("tier_id", tier.id)
+        .add_attribute("label", tier.label)
+        .add_attribute("price", tier.price))
+}
+
+fn execute_update_tier(ctx: ExecuteContext, tier: Tier) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    ensure_can_modify_tiers(&deps, &info)?;
+    tier.validate()?;
+    ensure!(
+        TIERS.has(deps.storage, tier.id.u64()),
+        ContractError::InvalidTier {
+            operation: "update".to_string(),
+            msg: format!("Tier with id {} does not exist", tier.id),
+        }
+    );
+    let sold_amount = TIER_SALES
+        .may_load(deps.storage, tier.id.u64())?
+        .unwrap_or_default();
+    ensure!(
+        tier.limit.map_or(true, |limit| limit >= sold_amount),
+        ContractError::InvalidTier {
+            operation: "update".to_string(),
+            msg: "Tier limit cannot be lower than the amount already sold".to_string(),
+        }
+    );
+    TIERS.save(deps.storage, tier.id.u64(), &tier)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_tier")
+        .add_attribute("tier_id", tier.id)
+        .add_attribute("label", tier.label)
+        .add_attribute("price", tier.price))
+}
+
+fn execute_remove_tier(ctx: ExecuteContext, tier_id: Uint64) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    ensure_can_modify_tiers(&deps, &info)?;
+    ensure!(
+        TIERS.has(deps.storage, tier_id.u64()),
+        ContractError::InvalidTier {
+            operation: "remove".to_string(),
+            msg: format!("Tier with id {tier_id} does not exist"),
+        }
+    );
+    // Token ids are derived from the amount sold, so a tier that has sold tokens is kept around
+    // to avoid minting duplicate ids if it were to be added again.
+    ensure!(
+        TIER_SALES
+            .may_load(deps.storage, tier_id.u64())?
+            .unwrap_or_default()
+            .is_zero(),
+        ContractError::InvalidTier {
+            operation: "remove".to_string(),
+            msg: "Cannot remove a tier that has sold tokens".to_string(),
+        }
+    );
+    TIERS.remove(deps.storage, tier_id.u64());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_tier")
+        .add_attribute("tier_id", tier_id))
+}
+
 fn execute_update_token_contract(
     ctx: ExecuteContext,
     address: AndrAddr,
@@ -287,13 +512,21 @@ fn execute_start_sale(
     ctx: ExecuteContext,
     start_time: Option<Milliseconds>,
     end_time: Milliseconds,
-    price: Coin,
-    min_tokens_sold: Uint128,
+    prices: Vec<Funds>,
+    pricing: Option<Pricing>,
+    soft_cap: Coin,
+    hard_cap: Option<Coin>,
+    referral_rate: Option<Decimal>,
     max_amount_per_wallet: Option<u32>,
     recipient: Recipient,
+    presale: Option<PresaleMsg>,
+    milestones: Option<Vec<MilestoneMsg>>,
 ) -> Result<Response, ContractError> {
     let ExecuteContext {
-        deps, info, env, ..
+        mut deps,
+        info,
+        env,
+        ..
     } = ctx;
     recipient.validate(&deps.as_ref())?;
     nonpayable(&info)?;
@@ -306,7 +539,7 @@ fn execute_start_sale(
         ContractError::Unauthorized {}
     );
     // If start time wasn't provided, it will be set as the current_time
-    let (start_expiration, _current_time) = get_and_validate_start_time(&env, start_time)?;
+    let start_expiration = get_start_expiration(&env, start_time)?;
 
     let end_expiration = expiration_from_milliseconds(end_time)?;
 
@@ -314,25 +547,74 @@ fn execute_start_sale(
         end_expiration > start_expiration,
         ContractError::StartTimeAfterEndTime {}
     );
+    validate_prices(&prices)?;
+    if let Some(pricing) = &pricing {
+        validate_pricing(pricing, &prices[0])?;
+    }
+    validate_funding_goal(&prices[0], &soft_cap, &hard_cap)?;
+    ensure!(
+        referral_rate.map_or(true, |rate| rate < Decimal::one()),
+        ContractError::InvalidRate {}
+    );
 
     SALE_CONDUCTED.save(deps.storage, &true)?;
     let state = STATE.may_load(deps.storage)?;
     ensure!(state.is_none(), ContractError::SaleStarted {});
+    clear_settled_escrow(deps.storage)?;
+    let campaign_id = Uint64::new(get_latest_campaign_id(deps.storage)?.map_or(1, |id| id + 1));
+    let milestones = milestones
+        .map(|milestones| save_milestones(&mut deps, milestones, end_expiration, &recipient))
+        .transpose()?;
     let max_amount_per_wallet = max_amount_per_wallet.unwrap_or(1u32);
+    let presale = presale
+        .map(|presale| {
+            get_presale(
+                &deps.as_ref(),
+                &env,
+                presale,
+                start_expiration,
+                &prices,
+                max_amount_per_wallet,
+            )
+        })
+        .transpose()?;
+    let presale_start = presale
+        .as_ref()
+        .map(|presale| ("presale_start_time", presale.start_time.to_string()));
 
-    // This is to prevent cloning price.
-    let price_str = price.to_string();
-    STATE.save(
+    let prices_str = prices
+        .iter()
+        .map(|price| funds_to_coin(price).to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    let state = State {
+        campaign_id,
+        start_time: start_expiration,
+        end_time: end_expiration,
+        prices,
+        pricing,
+        soft_cap: soft_cap.clone(),
+        hard_cap: hard_cap.clone(),
+        referral_rate,
+        max_amount_per_wallet,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient,
+        presale,
+        cancellation: None,
+    };
+    STATE.save(deps.storage, &state)?;
+    CAMPAIGNS.save(
         deps.storage,
-        &State {
-            end_time: end_expiration,
-            price,
-            min_tokens_sold,
-            max_amount_per_wallet,
-            amount_sold: Uint128::zero(),
-            amount_to_send: Uint128::zero(),
-            amount_transferred: Uint128::zero(),
-            recipient,
+        campaign_id.u64(),
+        &Campaign {
+            id: campaign_id,
+            state,
+            outcome: CampaignOutcome::Ongoing,
         },
     )?;
 
@@ -340,16 +622,488 @@ fn execute_start_sale(
 
     Ok(Response::new()
         .add_attribute("action", "start_sale")
+        .add_attribute("campaign_id", campaign_id)
         .add_attribute("start_time", start_expiration.to_string())
         .add_attribute("end_time", end_expiration.to_string())
-        .add_attribute("price", price_str)
-        .add_attribute("min_tokens_sold", min_tokens_sold)
-        .add_attribute("max_amount_per_wallet", max_amount_per_wallet.to_string()))
+        .add_attribute("prices", prices_str)
+        .add_attribute("soft_cap", soft_cap.to_string())
+        .add_attributes(hard_cap.map(|hard_cap| ("hard_cap", hard_cap.to_string())))
+        .add_attributes(referral_rate.map(|rate| ("referral_rate", rate.to_string())))
+        .add_attribute("max_amount_per_wallet", max_amount_per_wallet.to_string())
+        .add_attributes(presale_start)
+        .add_attributes(milestones.map(|milestones| ("milestones", milestones.to_string()))))
+}
+
+/// Removes the escrow and milestones of the previous sale. Fails if some milestones are still
+/// pending. Refunds of cancelled milestones that are not claimed yet remain claimable through the
+/// campaign of the escrow.
+fn clear_settled_escrow(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    if let Some(escrow) = ESCROW.may_load(storage)? {
+        ensure!(
+            get_pending_milestones(storage)?.is_empty(),
+            ContractError::InvalidMilestone {
+                msg: "Funds of the previous sale are still escrowed".to_string(),
+            }
+        );
+        let campaign_id = escrow.campaign_id.u64();
+        let is_claimed = ESCROW_CONTRIBUTIONS
+            .prefix(campaign_id)
+            .keys_raw(storage, None, None, Order::Ascending)
+            .next()
+            .is_none();
+        if escrow.cancelled && !is_claimed {
+            UNCLAIMED_ESCROWS.save(storage, campaign_id, &escrow)?;
+        } else {
+            clear_escrow_contributions(storage, campaign_id)?;
+        }
+        ESCROW.remove(storage);
+    }
+    MILESTONES.clear(storage);
+    Ok(())
+}
+
+/// Validates and saves the milestones, returning the number of milestones. The shares of the
+/// milestones have to add up to one and deadlines have to be after the end of the sale.
+fn save_milestones(
+    deps: &mut DepsMut,
+    milestones: Vec<MilestoneMsg>,
+    sale_end: Expiration,
+    sale_recipient: &Recipient,
+) -> Result<usize, ContractError> {
+    ensure!(
+        !milestones.is_empty(),
+        ContractError::InvalidMilestone {
+            msg: "At least one milestone must be provided".to_string(),
+        }
+    );
+    let number_of_milestones = milestones.len();
+    let mut total_percent = Decimal::zero();
+    for (i, milestone) in milestones.into_iter().enumerate() {
+        ensure!(
+            !milestone.description.is_empty(),
+            ContractError::InvalidMilestone {
+                msg: "Milestone description cannot be empty".to_string(),
+            }
+        );
+        ensure!(
+            !milestone.percent.is_zero(),
+            ContractError::InvalidMilestone {
+                msg: "Milestone percent must be greater than zero".to_string(),
+            }
+        );
+        total_percent = total_percent.checked_add(milestone.percent)?;
+
+        let recipient = match milestone.recipient {
+            Some(recipient) => {
+                recipient.validate(&deps.as_ref())?;
+                recipient
+            }
+            None => sale_recipient.clone(),
+        };
+        let deadline = milestone
+            .deadline
+            .map(expiration_from_milliseconds)
+            .transpose()?;
+        ensure!(
+            deadline.map_or(true, |deadline| deadline > sale_end),
+            ContractError::InvalidMilestone {
+                msg: "Milestone deadline must be after the end of the sale".to_string(),
+            }
+        );
+
+        let id = i as u64 + 1;
+        MILESTONES.save(
+            deps.storage,
+            id,
+            &Milestone {
+                id: Uint64::new(id),
+                description: milestone.description,
+                percent: milestone.percent,
+                recipient,
+                deadline,
+                status: MilestoneStatus::Pending,
+                released: vec![],
+            },
+        )?;
+    }
+    ensure!(
+        total_percent == Decimal::one(),
+        ContractError::InvalidMilestone {
+            msg: "Milestone percentages must add up to one".to_string(),
+        }
+    );
+    Ok(number_of_milestones)
+}
+
+/// Returns the expiration of the given start time after validating it is not in the past. Defaults
+/// to the current time so that purchases can be made right away.
+fn get_start_expiration(
+    env: &Env,
+    start_time: Option<Milliseconds>,
+) -> Result<Expiration, ContractError> {
+    let (start_expiration, current_time) = get_and_validate_start_time(env, start_time)?;
+    match start_time {
+        Some(_) => Ok(start_expiration),
+        None => expiration_from_milliseconds(current_time),
+    }
+}
+
+/// Validates the presale, which has to start before the sale and be priced in the same base denom.
+fn get_presale(
+    deps: &Deps,
+    env: &Env,
+    presale: PresaleMsg,
+    sale_start: Expiration,
+    sale_prices: &[Funds],
+    sale_max_amount_per_wallet: u32,
+) -> Result<Presale, ContractError> {
+    let start_time = get_start_expiration(env, presale.start_time)?;
+    ensure!(
+        start_time < sale_start,
+        ContractError::InvalidPresale {
+            msg: "Presale must start before the sale".to_string(),
+        }
+    );
+
+    match &presale.whitelist {
+        Whitelist::Addresses(addresses) => {
+            ensure!(
+                !addresses.is_empty(),
+                ContractError::InvalidPresale {
+                    msg: "Whitelist cannot be empty".to_string(),
+                }
+            );
+            for address in addresses {
+                deps.api.addr_validate(address)?;
+            }
+        }
+        Whitelist::AddressList(address_list) => {
+            address_list.get_raw_address(deps)?;
+        }
+    }
+
+    let prices = match presale.prices {
+        Some(prices) => {
+            validate_prices(&prices)?;
+            ensure!(
+                funds_denom(&prices[0]) == funds_denom(&sale_prices[0]),
+                ContractError::InvalidPresale {
+                    msg: "Presale base denom must be the same as the sale".to_string(),
+                }
+            );
+            prices
+        }
+        None => sale_prices.to_vec(),
+    };
+
+    Ok(Presale {
+        start_time,
+        whitelist: presale.whitelist,
+        prices,
+        max_amount_per_wallet: presale
+            .max_amount_per_wallet
+            .unwrap_or(sale_max_amount_per_wallet),
+    })
+}
+
+/// At least one price has to be provided and each denom can only be priced once. As the prices are
+/// used to convert between denoms, none of them can be zero when accepting multiple denoms.
+fn validate_prices(prices: &[Funds]) -> Result<(), ContractError> {
+    ensure!(
+        !prices.is_empty(),
+        ContractError::InvalidPrices {
+            msg: "At least one price must be provided".to_string(),
+        }
+    );
+    let mut denoms = HashSet::new();
+    for price in prices {
+        ensure!(
+            denoms.insert(funds_denom(price)),
+            ContractError::DuplicateCoinDenoms {}
+        );
+        ensure!(
+            prices.len() == 1 || !funds_to_coin(price).amount.is_zero(),
+            ContractError::InvalidPrices {
+                msg: "Prices must be greater than zero when accepting multiple denoms".to_string(),
+            }
+        );
+    }
+    Ok(())
+}
+
+/// The funding goal has to be denominated in the base denom and the soft cap cannot exceed the
+/// hard cap.
+fn validate_pricing(pricing: &Pricing, base_price: &Funds) -> Result<(), ContractError> {
+    match pricing {
+        Pricing::LinearDecay { floor_price } => ensure!(
+            *floor_price < funds_to_coin(base_price).amount,
+            ContractError::InvalidPrices {
+                msg: "Floor price must be lower than the base price".to_string(),
+            }
+        ),
+        Pricing::Step { step, increment } => ensure!(
+            !step.is_zero() && !increment.is_zero(),
+            ContractError::InvalidPrices {
+                msg: "Step and increment must be greater than zero".to_string(),
+            }
+        ),
+    }
+    Ok(())
+}
+
+/// Returns the accepted prices of each token at the current block. The base price follows the
+/// pricing of the sale and the other prices are converted from it.
+fn get_current_prices(state: &State, block: &BlockInfo) -> Result<Vec<Funds>, ContractError> {
+    let start_price = funds_to_coin(&state.prices[0]).amount;
+    let base_price = match &state.pricing {
+        None => return Ok(state.prices.clone()),
+        Some(Pricing::LinearDecay { floor_price }) => {
+            let decay = start_price
+                .checked_sub(*floor_price)?
+                .mul_floor(get_sale_progress(state, block));
+            start_price.checked_sub(decay)?
+        }
+        Some(Pricing::Step { step, increment }) => {
+            let steps = state
+                .amount_sold
+                .checked_div(*step)
+                .map_err(StdError::divide_by_zero)?;
+            start_price.checked_add(increment.checked_mul(steps)?)?
+        }
+    };
+    Ok(state
+        .prices
+        .iter()
+        .map(|price| funds_with_amount(price, convert_from_base(&state.prices, base_price, price)))
+        .collect())
+}
+
+/// Returns how far along the sale is, from zero when it starts to one when it ends.
+fn get_sale_progress(state: &State, block: &BlockInfo) -> Decimal {
+    let (elapsed, duration) = match (state.start_time, state.end_time) {
+        (Expiration::AtTime(start), Expiration::AtTime(end)) => (
+            block.time.nanos().saturating_sub(start.nanos()),
+            end.nanos().saturating_sub(start.nanos()),
+        ),
+        (Expiration::AtHeight(start), Expiration::AtHeight(end)) => (
+            block.height.saturating_sub(start),
+            end.saturating_sub(start),
+        ),
+        _ => return Decimal::zero(),
+    };
+    Decimal::checked_from_ratio(elapsed, duration)
+        .unwrap_or(Decimal::one())
+        .min(Decimal::one())
+}
+
+/// Returns how many tokens can still be purchased before the price goes up a step.
+fn get_max_tokens_at_current_price(state: &State) -> Result<u32, ContractError> {
+    let Some(Pricing::Step { step, .. }) = &state.pricing else {
+        return Ok(u32::MAX);
+    };
+    let max_tokens = step.checked_sub(
+        state
+            .amount_sold
+            .checked_rem(*step)
+            .map_err(StdError::divide_by_zero)?,
+    )?;
+    Ok(u32::try_from(max_tokens.u128()).unwrap_or(u32::MAX))
+}
+
+fn validate_funding_goal(
+    price: &Funds,
+    soft_cap: &Coin,
+    hard_cap: &Option<Coin>,
+) -> Result<(), ContractError> {
+    let price = funds_to_coin(price);
+    ensure!(
+        soft_cap.denom == price.denom,
+        ContractError::InvalidFundingGoal {
+            msg: "Soft cap must be in the base denom".to_string(),
+        }
+    );
+    if let Some(hard_cap) = hard_cap {
+        ensure!(
+            hard_cap.denom == price.denom,
+            ContractError::InvalidFundingGoal {
+                msg: "Hard cap must be in the base denom".to_string(),
+            }
+        );
+        ensure!(
+            hard_cap.amount >= soft_cap.amount && hard_cap.amount >= price.amount,
+            ContractError::InvalidFundingGoal {
+                msg: "Hard cap must be at least the soft cap and the price".to_string(),
+            }
+        );
+    }
+    Ok(())
+}
+
+/// Returns how many tokens of the given price can still be purchased without going over the hard
+/// cap.
+fn get_max_tokens_under_hard_cap(state: &State, price: Uint128) -> Result<u32, ContractError> {
+    let Some(hard_cap) = &state.hard_cap else {
+        return Ok(u32::MAX);
+    };
+    let remaining = hard_cap.amount.saturating_sub(state.amount_raised);
+    // Free tokens never raise anything, so they are not limited by the hard cap.
+    let max_tokens = remaining.checked_div(price).unwrap_or(Uint128::MAX);
+    // CHECK :: At least one more token fits under the hard cap.
+    ensure!(!max_tokens.is_zero(), ContractError::HardCapReached {});
+    Ok(u32::try_from(max_tokens.u128()).unwrap_or(u32::MAX))
+}
+
+/// The prices and per-wallet limit that apply to a purchase.
+struct SaleTerms {
+    prices: Vec<Funds>,
+    max_amount_per_wallet: u32,
+    /// How many tokens can be purchased before the prices change.
+    max_tokens_at_price: u32,
+}
+
+/// Returns the ongoing sale along with the terms `purchaser` can purchase tokens at. Before the
+/// sale starts only whitelisted addresses can purchase tokens, at the presale terms.
+fn get_ongoing_sale(
+    deps: &Deps,
+    env: &Env,
+    purchaser: &str,
+) -> Result<(State, SaleTerms), ContractError> {
+    let state = STATE.may_load(deps.storage)?;
+
+    // CHECK :: That there is an ongoing sale.
+    ensure!(state.is_some(), ContractError::NoOngoingSale {});
+
+    let state = state.unwrap();
+    ensure!(
+        !state.end_time.is_expired(&env.block),
+        ContractError::NoOngoingSale {}
+    );
+    ensure!(
+        state.cancellation.is_none(),
+        ContractError::SaleCancelled {}
+    );
+
+    if state.start_time.is_expired(&env.block) {
+        let terms = SaleTerms {
+            prices: get_current_prices(&state, &env.block)?,
+            max_amount_per_wallet: state.max_amount_per_wallet,
+            max_tokens_at_price: get_max_tokens_at_current_price(&state)?,
+        };
+        return Ok((state, terms));
+    }
+
+    // CHECK :: The presale has started and the purchaser is whitelisted.
+    let presale = state
+        .presale
+        .as_ref()
+        .filter(|presale| presale.start_time.is_expired(&env.block))
+        .ok_or(ContractError::SaleNotStarted {})?;
+    ensure!(
+        is_whitelisted(deps, &presale.whitelist, purchaser)?,
+        ContractError::Unauthorized {}
+    );
+    let terms = SaleTerms {
+        prices: presale.prices.clone(),
+        max_amount_per_wallet: presale.max_amount_per_wallet,
+        max_tokens_at_price: u32::MAX,
+    };
+    Ok((state, terms))
+}
+
+fn is_whitelisted(
+    deps: &Deps,
+    whitelist: &Whitelist,
+    address: &str,
+) -> Result<bool, ContractError> {
+    match whitelist {
+        Whitelist::Addresses(addresses) => Ok(addresses.iter().any(|addr| addr == address)),
+        Whitelist::AddressList(address_list) => {
+            let address_list = address_list.get_raw_address(deps)?;
+            let IncludesAddressResponse { included } = deps.querier.query_wasm_smart(
+                address_list.clone(),
+                &AddressListQueryMsg::IncludesAddress {
+                    address: address.to_owned(),
+                },
+            )?;
+            // Addresses included in an exclusive address list are the ones not permitted.
+            let is_inclusive: bool = deps
+                .querier
+                .query_wasm_smart(address_list, &AddressListQueryMsg::IsInclusive {})?;
+            Ok(included == is_inclusive)
+        }
+    }
+}
+
+/// Returns the accepted price of the native denom attached to the message along with the amount
+/// sent. Purchases can only be paid for in a single denom.
+fn get_native_payment(prices: &[Funds], funds: &[Coin]) -> Result<(Funds, Uint128), ContractError> {
+    ensure!(
+        prices.iter().any(|price| matches!(price, Funds::Native(_))),
+        ContractError::InvalidFunds {
+            msg: "Sale must be paid for with CW20 tokens".to_string(),
+        }
+    );
+    let mut payments = funds.iter().filter_map(|coin| {
+        get_accepted_price(prices, &coin.denom)
+            .filter(|price| matches!(price, Funds::Native(_)))
+            .map(|price| (price.clone(), coin.amount))
+    });
+    let payment = payments.next().ok_or(ContractError::InsufficientFunds {})?;
+    ensure!(
+        payments.next().is_none(),
+        ContractError::InvalidFunds {
+            msg: "Purchases must be paid for in a single denom".to_string(),
+        }
+    );
+    Ok(payment)
+}
+
+/// Returns the accepted price of the CW20 token at `token_address` along with the amount sent.
+fn get_cw20_payment(
+    prices: &[Funds],
+    token_address: &str,
+    amount: Uint128,
+) -> Result<(Funds, Uint128), ContractError> {
+    match get_accepted_price(prices, token_address) {
+        Some(price @ Funds::Cw20(_)) => Ok((price.clone(), amount)),
+        _ => Err(ContractError::InvalidFunds {
+            msg: format!("Sale cannot be paid for with {token_address}"),
+        }),
+    }
+}
+
+fn get_accepted_price<'a>(prices: &'a [Funds], denom: &str) -> Option<&'a Funds> {
+    prices.iter().find(|price| funds_denom(price) == denom)
+}
+
+/// Returns the sale or presale price of the given denom.
+fn get_sale_price<'a>(state: &'a State, denom: &str) -> Option<&'a Funds> {
+    get_accepted_price(&state.prices, denom).or_else(|| {
+        state
+            .presale
+            .as_ref()
+            .and_then(|presale| get_accepted_price(&presale.prices, denom))
+    })
+}
+
+/// Converts an amount in the base denom to the equivalent amount in the denom of `price`.
+fn convert_from_base(prices: &[Funds], base_amount: Uint128, price: &Funds) -> Uint128 {
+    let base_price = &prices[0];
+    if funds_denom(base_price) == funds_denom(price) {
+        return base_amount;
+    }
+    // Prices are validated to be non-zero when accepting multiple denoms.
+    base_amount.multiply_ratio(
+        funds_to_coin(price).amount,
+        funds_to_coin(base_price).amount,
+    )
 }
 
 fn execute_purchase_by_token_id(
     ctx: ExecuteContext,
     token_id: String,
+    refund_recipient: Option<Recipient>,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
     let ExecuteContext {
         mut deps,
@@ -358,16 +1112,10 @@ fn execute_purchase_by_token_id(
         ..
     } = ctx;
     let sender = info.sender.to_string();
-    let state = STATE.may_load(deps.storage)?;
-
-    // CHECK :: That there is an ongoing sale.
-    ensure!(state.is_some(), ContractError::NoOngoingSale {});
-
-    let mut state = state.unwrap();
-    ensure!(
-        !state.end_time.is_expired(&env.block),
-        ContractError::NoOngoingSale {}
-    );
+    let (mut state, terms) = get_ongoing_sale(&deps.as_ref(), &env, &sender)?;
+    let (price, amount_sent) = get_native_payment(&terms.prices, &info.funds)?;
+    let referrer = validate_referrer(&deps.as_ref(), &sender, referrer)?;
+    save_refund_recipient(&mut deps, &state, &sender, refund_recipient)?;
 
     let mut purchases = PURCHASES
         .may_load(deps.storage, &sender)?
@@ -378,17 +1126,26 @@ fn execute_purchase_by_token_id(
         ContractError::TokenNotAvailable {}
     );
 
-    let max_possible = state.max_amount_per_wallet - purchases.len() as u32;
+    let max_possible = terms
+        .max_amount_per_wallet
+        .saturating_sub(purchases.len() as u32);
 
     // CHECK :: The user is able to purchase these without going over the limit.
     ensure!(max_possible > 0, ContractError::PurchaseLimitReached {});
+    let base_price = funds_to_coin(&terms.prices[0]).amount;
+    get_max_tokens_under_hard_cap(&state, base_price)?;
 
     purchase_tokens(
         &mut deps,
         vec![token_id.clone()],
-        &info,
+        &sender,
+        amount_sent,
         &mut state,
         &mut purchases,
+        None,
+        price,
+        base_price,
+        referrer,
     )?;
 
     STATE.save(deps.storage, &state)?;
@@ -402,6 +1159,9 @@ fn execute_purchase_by_token_id(
 fn execute_purchase(
     ctx: ExecuteContext,
     number_of_tokens: Option<u32>,
+    tier_id: Option<Uint64>,
+    refund_recipient: Option<Recipient>,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
     let ExecuteContext {
         mut deps,
@@ -409,54 +1169,205 @@ fn execute_purchase(
         env,
         ..
     } = ctx;
-    let sender = info.sender.to_string();
-    let state = STATE.may_load(deps.storage)?;
+    let (state, terms) = get_ongoing_sale(&deps.as_ref(), &env, info.sender.as_str())?;
+    let (price, amount_sent) = get_native_payment(&terms.prices, &info.funds)?;
+    let referrer = validate_referrer(&deps.as_ref(), info.sender.as_str(), referrer)?;
+    save_refund_recipient(&mut deps, &state, info.sender.as_str(), refund_recipient)?;
 
-    // CHECK :: That there is an ongoing sale.
-    ensure!(state.is_some(), ContractError::NoOngoingSale {});
-
-    let mut state = state.unwrap();
-    ensure!(
-        !state.end_time.is_expired(&env.block),
-        ContractError::NoOngoingSale {}
-    );
+    purchase(
+        &mut deps,
+        state,
+        terms,
+        info.sender.to_string(),
+        price,
+        amount_sent,
+        number_of_tokens,
+        tier_id,
+        referrer,
+    )
+}
 
-    let mut purchases = PURCHASES
-        .may_load(deps.storage, &sender)?
+fn execute_receive(
+    ctx: ExecuteContext,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        mut deps,
+        info,
+        env,
+        ..
+    } = ctx;
+    nonpayable(&info)?;
+
+    match from_json(&receive_msg.msg)? {
+        Cw20HookMsg::Purchase {
+            number_of_tokens,
+            tier_id,
+            refund_recipient,
+            referrer,
+        } => {
+            let (state, terms) = get_ongoing_sale(&deps.as_ref(), &env, &receive_msg.sender)?;
+            let (price, amount_sent) =
+                get_cw20_payment(&terms.prices, info.sender.as_str(), receive_msg.amount)?;
+            let referrer = validate_referrer(&deps.as_ref(), &receive_msg.sender, referrer)?;
+            save_refund_recipient(&mut deps, &state, &receive_msg.sender, refund_recipient)?;
+
+            purchase(
+                &mut deps,
+                state,
+                terms,
+                receive_msg.sender,
+                price,
+                amount_sent,
+                number_of_tokens,
+                tier_id,
+                referrer,
+            )
+        }
+    }
+}
+
+/// Validates the address that referred `purchaser`, purchasers cannot refer themselves.
+fn validate_referrer(
+    deps: &Deps,
+    purchaser: &str,
+    referrer: Option<String>,
+) -> Result<Option<String>, ContractError> {
+    let Some(referrer) = referrer else {
+        return Ok(None);
+    };
+    ensure!(
+        referrer != purchaser,
+        ContractError::InvalidReferrer {
+            msg: "Purchasers cannot refer themselves".to_string(),
+        }
+    );
+    Ok(Some(deps.api.addr_validate(&referrer)?.into_string()))
+}
+
+/// Saves where the refunds of `purchaser` in the ongoing campaign are sent, if provided.
+fn save_refund_recipient(
+    deps: &mut DepsMut,
+    state: &State,
+    purchaser: &str,
+    refund_recipient: Option<Recipient>,
+) -> Result<(), ContractError> {
+    let Some(refund_recipient) = refund_recipient else {
+        return Ok(());
+    };
+    refund_recipient.address.validate(deps.api)?;
+    // Refunds are sent by the crowdfund, which is also the token minter, so a message attached to
+    // them would be executed with the crowdfund's authority.
+    ensure!(
+        refund_recipient.msg.is_none(),
+        ContractError::InvalidRecipientType {
+            msg: "Refund recipients cannot have a message".to_string(),
+        }
+    );
+    // Cross-chain addresses are resolved by the kernel once the refund is sent.
+    if refund_recipient.address.get_protocol().is_none() {
+        refund_recipient.address.get_raw_address(&deps.as_ref())?;
+    }
+    REFUND_RECIPIENTS.save(
+        deps.storage,
+        (state.campaign_id.u64(), purchaser),
+        &refund_recipient,
+    )?;
+    Ok(())
+}
+
+/// Purchases up to `number_of_tokens` for `purchaser` using `amount_sent` of the denom of `price`.
+/// Any funds that were not needed are refunded.
+#[allow(clippy::too_many_arguments)]
+fn purchase(
+    deps: &mut DepsMut,
+    mut state: State,
+    terms: SaleTerms,
+    purchaser: String,
+    price: Funds,
+    amount_sent: Uint128,
+    number_of_tokens: Option<u32>,
+    tier_id: Option<Uint64>,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut purchases = PURCHASES
+        .may_load(deps.storage, &purchaser)?
         .unwrap_or_default();
 
-    let max_possible = state.max_amount_per_wallet - purchases.len() as u32;
+    let max_possible = terms
+        .max_amount_per_wallet
+        .saturating_sub(purchases.len() as u32);
 
     // CHECK :: The user is able to purchase these without going over the limit.
     ensure!(max_possible > 0, ContractError::PurchaseLimitReached {});
 
+    let tier =
+        match tier_id {
+            Some(tier_id) => Some(TIERS.may_load(deps.storage, tier_id.u64())?.ok_or_else(
+                || ContractError::InvalidTier {
+                    operation: "purchase".to_string(),
+                    msg: format!("Tier with id {tier_id} does not exist"),
+                },
+            )?),
+            None => None,
+        };
+    let base_price = tier
+        .as_ref()
+        .map_or(funds_to_coin(&terms.prices[0]).amount, |tier| tier.price);
+    let price = match &tier {
+        Some(tier) => {
+            funds_with_amount(&price, convert_from_base(&terms.prices, tier.price, &price))
+        }
+        None => price,
+    };
+
+    // Purchases that would go over the hard cap are truncated and the excess funds are refunded.
+    let max_possible = cmp::min(
+        max_possible,
+        get_max_tokens_under_hard_cap(&state, base_price)?,
+    );
+    // Untiered purchases going over a price step are truncated the same way.
+    let max_possible = match &tier {
+        Some(_) => max_possible,
+        None => cmp::min(max_possible, terms.max_tokens_at_price),
+    };
     let number_of_tokens_wanted =
         number_of_tokens.map_or(max_possible, |n| cmp::min(n, max_possible));
 
     // The number of token ids here is equal to min(number_of_tokens_wanted, num_tokens_left).
-    let token_ids = get_available_tokens(deps.storage, None, Some(number_of_tokens_wanted))?;
+    let token_ids = match (&tier, LAZY_MINT.may_load(deps.storage)?) {
+        (Some(tier), _) => get_tier_token_ids(deps.storage, tier, number_of_tokens_wanted)?,
+        (None, Some(lazy_mint)) => {
+            get_lazy_token_ids(deps.storage, &lazy_mint, number_of_tokens_wanted)?
+        }
+        (None, None) => get_available_tokens(deps.storage, None, Some(number_of_tokens_wanted))?,
+    };
 
     let number_of_tokens_purchased = token_ids.len();
 
-    let required_payment =
-        purchase_tokens(&mut deps, token_ids, &info, &mut state, &mut purchases)?;
+    let required_payment = purchase_tokens(
+        deps,
+        token_ids,
+        &purchaser,
+        amount_sent,
+        &mut state,
+        &mut purchases,
+        tier.as_ref(),
+        price.clone(),
+        base_price,
+        referrer,
+    )?;
 
-    PURCHASES.save(deps.storage, &sender, &purchases)?;
+    PURCHASES.save(deps.storage, &purchaser, &purchases)?;
     STATE.save(deps.storage, &state)?;
 
     // Refund user if they sent more. This can happen near the end of the sale when they weren't
     // able to get the amount that they wanted.
-    let mut funds = info.funds;
-    deduct_funds(&mut funds, &required_payment)?;
-
-    // If any funds were remaining after deduction, send refund.
-    let resp = if has_coins(&funds, &Coin::new(1, state.price.denom)) {
-        Response::new().add_message(BankMsg::Send {
-            to_address: sender,
-            amount: funds,
-        })
-    } else {
+    let refund_amount = amount_sent.checked_sub(required_payment)?;
+    let resp = if refund_amount.is_zero() {
         Response::new()
+    } else {
+        Response::new().add_message(generate_transfer_msg(&price, purchaser, refund_amount)?)
     };
 
     Ok(resp
@@ -471,25 +1382,36 @@ fn execute_purchase(
         ))
 }
 
+/// Records the purchase of the given tokens at `price` each, which is worth `base_price` in the
+/// base denom, and credits it to the `referrer` if any. Tokens purchased from a `tier` or the lazy
+/// mint range are minted once the sale succeeds, otherwise they are removed from the pre-minted
+/// tokens available for sale.
+///
+/// Returns the amount of the denom of `price` required to pay for the tokens, including taxes.
+#[allow(clippy::too_many_arguments)]
 fn purchase_tokens(
     deps: &mut DepsMut,
     token_ids: Vec<String>,
-    info: &MessageInfo,
+    purchaser: &str,
+    amount_sent: Uint128,
     state: &mut State,
     purchases: &mut Vec<Purchase>,
-) -> Result<Coin, ContractError> {
+    tier: Option<&Tier>,
+    price: Funds,
+    base_price: Uint128,
+    referrer: Option<String>,
+) -> Result<Uint128, ContractError> {
     // CHECK :: There are any tokens left to purchase.
     ensure!(!token_ids.is_empty(), ContractError::AllTokensPurchased {});
 
     let number_of_tokens_purchased = token_ids.len();
+    let denom = funds_denom(&price).to_owned();
+    let price_amount = funds_to_coin(&price).amount;
 
     // CHECK :: The user has sent enough funds to cover the base fee (without any taxes).
-    let total_cost = Coin::new(
-        state.price.amount.u128() * number_of_tokens_purchased as u128,
-        state.price.denom.clone(),
-    );
+    let total_cost = price_amount.checked_mul(Uint128::from(number_of_tokens_purchased as u128))?;
     ensure!(
-        has_coins(&info.funds, &total_cost),
+        amount_sent >= total_cost,
         ContractError::InsufficientFunds {}
     );
 
@@ -498,79 +1420,207 @@ fn purchase_tokens(
     // This is the same for each token, so we only need to do it once.
     let (msgs, _events, remainder) = ADOContract::default().on_funds_transfer(
         &deps.as_ref(),
-        info.sender.to_string(),
-        Funds::Native(state.price.clone()),
+        purchaser.to_owned(),
+        price,
         encode_binary(&"")?,
     )?;
 
+    let is_lazy = tier.is_none() && LAZY_MINT.exists(deps.storage);
     let mut current_number = NUMBER_OF_TOKENS_AVAILABLE.load(deps.storage)?;
+    let mut purchase_history = vec![];
     for token_id in token_ids {
-        let remaining_amount = remainder.try_get_coin()?;
+        let remaining_amount = funds_to_coin(&remainder).amount;
 
-        let tax_amount = get_tax_amount(&msgs, state.price.amount, remaining_amount.amount);
+        let tax_amount = get_tax_amount(&msgs, price_amount, remaining_amount);
 
         let purchase = Purchase {
             token_id: token_id.clone(),
+            tier_id: tier.map(|tier| tier.id),
+            denom: denom.clone(),
+            price: price_amount,
             tax_amount,
             msgs: msgs.clone(),
-            purchaser: info.sender.to_string(),
+            purchaser: purchaser.to_owned(),
+            referrer: referrer.clone(),
         };
         total_tax_amount = total_tax_amount.checked_add(tax_amount)?;
+        purchase_history.push(PurchaseResponse {
+            token_id: token_id.clone(),
+            tier_id: purchase.tier_id,
+            denom: denom.clone(),
+            price: price_amount,
+            tax_amount,
+        });
 
-        state.amount_to_send = state.amount_to_send.checked_add(remaining_amount.amount)?;
+        add_to_coins(&mut state.amount_to_send, &denom, remaining_amount)?;
+        add_to_coins(&mut state.amounts_raised, &denom, price_amount)?;
         state.amount_sold = state.amount_sold.checked_add(Uint128::one())?;
+        state.amount_raised = state.amount_raised.checked_add(base_price)?;
 
         purchases.push(purchase);
 
-        AVAILABLE_TOKENS.remove(deps.storage, &token_id);
-        current_number = current_number.checked_sub(Uint128::one())?;
+        if tier.is_none() && !is_lazy {
+            AVAILABLE_TOKENS.remove(deps.storage, &token_id);
+            current_number = current_number.checked_sub(Uint128::one())?;
+        }
     }
     NUMBER_OF_TOKENS_AVAILABLE.save(deps.storage, &current_number)?;
+    if is_lazy {
+        LAZY_MINT_SALES.update(deps.storage, |sold| {
+            Ok::<_, ContractError>(
+                sold.checked_add(Uint128::from(number_of_tokens_purchased as u128))?,
+            )
+        })?;
+    }
+    if let Some(tier) = tier {
+        TIER_SALES.update(deps.storage, tier.id.u64(), |sold| {
+            Ok::<_, ContractError>(
+                sold.unwrap_or_default()
+                    .checked_add(Uint128::from(number_of_tokens_purchased as u128))?,
+            )
+        })?;
+    }
 
     // CHECK :: User has sent enough to cover taxes.
-    let required_payment = Coin {
-        denom: state.price.denom.clone(),
-        amount: state
-            .price
-            .amount
-            .checked_mul(Uint128::from(number_of_tokens_purchased as u128))?
-            .checked_add(total_tax_amount)?,
-    };
+    let required_payment = total_cost.checked_add(total_tax_amount)?;
     ensure!(
-        has_coins(&info.funds, &required_payment),
+        amount_sent >= required_payment,
         ContractError::InsufficientFunds {}
     );
+
+    add_to_coins(&mut state.taxes_paid, &denom, total_tax_amount)?;
+    let campaign_id = state.campaign_id.u64();
+    CAMPAIGN_PURCHASES.update(deps.storage, (campaign_id, purchaser), |purchases| {
+        let mut purchases = purchases.unwrap_or_default();
+        purchases.extend(purchase_history);
+        Ok::<_, ContractError>(purchases)
+    })?;
+    contributors().update(deps.storage, (campaign_id, purchaser), |contributor| {
+        let mut contributor = contributor.unwrap_or(Contributor {
+            purchaser: purchaser.to_owned(),
+            number_of_tokens: 0,
+            amount: Uint128::zero(),
+            amounts_paid: vec![],
+            taxes_paid: vec![],
+        });
+        contributor.number_of_tokens += number_of_tokens_purchased as u32;
+        contributor.amount = contributor.amount.checked_add(
+            base_price.checked_mul(Uint128::from(number_of_tokens_purchased as u128))?,
+        )?;
+        add_to_coins(&mut contributor.amounts_paid, &denom, total_cost)?;
+        add_to_coins(&mut contributor.taxes_paid, &denom, total_tax_amount)?;
+        Ok::<_, ContractError>(contributor)
+    })?;
+    if let Some(referrer) = referrer {
+        REFERRALS.update(deps.storage, (campaign_id, &referrer), |referral| {
+            let mut referral = referral.unwrap_or(Referral {
+                referrer: referrer.clone(),
+                number_of_tokens: 0,
+                volume: vec![],
+                rewards_paid: vec![],
+            });
+            referral.number_of_tokens += number_of_tokens_purchased as u32;
+            add_to_coins(&mut referral.volume, &denom, total_cost)?;
+            Ok::<_, ContractError>(referral)
+        })?;
+    }
     Ok(required_payment)
 }
 
-fn execute_claim_refund(ctx: ExecuteContext) -> Result<Response, ContractError> {
+fn execute_claim_refund(
+    ctx: ExecuteContext,
+    campaign_id: Option<Uint64>,
+) -> Result<Response, ContractError> {
     let ExecuteContext {
-        deps, info, env, ..
+        mut deps,
+        info,
+        env,
+        ..
     } = ctx;
     nonpayable(&info)?;
 
-    let state = STATE.may_load(deps.storage)?;
-    ensure!(state.is_some(), ContractError::NoOngoingSale {});
-    let state = state.unwrap();
+    let state = match STATE.may_load(deps.storage)? {
+        Some(state) if campaign_id.is_none() => state,
+        // Outside of a sale, or for previous campaigns, purchasers can only claim their share of
+        // cancelled milestones.
+        _ => {
+            let refund_msgs =
+                process_escrow_refund(&mut deps, &env, info.sender.as_str(), campaign_id)?;
+            return Ok(Response::new()
+                .add_submessages(refund_msgs)
+                .add_attribute("action", "claim_refund"));
+        }
+    };
+    // Cancelled sales can be refunded right away.
+    let is_cancelled = state.cancellation.is_some();
     ensure!(
-        state.end_time.is_expired(&env.block),
+        is_cancelled || state.end_time.is_expired(&env.block),
         ContractError::SaleNotEnded {}
     );
     ensure!(
-        state.amount_sold < state.min_tokens_sold,
+        is_cancelled || state.amount_raised < state.soft_cap.amount,
         ContractError::MinSalesExceeded {}
     );
 
     let purchases = PURCHASES.may_load(deps.storage, info.sender.as_str())?;
     ensure!(purchases.is_some(), ContractError::NoPurchases {});
     let purchases = purchases.unwrap();
-    let refund_msg = process_refund(deps.storage, &purchases, &state.price);
-    let mut resp = Response::new();
-    if let Some(refund_msg) = refund_msg {
-        resp = resp.add_message(refund_msg);
+    let (refund_msgs, refund_event) = process_refund(&mut deps, &env, &purchases, &state)?;
+
+    Ok(Response::new()
+        .add_submessages(refund_msgs)
+        .add_attribute("action", "claim_refund")
+        .add_event(refund_event))
+}
+
+fn execute_cancel_sale(
+    ctx: ExecuteContext,
+    return_unsold_to: Option<AndrAddr>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
+    nonpayable(&info)?;
+    ensure!(
+        ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+
+    let mut state = STATE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoOngoingSale {})?;
+    ensure!(
+        state.cancellation.is_none(),
+        ContractError::SaleCancelled {}
+    );
+    // Once tokens are transferred to purchasers the sale has gone through.
+    ensure!(
+        state.amount_transferred.is_zero(),
+        ContractError::SaleExecuted {}
+    );
+    if let Some(return_unsold_to) = &return_unsold_to {
+        return_unsold_to.get_raw_address(&deps.as_ref())?;
     }
 
-    Ok(resp.add_attribute("action", "claim_refund"))
+    let cancelled_at = Milliseconds::from_nanos(env.block.time.nanos());
+    let event = Event::new("sale_cancelled")
+        .add_attribute("cancelled_at", cancelled_at.to_string())
+        .add_attribute("amount_sold", state.amount_sold)
+        .add_attribute("amount_raised", state.amount_raised)
+        .add_attributes(
+            return_unsold_to
+                .as_ref()
+                .map(|address| ("return_unsold_to", address.to_string())),
+        );
+    state.cancellation = Some(Cancellation {
+        cancelled_at,
+        return_unsold_to,
+    });
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_sale")
+        .add_event(event))
 }
 
 fn execute_end_sale(ctx: ExecuteContext, limit: Option<u32>) -> Result<Response, ContractError> {
@@ -586,19 +1636,28 @@ fn execute_end_sale(ctx: ExecuteContext, limit: Option<u32>) -> Result<Response,
     ensure!(state.is_some(), ContractError::NoOngoingSale {});
     let state = state.unwrap();
     let number_of_tokens_available = NUMBER_OF_TOKENS_AVAILABLE.load(deps.storage)?;
-    // In case the minimum sold tokens threshold is met, it has to be the owner who calls the function
+    // In case only the soft cap is met, it has to be the owner who calls the function
     let contract = ADOContract::default();
-    let has_minimum_sold = state.min_tokens_sold <= state.amount_sold;
+    let soft_cap_reached = state.soft_cap.amount <= state.amount_raised;
+    let hard_cap_reached = state
+        .hard_cap
+        .as_ref()
+        .map_or(false, |hard_cap| hard_cap.amount <= state.amount_raised);
     let is_owner = contract.is_contract_owner(deps.storage, info.sender.as_str())?;
+    let is_cancelled = state.cancellation.is_some();
 
     ensure!(
-        // If all tokens have been sold the sale can be ended too.
-        state.end_time.is_expired(&env.block)
-            || number_of_tokens_available.is_zero()
-            || (has_minimum_sold && is_owner),
+        // If all tokens have been sold or the hard cap is reached the sale can be ended too.
+        is_cancelled
+            || state.end_time.is_expired(&env.block)
+            || (number_of_tokens_available.is_zero()
+                && lazy_mint_sold_out(deps.storage)?
+                && all_tiers_sold_out(deps.storage)?)
+            || hard_cap_reached
+            || (soft_cap_reached && is_owner),
         ContractError::SaleNotEnded {}
     );
-    if state.amount_sold < state.min_tokens_sold {
+    if is_cancelled || !soft_cap_reached {
         issue_refunds_and_burn_tokens(&mut deps, env, limit)
     } else {
         transfer_tokens_and_send_funds(
@@ -613,6 +1672,10 @@ fn execute_end_sale(ctx: ExecuteContext, limit: Option<u32>) -> Result<Response,
     }
 }
 
+fn lazy_mint_sold_out(storage: &dyn Storage) -> Result<bool, ContractError> {
+    Ok(get_lazy_mint_response(storage)?.map_or(true, |response| response.remaining.is_zero()))
+}
+
 fn issue_refunds_and_burn_tokens(
     deps: &mut DepsMut,
     env: Env,
@@ -621,7 +1684,8 @@ fn issue_refunds_and_burn_tokens(
     let state = STATE.load(deps.storage)?;
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     ensure!(limit > 0, ContractError::LimitMustNotBeZero {});
-    let mut refund_msgs: Vec<CosmosMsg> = vec![];
+    let mut refund_msgs: Vec<SubMsg> = vec![];
+    let mut refund_events: Vec<Event> = vec![];
     // Issue refunds for `limit` number of users.
     let purchases: Vec<Vec<Purchase>> = PURCHASES
         .range(deps.storage, None, None, Order::Ascending)
@@ -630,24 +1694,42 @@ fn issue_refunds_and_burn_tokens(
         .map(|(_v, p)| p)
         .collect();
     for purchase_vec in purchases.iter() {
-        let refund_msg = process_refund(deps.storage, purchase_vec, &state.price);
-        if let Some(refund_msg) = refund_msg {
-            refund_msgs.push(refund_msg);
-        }
+        let (msgs, event) = process_refund(deps, &env, purchase_vec, &state)?;
+        refund_msgs.extend(msgs);
+        refund_events.push(event);
     }
 
-    // Burn `limit` number of tokens
-    let burn_msgs = get_burn_messages(deps, env.contract.address.to_string(), limit)?;
+    // Burn `limit` number of tokens, or return them to the owner's chosen address if the sale was
+    // cancelled.
+    let return_unsold_to = state
+        .cancellation
+        .as_ref()
+        .and_then(|cancellation| cancellation.return_unsold_to.clone())
+        .map(|address| address.get_raw_address(&deps.as_ref()))
+        .transpose()?;
+    let burn_msgs = get_burn_messages(
+        deps,
+        env.contract.address.to_string(),
+        limit,
+        return_unsold_to,
+    )?;
 
     if burn_msgs.is_empty() && purchases.is_empty() {
         // When all tokens have been burned and all purchases have been refunded, the sale is over.
-        clear_state(deps.storage)?;
+        let outcome = if state.cancellation.is_some() {
+            CampaignOutcome::Cancelled
+        } else {
+            CampaignOutcome::Failed
+        };
+        clear_state(deps.storage, outcome)?;
+        MILESTONES.clear(deps.storage);
     }
 
     Ok(Response::new()
         .add_attribute("action", "issue_refunds_and_burn_tokens")
-        .add_messages(refund_msgs)
-        .add_messages(burn_msgs))
+        .add_submessages(refund_msgs)
+        .add_messages(burn_msgs)
+        .add_events(refund_events))
 }
 
 fn transfer_tokens_and_send_funds(
@@ -665,53 +1747,52 @@ fn transfer_tokens_and_send_funds(
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
 
     ensure!(limit > 0, ContractError::LimitMustNotBeZero {});
-    // Send the funds if they haven't been sent yet and if all of the tokens have been transferred.
-    let mut pkt = match ctx.amp_ctx {
-        Some(pkt) => pkt,
-        None => AMPPkt::new(info.sender, env.contract.address.clone(), vec![]),
-    };
+    // Purchases of sales with milestones are tracked to be able to refund the escrowed funds.
+    let is_escrowed = !MILESTONES.is_empty(deps.storage);
 
+    // Send the funds if they haven't been sent yet and if all of the tokens have been transferred.
     if state.amount_transferred == state.amount_sold {
-        if state.amount_to_send > Uint128::zero() {
-            let funds = vec![Coin {
-                denom: state.price.denom.clone(),
-                amount: state.amount_to_send,
-            }];
-            match state.recipient.msg {
-                None => {
-                    resp = resp.add_submessage(
-                        state.recipient.generate_direct_msg(&deps.as_ref(), funds)?,
-                    );
-                }
-                Some(_) => {
-                    let amp_message = state
-                        .recipient
-                        .generate_amp_msg(&deps.as_ref(), Some(funds))
-                        .unwrap();
-                    pkt = pkt.add_message(amp_message);
-                    let kernel_address = ADOContract::default().get_kernel_address(deps.storage)?;
-                    let sub_msg = pkt.to_sub_msg(
-                        kernel_address,
-                        Some(coins(
-                            state.amount_to_send.u128(),
-                            state.price.denom.clone(),
-                        )),
-                        1,
-                    )?;
-                    resp = resp.add_submessage(sub_msg);
-                }
+        if !state.amount_to_send.is_empty() {
+            let prices = get_all_prices(&state);
+            if is_escrowed {
+                // The funds are held until they are released through the milestones.
+                ESCROW.save(
+                    deps.storage,
+                    &Escrow {
+                        campaign_id: state.campaign_id,
+                        total: state.amount_to_send.clone(),
+                        released: vec![],
+                        contributions: state.amounts_raised.clone(),
+                        refundable: vec![],
+                        prices,
+                        cancelled: false,
+                    },
+                )?;
+            } else {
+                let pkt = match ctx.amp_ctx {
+                    Some(pkt) => pkt,
+                    None => AMPPkt::new(info.sender, env.contract.address.clone(), vec![]),
+                };
+                resp = resp.add_submessages(generate_payout_msgs(
+                    &deps.as_ref(),
+                    &state.recipient,
+                    &state.amount_to_send,
+                    &prices,
+                    pkt,
+                )?);
             }
-            state.amount_to_send = Uint128::zero();
+            state.amount_to_send = vec![];
             STATE.save(deps.storage, &state)?;
         }
         // Once all purchased tokens have been transferred, begin burning `limit` number of tokens
         // that were not purchased.
-        let burn_msgs = get_burn_messages(&mut deps, env.contract.address.to_string(), limit)?;
+        let burn_msgs =
+            get_burn_messages(&mut deps, env.contract.address.to_string(), limit, None)?;
 
         if burn_msgs.is_empty() {
             // When burn messages are empty, we have finished the sale, which is represented by
             // having no State.
-            clear_state(deps.storage)?;
+            clear_state(deps.storage, CampaignOutcome::Succeeded)?;
         } else {
             resp = resp.add_messages(burn_msgs);
         }
@@ -731,6 +1812,7 @@ fn transfer_tokens_and_send_funds(
     let config = CONFIG.load(deps.storage)?;
     let mut rate_messages: Vec<SubMsg> = vec![];
     let mut transfer_msgs: Vec<CosmosMsg> = vec![];
+    let mut referral_rewards: BTreeMap<String, Vec<Coin>> = BTreeMap::new();
 
     let last_purchaser = if purchases.len() == 1 {
         purchases[0].purchaser.clone()
@@ -754,6 +1836,7 @@ fn transfer_tokens_and_send_funds(
 
     // Resolve the token contract address from the VFS
     let token_contract_address = config.token_address.get_raw_address(&deps.as_ref())?;
+    let lazy_mint = LAZY_MINT.may_load(deps.storage)?;
     for purchase in purchases.into_iter() {
         let purchaser = purchase.purchaser;
         let should_remove = purchaser != last_purchaser || remove_last_purchaser;
@@ -764,17 +1847,86 @@ fn transfer_tokens_and_send_funds(
             // at the end, if not all of them were removed.
             number_of_last_purchases_removed += 1;
         }
+        if let (Some(referrer), Some(referral_rate)) = (purchase.referrer, state.referral_rate) {
+            // Rewards are taken from what is left of the price after royalties, so they can never
+            // exceed the amount owed to the recipient. With the base amount as the remainder, the
+            // rate messages add up to the taxes plus the royalties.
+            let royalties = get_tax_amount(&purchase.msgs, purchase.price, purchase.price)
+                .checked_sub(purchase.tax_amount)?;
+            add_to_coins(
+                referral_rewards.entry(referrer).or_default(),
+                &purchase.denom,
+                purchase
+                    .price
+                    .checked_sub(royalties)?
+                    .mul_floor(referral_rate),
+            )?;
+        }
         rate_messages.extend(purchase.msgs);
-        transfer_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: token_contract_address.to_string(),
-            msg: encode_binary(&Cw721ExecuteMsg::TransferNft {
+        if is_escrowed {
+            ESCROW_CONTRIBUTIONS.update(
+                deps.storage,
+                (state.campaign_id.u64(), &purchaser),
+                |contributions| {
+                    let mut contributions = contributions.unwrap_or_default();
+                    add_to_coins(&mut contributions, &purchase.denom, purchase.price)?;
+                    Ok::<_, ContractError>(contributions)
+                },
+            )?;
+        }
+        // Tier and lazy mint tokens do not exist yet and are minted directly to the purchaser.
+        let lazy_number = lazy_mint
+            .as_ref()
+            .filter(|_| purchase.tier_id.is_none())
+            .and_then(|lazy_mint| Some((lazy_mint, lazy_mint.token_number(&purchase.token_id)?)));
+        let msg = match (purchase.tier_id, lazy_number) {
+            (Some(tier_id), _) => {
+                let tier = TIERS.load(deps.storage, tier_id.u64())?;
+                Cw721ExecuteMsg::Mint {
+                    token_id: purchase.token_id,
+                    owner: purchaser,
+                    token_uri: tier.metadata.token_uri,
+                    extension: tier.metadata.extension,
+                }
+            }
+            (None, Some((lazy_mint, number))) => Cw721ExecuteMsg::Mint {
+                token_id: purchase.token_id,
+                owner: purchaser,
+                token_uri: lazy_mint.token_uri(number),
+                extension: lazy_mint.extension.clone(),
+            },
+            (None, None) => Cw721ExecuteMsg::TransferNft {
                 recipient: AndrAddr::from_string(purchaser),
                 token_id: purchase.token_id,
-            })?,
+            },
+        };
+        transfer_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token_contract_address.to_string(),
+            msg: encode_binary(&msg)?,
             funds: vec![],
         }));
         state.amount_transferred = state.amount_transferred.checked_add(Uint128::one())?;
     }
+    // Referrers are paid out of the raised funds, before the remainder is sent to the recipient.
+    let mut referral_msgs: Vec<CosmosMsg> = vec![];
+    for (referrer, rewards) in referral_rewards {
+        for reward in rewards.iter() {
+            // The price is guaranteed to exist as purchases can only be made in accepted denoms.
+            let price = get_sale_price(&state, &reward.denom).unwrap();
+            referral_msgs.push(generate_transfer_msg(
+                price,
+                referrer.clone(),
+                reward.amount,
+            )?);
+        }
+        state.amount_to_send = subtract_coins(&state.amount_to_send, &rewards)?;
+        let key = (state.campaign_id.u64(), referrer.as_str());
+        let mut referral = REFERRALS.load(deps.storage, key)?;
+        for reward in rewards.iter() {
+            add_to_coins(&mut referral.rewards_paid, &reward.denom, reward.amount)?;
+        }
+        REFERRALS.save(deps.storage, key, &referral)?;
+    }
     // If the last purchaser wasn't removed, remove the subset of purchases that were processed.
     if PURCHASES.has(deps.storage, &last_purchaser) {
         let last_purchases = PURCHASES.load(deps.storage, &last_purchaser)?;
@@ -789,54 +1941,507 @@ fn transfer_tokens_and_send_funds(
     Ok(resp
         .add_attribute("action", "transfer_tokens_and_send_funds")
         .add_messages(transfer_msgs)
+        .add_messages(referral_msgs)
         .add_submessages(merge_sub_msgs(rate_messages)))
 }
 
-/// Processes a vector of purchases for the SAME user by merging all funds into a single BankMsg.
-/// The given purchaser is then removed from `PURCHASES`.
+/// Processes a vector of purchases for the SAME user by merging all funds of the same denom into a
+/// single message. The given purchaser is then removed from `PURCHASES` and any tier tokens they
+/// purchased are made available again.
 ///
 /// ## Arguments
-/// * `storage`  - Mutable reference to Storage
-/// * `purchase` - Vector of purchases for the same user to issue a refund message for.
-/// * `price`    - The price of a token
+/// * `deps`     - Mutable reference to the dependencies
+/// * `env`      - The environment, used to send refunds through the kernel
+/// * `purchase` - Vector of purchases for the same user to issue refund messages for.
+/// * `state`    - The state of the sale the purchases were made in
 ///
-/// Returns an `Option<CosmosMsg>` which is `None` when the amount to refund is zero.
+/// Returns the messages refunding each denom with a non-zero amount and a `refund` event.
 fn process_refund(
-    storage: &mut dyn Storage,
+    deps: &mut DepsMut,
+    env: &Env,
     purchases: &[Purchase],
-    price: &Coin,
-) -> Option<CosmosMsg> {
+    state: &State,
+) -> Result<(Vec<SubMsg>, Event), ContractError> {
     let purchaser = purchases[0].purchaser.clone();
     // Remove each entry as they get processed.
-    PURCHASES.remove(storage, &purchaser);
+    PURCHASES.remove(deps.storage, &purchaser);
     // Reduce a user's purchases into one message. While the tax paid on each item should
     // be the same, it is not guaranteed given that the rates module is mutable during the
     // sale.
-    let amount = purchases
-        .iter()
+    let mut amounts: Vec<Coin> = vec![];
+    let lazy_mint = LAZY_MINT.may_load(deps.storage)?;
+    for purchase in purchases {
+        if let Some(tier_id) = purchase.tier_id {
+            TIER_SALES.update(deps.storage, tier_id.u64(), |sold| {
+                Ok::<_, ContractError>(sold.unwrap_or_default().checked_sub(Uint128::one())?)
+            })?;
+        } else if lazy_mint
+            .as_ref()
+            .and_then(|lazy_mint| lazy_mint.token_number(&purchase.token_id))
+            .is_some()
+        {
+            // The token was never minted, so it is made available again.
+            LAZY_MINT_SALES.update(deps.storage, |sold| {
+                Ok::<_, ContractError>(sold.checked_sub(Uint128::one())?)
+            })?;
+        }
         // This represents the total amount of funds they sent for each purchase.
-        .map(|p| p.tax_amount + price.amount)
-        // Adds up all of the purchases.
-        .reduce(|accum, item| accum + item)
-        .unwrap_or_else(Uint128::zero);
-
-    if amount > Uint128::zero() {
-        Some(CosmosMsg::Bank(BankMsg::Send {
-            to_address: purchaser,
-            amount: vec![Coin {
-                denom: price.denom.clone(),
+        add_to_coins(
+            &mut amounts,
+            &purchase.denom,
+            purchase.tax_amount.checked_add(purchase.price)?,
+        )?;
+    }
+
+    let event = Event::new("refund")
+        .add_attribute("purchaser", &purchaser)
+        .add_attribute("amount", coins_to_string(&amounts));
+    let refunds = amounts
+        .into_iter()
+        .map(|coin| {
+            // The price is guaranteed to exist as purchases can only be made in accepted denoms.
+            let price = get_sale_price(state, &coin.denom).unwrap();
+            (price, coin.amount)
+        })
+        .collect();
+    let refund_recipient =
+        REFUND_RECIPIENTS.may_load(deps.storage, (state.campaign_id.u64(), &purchaser))?;
+    let msgs = generate_refund_msgs(deps, env, &purchaser, refund_recipient, refunds)?;
+    Ok((msgs, event))
+}
+
+/// Generates the messages sending `refunds` to the purchaser's refund recipient, or directly to
+/// the purchaser if they did not provide one. Native funds are sent to refund recipients through
+/// the kernel so that they can be VFS paths or cross-chain addresses, CW20 tokens are sent
+/// directly.
+fn generate_refund_msgs(
+    deps: &mut DepsMut,
+    env: &Env,
+    purchaser: &str,
+    refund_recipient: Option<Recipient>,
+    refunds: Vec<(&Funds, Uint128)>,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let refunds: Vec<Funds> = refunds
+        .into_iter()
+        .map(|(price, amount)| funds_with_amount(price, amount))
+        .collect();
+    let Some(refund_recipient) = refund_recipient else {
+        return generate_direct_refund_msgs(purchaser, &refunds);
+    };
+
+    let mut msgs = vec![];
+    let mut native_refunds = vec![];
+    for refund in refunds {
+        match &refund {
+            Funds::Native(_) => native_refunds.push(refund),
+            Funds::Cw20(cw20_coin) => {
+                let msg = refund_recipient.generate_msg_cw20(&deps.as_ref(), cw20_coin.clone());
+                msgs.extend(generate_best_effort_refund_msgs(
+                    deps,
+                    purchaser,
+                    msg,
+                    vec![refund],
+                )?);
+            }
+        }
+    }
+    if !native_refunds.is_empty() {
+        let native_funds: Vec<Coin> = native_refunds.iter().map(funds_to_coin).collect();
+        let msg = generate_native_refund_msg(&deps.as_ref(), env, &refund_recipient, native_funds);
+        msgs.extend(generate_best_effort_refund_msgs(
+            deps,
+            purchaser,
+            msg,
+            native_refunds,
+        )?);
+    }
+    Ok(msgs)
+}
+
+fn generate_native_refund_msg(
+    deps: &Deps,
+    env: &Env,
+    refund_recipient: &Recipient,
+    native_funds: Vec<Coin>,
+) -> Result<SubMsg, ContractError> {
+    // Only cross-chain refunds need to be sent via the kernel
+    if refund_recipient.address.get_protocol().is_none() {
+        return refund_recipient.generate_direct_msg(deps, native_funds);
+    }
+    let amp_msg = refund_recipient.generate_amp_msg(deps, Some(native_funds.clone()))?;
+    let pkt = AMPPkt::new(
+        env.contract.address.clone(),
+        env.contract.address.clone(),
+        vec![amp_msg],
+    );
+    let kernel_address = ADOContract::default().get_kernel_address(deps.storage)?;
+    pkt.to_sub_msg(kernel_address, Some(native_funds), 0)
+}
+
+/// Refunds to a refund recipient are best-effort so that a single recipient cannot block the
+/// refunds of every other purchaser. The funds are sent to the purchaser directly if the recipient
+/// cannot be resolved, and can be claimed by the purchaser if the refund fails.
+fn generate_best_effort_refund_msgs(
+    deps: &mut DepsMut,
+    purchaser: &str,
+    msg: Result<SubMsg, ContractError>,
+    refunds: Vec<Funds>,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let Ok(msg) = msg else {
+        return generate_direct_refund_msgs(purchaser, &refunds);
+    };
+    let id = NEXT_REFUND_REPLY_ID
+        .may_load(deps.storage)?
+        .unwrap_or(REFUND_REPLY_ID_START);
+    NEXT_REFUND_REPLY_ID.save(deps.storage, &(id + 1))?;
+    REFUND_REPLIES.save(deps.storage, id, &(purchaser.to_owned(), refunds))?;
+    Ok(vec![SubMsg::reply_always(msg.msg, id)])
+}
+
+fn generate_direct_refund_msgs(
+    purchaser: &str,
+    refunds: &[Funds],
+) -> Result<Vec<SubMsg>, ContractError> {
+    refunds
+        .iter()
+        .map(|refund| {
+            let amount = funds_to_coin(refund).amount;
+            Ok(SubMsg::new(generate_transfer_msg(
+                refund,
+                purchaser.to_owned(),
                 amount,
-            }],
-        }))
+            )?))
+        })
+        .collect()
+}
+
+fn execute_claim_failed_refund(ctx: ExecuteContext) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    nonpayable(&info)?;
+
+    let purchaser = info.sender.as_str();
+    let refunds = FAILED_REFUNDS
+        .may_load(deps.storage, purchaser)?
+        .ok_or(ContractError::NoPurchases {})?;
+    FAILED_REFUNDS.remove(deps.storage, purchaser);
+
+    Ok(Response::new()
+        .add_submessages(generate_direct_refund_msgs(purchaser, &refunds)?)
+        .add_attribute("action", "claim_failed_refund")
+        .add_attribute("purchaser", purchaser))
+}
+
+/// Refunds the purchaser's share of the funds left in escrow when the milestones were cancelled,
+/// pro rata to the amount they paid in each denom.
+fn process_escrow_refund(
+    deps: &mut DepsMut,
+    env: &Env,
+    purchaser: &str,
+    campaign_id: Option<Uint64>,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let escrow = get_escrow(deps.storage, campaign_id)?
+        .filter(|escrow| escrow.cancelled)
+        .ok_or(ContractError::NoOngoingSale {})?;
+    let campaign_id = escrow.campaign_id.u64();
+    let contributions = ESCROW_CONTRIBUTIONS
+        .may_load(deps.storage, (campaign_id, purchaser))?
+        .ok_or(ContractError::NoPurchases {})?;
+    ESCROW_CONTRIBUTIONS.remove(deps.storage, (campaign_id, purchaser));
+    // The escrow of a previous campaign is removed once every purchaser has claimed their refund.
+    let is_claimed = ESCROW_CONTRIBUTIONS
+        .prefix(campaign_id)
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none();
+    if is_claimed {
+        UNCLAIMED_ESCROWS.remove(deps.storage, campaign_id);
+    }
+
+    let mut refunds = vec![];
+    for contribution in contributions {
+        let total_contributed = get_coin_amount(&escrow.contributions, &contribution.denom);
+        let refundable = get_coin_amount(&escrow.refundable, &contribution.denom);
+        let amount = refundable.multiply_ratio(contribution.amount, total_contributed);
+        if amount.is_zero() {
+            continue;
+        }
+        // The price is guaranteed to exist as purchases can only be made in accepted denoms.
+        let price = get_accepted_price(&escrow.prices, &contribution.denom).unwrap();
+        refunds.push((price, amount));
+    }
+    let refund_recipient = REFUND_RECIPIENTS.may_load(deps.storage, (campaign_id, purchaser))?;
+    generate_refund_msgs(deps, env, purchaser, refund_recipient, refunds)
+}
+
+fn execute_release_milestone(
+    ctx: ExecuteContext,
+    milestone_id: Uint64,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps,
+        info,
+        env,
+        amp_ctx,
+    } = ctx;
+    nonpayable(&info)?;
+
+    let mut milestone = MILESTONES
+        .may_load(deps.storage, milestone_id.u64())?
+        .ok_or_else(|| ContractError::InvalidMilestone {
+            msg: format!("Milestone with id {milestone_id} does not exist"),
+        })?;
+    ensure!(
+        milestone.status == MilestoneStatus::Pending,
+        ContractError::InvalidMilestone {
+            msg: "Milestone is not pending".to_string(),
+        }
+    );
+    // The owner attests that the milestone was reached, otherwise it can only be released once its
+    // deadline has passed.
+    let is_owner = ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?;
+    let deadline_passed = milestone
+        .deadline
+        .map_or(false, |deadline| deadline.is_expired(&env.block));
+    ensure!(is_owner || deadline_passed, ContractError::Unauthorized {});
+
+    let mut escrow =
+        ESCROW
+            .may_load(deps.storage)?
+            .ok_or_else(|| ContractError::InvalidMilestone {
+                msg: "Milestone funds are not escrowed".to_string(),
+            })?;
+
+    // The last milestone receives whatever is left so that no funds are lost to rounding.
+    let is_last_milestone = get_pending_milestones(deps.storage)?.len() == 1;
+    let amount = if is_last_milestone {
+        subtract_coins(&escrow.total, &escrow.released)?
     } else {
-        None
+        escrow
+            .total
+            .iter()
+            .map(|coin| Coin::new((coin.amount * milestone.percent).u128(), &coin.denom))
+            .filter(|coin| !coin.amount.is_zero())
+            .collect()
+    };
+    for coin in amount.iter() {
+        add_to_coins(&mut escrow.released, &coin.denom, coin.amount)?;
+    }
+    ESCROW.save(deps.storage, &escrow)?;
+
+    milestone.status = MilestoneStatus::Released;
+    milestone.released = amount.clone();
+    MILESTONES.save(deps.storage, milestone_id.u64(), &milestone)?;
+
+    let mut resp = Response::new()
+        .add_attribute("action", "release_milestone")
+        .add_attribute("milestone_id", milestone_id)
+        .add_attribute("amount", coins_to_string(&amount));
+    if !amount.is_empty() {
+        let pkt = match amp_ctx {
+            Some(pkt) => pkt,
+            None => AMPPkt::new(info.sender, env.contract.address, vec![]),
+        };
+        resp = resp.add_submessages(generate_payout_msgs(
+            &deps.as_ref(),
+            &milestone.recipient,
+            &amount,
+            &escrow.prices,
+            pkt,
+        )?);
+    }
+    Ok(resp)
+}
+
+fn execute_cancel_milestones(ctx: ExecuteContext) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    nonpayable(&info)?;
+    ensure!(
+        ADOContract::default().is_contract_owner(deps.storage, info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+
+    let mut escrow =
+        ESCROW
+            .may_load(deps.storage)?
+            .ok_or_else(|| ContractError::InvalidMilestone {
+                msg: "Milestone funds are not escrowed".to_string(),
+            })?;
+    let pending_milestones = get_pending_milestones(deps.storage)?;
+    ensure!(
+        !pending_milestones.is_empty(),
+        ContractError::InvalidMilestone {
+            msg: "No milestones are pending".to_string(),
+        }
+    );
+    for mut milestone in pending_milestones {
+        milestone.status = MilestoneStatus::Cancelled;
+        MILESTONES.save(deps.storage, milestone.id.u64(), &milestone)?;
+    }
+
+    escrow.refundable = subtract_coins(&escrow.total, &escrow.released)?;
+    escrow.cancelled = true;
+    ESCROW.save(deps.storage, &escrow)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_milestones")
+        .add_attribute("refundable", coins_to_string(&escrow.refundable)))
+}
+
+fn get_pending_milestones(storage: &dyn Storage) -> Result<Vec<Milestone>, ContractError> {
+    MILESTONES
+        .range(storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, milestone)| {
+                milestone.status == MilestoneStatus::Pending
+            })
+        })
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Generates the messages sending `funds` to `recipient`. CW20 tokens are sent directly to the
+/// recipient, native funds are sent through the kernel if the recipient has a message attached.
+fn generate_payout_msgs(
+    deps: &Deps,
+    recipient: &Recipient,
+    funds: &[Coin],
+    prices: &[Funds],
+    mut pkt: AMPPkt,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let mut msgs = vec![];
+    let mut native_funds: Vec<Coin> = vec![];
+    for coin in funds {
+        match get_accepted_price(prices, &coin.denom) {
+            Some(Funds::Cw20(_)) => {
+                msgs.push(recipient.generate_msg_cw20(
+                    deps,
+                    Cw20Coin {
+                        address: coin.denom.clone(),
+                        amount: coin.amount,
+                    },
+                )?);
+            }
+            _ => native_funds.push(coin.clone()),
+        }
+    }
+    if !native_funds.is_empty() {
+        match recipient.msg {
+            None => msgs.push(recipient.generate_direct_msg(deps, native_funds)?),
+            Some(_) => {
+                let amp_message = recipient.generate_amp_msg(deps, Some(native_funds.clone()))?;
+                pkt = pkt.add_message(amp_message);
+                let kernel_address = ADOContract::default().get_kernel_address(deps.storage)?;
+                msgs.push(pkt.to_sub_msg(kernel_address, Some(native_funds), 1)?);
+            }
+        }
+    }
+    Ok(msgs)
+}
+
+/// Returns the sale prices followed by the presale prices in denoms not accepted by the sale.
+fn get_all_prices(state: &State) -> Vec<Funds> {
+    let mut prices = state.prices.clone();
+    if let Some(presale) = &state.presale {
+        for price in presale.prices.iter() {
+            if get_accepted_price(&state.prices, funds_denom(price)).is_none() {
+                prices.push(price.clone());
+            }
+        }
+    }
+    prices
+}
+
+fn get_coin_amount(coins: &[Coin], denom: &str) -> Uint128 {
+    coins
+        .iter()
+        .find(|coin| coin.denom == denom)
+        .map_or(Uint128::zero(), |coin| coin.amount)
+}
+
+/// Subtracts `other` from `coins` per denom, skipping the denoms with nothing left.
+fn subtract_coins(coins: &[Coin], other: &[Coin]) -> Result<Vec<Coin>, ContractError> {
+    let mut result = vec![];
+    for coin in coins {
+        let amount = coin
+            .amount
+            .checked_sub(get_coin_amount(other, &coin.denom))?;
+        add_to_coins(&mut result, &coin.denom, amount)?;
+    }
+    Ok(result)
+}
+
+fn coins_to_string(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Adds `amount` to the coin of the given denom, skipping zero amounts.
+fn add_to_coins(coins: &mut Vec<Coin>, denom: &str, amount: Uint128) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    match coins.iter_mut().find(|coin| coin.denom == denom) {
+        Some(coin) => coin.amount = coin.amount.checked_add(amount)?,
+        None => coins.push(Coin::new(amount.u128(), denom)),
+    }
+    Ok(())
+}
+
+/// Generates a message sending `amount` of the sale denom to `recipient`.
+fn generate_transfer_msg(
+    price: &Funds,
+    recipient: String,
+    amount: Uint128,
+) -> Result<CosmosMsg, ContractError> {
+    Ok(match price {
+        Funds::Native(coin) => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount: coins(amount.u128(), &coin.denom),
+        }),
+        Funds::Cw20(cw20_coin) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: cw20_coin.address.clone(),
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+            funds: vec![],
+        }),
+    })
+}
+
+/// Returns the denom of the given funds, which is the token address for CW20 tokens.
+fn funds_denom(funds: &Funds) -> &str {
+    match funds {
+        Funds::Native(coin) => &coin.denom,
+        Funds::Cw20(cw20_coin) => &cw20_coin.address,
+    }
+}
+
+/// Represents the given funds as a `Coin`, using the token address as the denom for CW20 tokens.
+fn funds_to_coin(funds: &Funds) -> Coin {
+    match funds {
+        Funds::Native(coin) => coin.clone(),
+        Funds::Cw20(cw20_coin) => Coin::new(cw20_coin.amount.u128(), &cw20_coin.address),
+    }
+}
+
+fn funds_with_amount(funds: &Funds, amount: Uint128) -> Funds {
+    match funds {
+        Funds::Native(coin) => Funds::Native(Coin::new(amount.u128(), &coin.denom)),
+        Funds::Cw20(cw20_coin) => Funds::Cw20(Cw20Coin {
+            address: cw20_coin.address.clone(),
+            amount,
+        }),
     }
 }
 
+/// Burns up to `limit` unsold tokens held by `address`, or transfers them to `return_to` if
+/// provided.
 fn get_burn_messages(
     deps: &mut DepsMut,
     address: String,
     limit: usize,
+    return_to: Option<Addr>,
 ) -> Result<Vec<CosmosMsg>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let token_address = config.token_address.get_raw_address(&deps.as_ref())?;
@@ -847,16 +2452,34 @@ fn get_burn_messages(
         .map(|token_id| {
             // Any token that is burnable has been added to this map, and so must be removed.
             AVAILABLE_TOKENS.remove(deps.storage, &token_id);
+            let msg = match &return_to {
+                Some(return_to) => Cw721ExecuteMsg::TransferNft {
+                    recipient: AndrAddr::from_string(return_to),
+                    token_id,
+                },
+                None => Cw721ExecuteMsg::Burn { token_id },
+            };
             Ok(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: token_address.to_string(),
                 funds: vec![],
-                msg: encode_binary(&Cw721ExecuteMsg::Burn { token_id })?,
+                msg: encode_binary(&msg)?,
             }))
         })
         .collect()
 }
 
-fn clear_state(storage: &mut dyn Storage) -> Result<(), ContractError> {
+/// Ends the ongoing campaign, keeping its final state and outcome in `CAMPAIGNS`.
+fn clear_state(storage: &mut dyn Storage, outcome: CampaignOutcome) -> Result<(), ContractError> {
+    let state = STATE.load(storage)?;
+    CAMPAIGNS.save(
+        storage,
+        state.campaign_id.u64(),
+        &Campaign {
+            id: state.campaign_id,
+            state,
+            outcome,
+        },
+    )?;
     STATE.remove(storage);
     NUMBER_OF_TOKENS_AVAILABLE.save(storage, &Uint128::zero())?;
 
@@ -889,6 +2512,54 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             encode_binary(&query_available_tokens(deps, start_after, limit)?)
         }
         QueryMsg::IsTokenAvailable { id } => encode_binary(&query_is_token_available(deps, id)),
+        QueryMsg::LazyMint {} => encode_binary(&query_lazy_mint(deps)?),
+        QueryMsg::Tier { tier_id } => encode_binary(&query_tier(deps, tier_id)?),
+        QueryMsg::Tiers { start_after, limit } => {
+            encode_binary(&query_tiers(deps, start_after, limit)?)
+        }
+        QueryMsg::Milestone { milestone_id } => {
+            encode_binary(&query_milestone(deps, milestone_id)?)
+        }
+        QueryMsg::Milestones { start_after, limit } => {
+            encode_binary(&query_milestones(deps, start_after, limit)?)
+        }
+        QueryMsg::Escrow { campaign_id } => encode_binary(&query_escrow(deps, campaign_id)?),
+        QueryMsg::FailedRefunds { purchaser } => encode_binary(
+            &FAILED_REFUNDS
+                .may_load(deps.storage, &purchaser)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::Purchases {
+            purchaser,
+            campaign_id,
+            start_after,
+            limit,
+        } => encode_binary(&query_purchases(
+            deps,
+            purchaser,
+            campaign_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Contributors {
+            campaign_id,
+            start_after,
+            limit,
+        } => encode_binary(&query_contributors(deps, campaign_id, start_after, limit)?),
+        QueryMsg::TopContributors { campaign_id, limit } => {
+            encode_binary(&query_top_contributors(deps, campaign_id, limit)?)
+        }
+        QueryMsg::Referrals {
+            campaign_id,
+            start_after,
+            limit,
+        } => encode_binary(&query_referrals(deps, campaign_id, start_after, limit)?),
+        QueryMsg::Summary {} => encode_binary(&query_summary(deps, env)?),
+        QueryMsg::CurrentPrice {} => encode_binary(&query_current_price(deps, env)?),
+        QueryMsg::Campaign { campaign_id } => encode_binary(&query_campaign(deps, campaign_id)?),
+        QueryMsg::Campaigns { start_after, limit } => {
+            encode_binary(&query_campaigns(deps, start_after, limit)?)
+        }
         _ => ADOContract::default().query(deps, env, msg),
     }
 }
@@ -913,6 +2584,181 @@ fn query_is_token_available(deps: Deps, id: String) -> bool {
     AVAILABLE_TOKENS.has(deps.storage, &id)
 }
 
+fn query_lazy_mint(deps: Deps) -> Result<Option<LazyMintResponse>, ContractError> {
+    get_lazy_mint_response(deps.storage)
+}
+
+fn query_tier(deps: Deps, tier_id: Uint64) -> Result<TierResponse, ContractError> {
+    let tier = TIERS.load(deps.storage, tier_id.u64())?;
+    get_tier_response(deps.storage, tier)
+}
+
+fn query_tiers(
+    deps: Deps,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<TierResponse>, ContractError> {
+    get_tiers(deps.storage, start_after, limit)
+}
+
+fn query_milestone(deps: Deps, milestone_id: Uint64) -> Result<Milestone, ContractError> {
+    Ok(MILESTONES.load(deps.storage, milestone_id.u64())?)
+}
+
+fn query_milestones(
+    deps: Deps,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<Milestone>, ContractError> {
+    get_milestones(deps.storage, start_after, limit)
+}
+
+fn query_escrow(deps: Deps, campaign_id: Option<Uint64>) -> Result<Option<Escrow>, ContractError> {
+    get_escrow(deps.storage, campaign_id)
+}
+
+/// Returns the given campaign id, or the latest one if not provided.
+fn get_campaign_id(deps: Deps, campaign_id: Option<Uint64>) -> Result<Option<u64>, ContractError> {
+    match campaign_id {
+        Some(campaign_id) => Ok(Some(campaign_id.u64())),
+        None => get_latest_campaign_id(deps.storage),
+    }
+}
+
+fn query_purchases(
+    deps: Deps,
+    purchaser: String,
+    campaign_id: Option<Uint64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<PurchaseResponse>, ContractError> {
+    let Some(campaign_id) = get_campaign_id(deps, campaign_id)? else {
+        return Ok(vec![]);
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let purchases = CAMPAIGN_PURCHASES
+        .may_load(deps.storage, (campaign_id, &purchaser))?
+        .unwrap_or_default();
+    let start = match start_after {
+        Some(start_after) => purchases
+            .iter()
+            .position(|purchase| purchase.token_id == start_after)
+            .map_or(purchases.len(), |i| i + 1),
+        None => 0,
+    };
+    Ok(purchases.into_iter().skip(start).take(limit).collect())
+}
+
+fn query_contributors(
+    deps: Deps,
+    campaign_id: Option<Uint64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<Contributor>, ContractError> {
+    match get_campaign_id(deps, campaign_id)? {
+        Some(campaign_id) => get_contributors(deps.storage, campaign_id, start_after, limit),
+        None => Ok(vec![]),
+    }
+}
+
+fn query_referrals(
+    deps: Deps,
+    campaign_id: Option<Uint64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<Referral>, ContractError> {
+    match get_campaign_id(deps, campaign_id)? {
+        Some(campaign_id) => get_referrals(deps.storage, campaign_id, start_after, limit),
+        None => Ok(vec![]),
+    }
+}
+
+fn query_top_contributors(
+    deps: Deps,
+    campaign_id: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<Contributor>, ContractError> {
+    match get_campaign_id(deps, campaign_id)? {
+        Some(campaign_id) => get_top_contributors(deps.storage, campaign_id, limit),
+        None => Ok(vec![]),
+    }
+}
+
+/// Replaces the state of the ongoing campaign with the live state of the sale.
+fn with_current_state(deps: Deps, mut campaign: Campaign) -> Result<Campaign, ContractError> {
+    if campaign.outcome == CampaignOutcome::Ongoing {
+        if let Some(state) = STATE.may_load(deps.storage)? {
+            campaign.state = state;
+        }
+    }
+    Ok(campaign)
+}
+
+fn query_campaign(deps: Deps, campaign_id: Option<Uint64>) -> Result<Campaign, ContractError> {
+    let campaign = get_campaign_id(deps, campaign_id)?
+        .map(|campaign_id| CAMPAIGNS.may_load(deps.storage, campaign_id))
+        .transpose()?
+        .flatten()
+        .ok_or(ContractError::SaleDoesNotExist {})?;
+    with_current_state(deps, campaign)
+}
+
+fn query_campaigns(
+    deps: Deps,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<Campaign>, ContractError> {
+    get_campaigns(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|campaign| with_current_state(deps, campaign))
+        .collect()
+}
+
+fn query_summary(deps: Deps, env: Env) -> Result<SummaryResponse, ContractError> {
+    let state = STATE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoOngoingSale {})?;
+
+    // Tier tokens are minted on purchase, so limited tiers add to the tokens left for sale.
+    let mut tokens_remaining = Some(NUMBER_OF_TOKENS_AVAILABLE.load(deps.storage)?);
+    if let Some(lazy_mint) = get_lazy_mint_response(deps.storage)? {
+        tokens_remaining = tokens_remaining
+            .map(|total| total.checked_add(lazy_mint.remaining))
+            .transpose()?;
+    }
+    for tier in TIERS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, tier) = tier?;
+        let remaining = get_tier_response(deps.storage, tier)?.remaining;
+        tokens_remaining = match (tokens_remaining, remaining) {
+            (Some(total), Some(remaining)) => Some(total.checked_add(remaining)?),
+            _ => None,
+        };
+    }
+    let time_left = match state.end_time {
+        Expiration::AtTime(end_time) => Some(Milliseconds::from_nanos(
+            end_time.nanos().saturating_sub(env.block.time.nanos()),
+        )),
+        _ => None,
+    };
+
+    Ok(SummaryResponse {
+        amount_raised: state.amount_raised,
+        amounts_raised: state.amounts_raised,
+        taxes_paid: state.taxes_paid,
+        amount_sold: state.amount_sold,
+        tokens_remaining,
+        soft_cap_reached: state.soft_cap.amount <= state.amount_raised,
+        time_left,
+    })
+}
+
+fn query_current_price(deps: Deps, env: Env) -> Result<Vec<Funds>, ContractError> {
+    let state = STATE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoOngoingSale {})?;
+    get_current_prices(&state, &env.block)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     ADOContract::default().migrate(deps, CONTRACT_NAME, CONTRACT_VERSION)
@@ -2,20 +2,23 @@
 
 use crate::contract::{execute, instantiate, query, reply};
 use andromeda_non_fungible_tokens::{
-    crowdfund::{CrowdfundMintMsg, ExecuteMsg, InstantiateMsg, QueryMsg},
+    crowdfund::{
+        CrowdfundMintMsg, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LazyMint, MilestoneMsg,
+        PresaleMsg, Pricing, QueryMsg, SummaryResponse, Tier, TierResponse,
+    },
     cw721::TokenExtension,
 };
 use andromeda_std::{
     ado_base::modules::Module,
     amp::{AndrAddr, Recipient},
-    common::Milliseconds,
+    common::{Funds, Milliseconds},
 };
 use andromeda_testing::{
     mock::MockApp,
     mock_ado,
     mock_contract::{ExecuteResult, MockADO, MockContract},
 };
-use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Empty, Uint64};
 use cw_multi_test::{Contract, ContractWrapper, Executor};
 
 pub struct MockCrowdfund(Addr);
@@ -60,18 +63,28 @@ impl MockCrowdfund {
         app: &mut MockApp,
         start_time: Option<Milliseconds>,
         end_time: Milliseconds,
-        price: Coin,
-        min_tokens_sold: Uint128,
+        prices: Vec<Funds>,
+        pricing: Option<Pricing>,
+        soft_cap: Coin,
+        hard_cap: Option<Coin>,
+        referral_rate: Option<Decimal>,
         max_amount_per_wallet: Option<u32>,
         recipient: Recipient,
+        presale: Option<PresaleMsg>,
+        milestones: Option<Vec<MilestoneMsg>>,
     ) -> ExecuteResult {
         let msg = mock_start_crowdfund_msg(
             start_time,
             end_time,
-            price,
-            min_tokens_sold,
+            prices,
+            pricing,
+            soft_cap,
+            hard_cap,
+            referral_rate,
             max_amount_per_wallet,
             recipient,
+            presale,
+            milestones,
         );
         self.execute(app, &msg, sender, &[])
     }
@@ -86,6 +99,16 @@ impl MockCrowdfund {
         self.execute(app, &msg, sender, &[])
     }
 
+    pub fn execute_cancel_sale(
+        &self,
+        sender: Addr,
+        app: &mut MockApp,
+        return_unsold_to: Option<AndrAddr>,
+    ) -> ExecuteResult {
+        let msg = mock_cancel_crowdfund_msg(return_unsold_to);
+        self.execute(app, &msg, sender, &[])
+    }
+
     pub fn execute_mint(
         &self,
         sender: Addr,
@@ -122,6 +145,43 @@ impl MockCrowdfund {
         let msg = mock_purchase_msg(number_of_tokens);
         self.execute(app, &msg, sender, funds)
     }
+
+    pub fn execute_add_tier(&self, sender: Addr, app: &mut MockApp, tier: Tier) -> ExecuteResult {
+        let msg = mock_add_tier_msg(tier);
+        self.execute(app, &msg, sender, &[])
+    }
+
+    pub fn execute_set_lazy_mint(
+        &self,
+        sender: Addr,
+        app: &mut MockApp,
+        lazy_mint: Option<LazyMint>,
+    ) -> ExecuteResult {
+        let msg = mock_set_lazy_mint_msg(lazy_mint);
+        self.execute(app, &msg, sender, &[])
+    }
+
+    pub fn execute_purchase_tier(
+        &self,
+        sender: Addr,
+        app: &mut MockApp,
+        tier_id: Uint64,
+        number_of_tokens: Option<u32>,
+        funds: &[Coin],
+    ) -> ExecuteResult {
+        let msg = mock_purchase_tier_msg(tier_id, number_of_tokens);
+        self.execute(app, &msg, sender, funds)
+    }
+
+    pub fn query_tiers(&self, app: &mut MockApp) -> Vec<TierResponse> {
+        let msg = mock_get_tiers(None, None);
+        self.query(app, msg)
+    }
+
+    pub fn query_summary(&self, app: &mut MockApp) -> SummaryResponse {
+        let msg = mock_get_summary();
+        self.query(app, msg)
+    }
 }
 
 pub fn mock_andromeda_crowdfund() -> Box<dyn Contract<Empty>> {
@@ -145,21 +205,32 @@ pub fn mock_crowdfund_instantiate_msg(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn mock_start_crowdfund_msg(
     start_time: Option<Milliseconds>,
     end_time: Milliseconds,
-    price: Coin,
-    min_tokens_sold: Uint128,
+    prices: Vec<Funds>,
+    pricing: Option<Pricing>,
+    soft_cap: Coin,
+    hard_cap: Option<Coin>,
+    referral_rate: Option<Decimal>,
     max_amount_per_wallet: Option<u32>,
     recipient: Recipient,
+    presale: Option<PresaleMsg>,
+    milestones: Option<Vec<MilestoneMsg>>,
 ) -> ExecuteMsg {
     ExecuteMsg::StartSale {
         start_time,
         end_time,
-        price,
-        min_tokens_sold,
+        prices,
+        pricing,
+        soft_cap,
+        hard_cap,
+        referral_rate,
         max_amount_per_wallet,
         recipient,
+        presale,
+        milestones,
     }
 }
 
@@ -167,6 +238,10 @@ pub fn mock_end_crowdfund_msg(limit: Option<u32>) -> ExecuteMsg {
     ExecuteMsg::EndSale { limit }
 }
 
+pub fn mock_cancel_crowdfund_msg(return_unsold_to: Option<AndrAddr>) -> ExecuteMsg {
+    ExecuteMsg::CancelSale { return_unsold_to }
+}
+
 pub fn mock_crowdfund_mint_msg(
     token_id: String,
     extension: TokenExtension,
@@ -196,5 +271,44 @@ pub fn mock_crowdfund_quick_mint_msg(amount: u32, publisher: String) -> ExecuteM
 }
 
 pub fn mock_purchase_msg(number_of_tokens: Option<u32>) -> ExecuteMsg {
-    ExecuteMsg::Purchase { number_of_tokens }
+    ExecuteMsg::Purchase {
+        number_of_tokens,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    }
+}
+
+pub fn mock_cw20_purchase_hook_msg(number_of_tokens: Option<u32>) -> Cw20HookMsg {
+    Cw20HookMsg::Purchase {
+        number_of_tokens,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    }
+}
+
+pub fn mock_add_tier_msg(tier: Tier) -> ExecuteMsg {
+    ExecuteMsg::AddTier { tier }
+}
+
+pub fn mock_set_lazy_mint_msg(lazy_mint: Option<LazyMint>) -> ExecuteMsg {
+    ExecuteMsg::SetLazyMint { lazy_mint }
+}
+
+pub fn mock_purchase_tier_msg(tier_id: Uint64, number_of_tokens: Option<u32>) -> ExecuteMsg {
+    ExecuteMsg::Purchase {
+        number_of_tokens,
+        tier_id: Some(tier_id),
+        refund_recipient: None,
+        referrer: None,
+    }
+}
+
+pub fn mock_get_tiers(start_after: Option<Uint64>, limit: Option<u32>) -> QueryMsg {
+    QueryMsg::Tiers { start_after, limit }
+}
+
+pub fn mock_get_summary() -> QueryMsg {
+    QueryMsg::Summary {}
 }
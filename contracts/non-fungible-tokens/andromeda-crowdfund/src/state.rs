@@ -1,8 +1,11 @@
-use andromeda_non_fungible_tokens::crowdfund::{Config, State};
-use andromeda_std::error::ContractError;
+use andromeda_non_fungible_tokens::crowdfund::{
+    Campaign, Config, Contributor, Escrow, LazyMint, LazyMintResponse, Milestone, PurchaseResponse,
+    Referral, State, Tier, TierResponse,
+};
+use andromeda_std::{amp::Recipient, common::Funds, error::ContractError};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Order, Storage, SubMsg, Uint128};
-use cw_storage_plus::{Bound, Item, Map};
+use cosmwasm_std::{Coin, Order, Storage, SubMsg, Uint128, Uint64};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex};
 
 /// The config.
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -23,16 +26,108 @@ pub const AVAILABLE_TOKENS: Map<&str, bool> = Map::new("available_tokens");
 /// config.can_mint_after_sale is false.
 pub const SALE_CONDUCTED: Item<bool> = Item::new("sale_conducted");
 
+/// The range of tokens minted to the purchasers once a sale succeeds, in place of the pre-minted
+/// tokens.
+pub const LAZY_MINT: Item<LazyMint> = Item::new("lazy_mint");
+
+/// The number of tokens of the lazy mint range that were sold.
+pub const LAZY_MINT_SALES: Item<Uint128> = Item::new("lazy_mint_sales");
+
+/// Relates a lazy mint token id template to the first id that has not been sold from a previous
+/// range using it, so that later ranges cannot mint the same ids again.
+pub const LAZY_MINT_NEXT_IDS: Map<&str, Uint128> = Map::new("lazy_mint_next_ids");
+
+/// Relates tier id to the tier.
+pub const TIERS: Map<u64, Tier> = Map::new("tiers");
+
+/// Relates tier id to the number of tokens sold for that tier.
+pub const TIER_SALES: Map<u64, Uint128> = Map::new("tier_sales");
+
+/// Relates milestone id to the milestone of the current or last sale.
+pub const MILESTONES: Map<u64, Milestone> = Map::new("milestones");
+
+/// The funds of the last successful sale with milestones, held until the milestones are released.
+pub const ESCROW: Item<Escrow> = Item::new("escrow");
+
+/// Relates campaign id to the escrow of a previous sale whose milestones were cancelled, as long as
+/// some of its refunds are unclaimed.
+pub const UNCLAIMED_ESCROWS: Map<u64, Escrow> = Map::new("unclaimed_escrows");
+
+/// Relates campaign id and purchaser address to the amount they paid per denom in a sale whose
+/// funds are escrowed. Used to refund the escrowed funds pro rata if the milestones are cancelled.
+pub const ESCROW_CONTRIBUTIONS: Map<(u64, &str), Vec<Coin>> = Map::new("escrow_contributions");
+
+/// Relates campaign id to the campaign. The state of the ongoing campaign is kept in `STATE`.
+pub const CAMPAIGNS: Map<u64, Campaign> = Map::new("campaigns");
+
+/// Relates campaign id and purchaser address to every purchase they made in the campaign.
+pub const CAMPAIGN_PURCHASES: Map<(u64, &str), Vec<PurchaseResponse>> =
+    Map::new("campaign_purchases");
+
+/// Relates campaign id and purchaser address to where their refunds are sent.
+pub const REFUND_RECIPIENTS: Map<(u64, &str), Recipient> = Map::new("refund_recipients");
+
+/// Relates purchaser address to the refunds their refund recipient failed to receive, which they
+/// can claim themselves.
+pub const FAILED_REFUNDS: Map<&str, Vec<Funds>> = Map::new("failed_refunds");
+
+/// Relates the reply id of a refund sent to a refund recipient to the purchaser and the refunded
+/// funds, so that they can be claimed if the refund fails.
+pub const REFUND_REPLIES: Map<u64, (String, Vec<Funds>)> = Map::new("refund_replies");
+
+/// The reply id of the next refund sent to a refund recipient.
+pub const NEXT_REFUND_REPLY_ID: Item<u64> = Item::new("next_refund_reply_id");
+
+/// Relates campaign id and referrer address to the purchases they referred in the campaign.
+pub const REFERRALS: Map<(u64, &str), Referral> = Map::new("referrals");
+
+pub struct ContributorIndices<'a> {
+    /// PK: campaign_id + purchaser
+    /// Secondary key: campaign_id + amount contributed in the base denom
+    pub amount: MultiIndex<'a, (u64, u128), Contributor, (u64, &'a str)>,
+}
+
+impl<'a> IndexList<Contributor> for ContributorIndices<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Contributor>> + '_> {
+        let v: Vec<&dyn Index<Contributor>> = vec![&self.amount];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Relates campaign id and purchaser address to their contributions in the campaign.
+pub fn contributors<'a>() -> IndexedMap<'a, (u64, &'a str), Contributor, ContributorIndices<'a>> {
+    let indexes = ContributorIndices {
+        amount: MultiIndex::new(
+            |pk: &[u8], c| {
+                let (campaign_id, _) =
+                    <(u64, &str)>::from_slice(pk).expect("invalid contributor key");
+                (campaign_id, c.amount.u128())
+            },
+            "contributors",
+            "contributors__amount",
+        ),
+    };
+    IndexedMap::new("contributors", indexes)
+}
+
 #[cw_serde]
 pub struct Purchase {
     /// The token id being purchased.
     pub token_id: String,
+    /// The tier the token was purchased from, `None` if it is a pre-minted token.
+    pub tier_id: Option<Uint64>,
+    /// The denom the token was paid for in, CW20 tokens are denoted by their address.
+    pub denom: String,
+    /// The price paid for the token, excluding taxes.
+    pub price: Uint128,
     /// Amount of tax paid.
     pub tax_amount: Uint128,
     /// sub messages for sending funds for rates.
     pub msgs: Vec<SubMsg>,
     /// The purchaser of the token.
     pub purchaser: String,
+    /// The address that referred the purchaser.
+    pub referrer: Option<String>,
 }
 
 const MAX_LIMIT: u32 = 50;
@@ -51,3 +146,216 @@ pub(crate) fn get_available_tokens(
         .collect();
     tokens
 }
+
+pub(crate) fn get_tier_response(
+    storage: &dyn Storage,
+    tier: Tier,
+) -> Result<TierResponse, ContractError> {
+    let sold_amount = TIER_SALES
+        .may_load(storage, tier.id.u64())?
+        .unwrap_or_default();
+    let remaining = tier.limit.map(|limit| limit.saturating_sub(sold_amount));
+    Ok(TierResponse {
+        tier,
+        sold_amount,
+        remaining,
+    })
+}
+
+pub(crate) fn get_tiers(
+    storage: &dyn Storage,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<TierResponse>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.u64()));
+    TIERS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, tier) = item?;
+            get_tier_response(storage, tier)
+        })
+        .collect()
+}
+
+/// Returns the ids of the next `number_wanted` tokens of the given tier, capped by the number of
+/// tokens left for the tier.
+pub(crate) fn get_tier_token_ids(
+    storage: &dyn Storage,
+    tier: &Tier,
+    number_wanted: u32,
+) -> Result<Vec<String>, ContractError> {
+    let sold_amount = TIER_SALES
+        .may_load(storage, tier.id.u64())?
+        .unwrap_or_default()
+        .u128();
+    let mut end = sold_amount + number_wanted as u128;
+    if let Some(limit) = tier.limit {
+        end = end.min(limit.u128());
+    }
+    Ok((sold_amount..end)
+        .map(|n| format!("tier-{}-{}", tier.id, n + 1))
+        .collect())
+}
+
+pub(crate) fn get_lazy_mint_response(
+    storage: &dyn Storage,
+) -> Result<Option<LazyMintResponse>, ContractError> {
+    let Some(lazy_mint) = LAZY_MINT.may_load(storage)? else {
+        return Ok(None);
+    };
+    let sold_amount = LAZY_MINT_SALES.may_load(storage)?.unwrap_or_default();
+    let remaining = lazy_mint.supply().saturating_sub(sold_amount);
+    Ok(Some(LazyMintResponse {
+        lazy_mint,
+        sold_amount,
+        remaining,
+    }))
+}
+
+/// Returns the ids of the next tokens of the lazy mint range, up to `number_wanted`.
+pub(crate) fn get_lazy_token_ids(
+    storage: &dyn Storage,
+    lazy_mint: &LazyMint,
+    number_wanted: u32,
+) -> Result<Vec<String>, ContractError> {
+    let sold_amount = LAZY_MINT_SALES.may_load(storage)?.unwrap_or_default();
+    let start = lazy_mint.first_id.checked_add(sold_amount)?.u128();
+    let end = (start + number_wanted as u128).min(lazy_mint.last_id.u128() + 1);
+    Ok((start..end)
+        .map(|n| lazy_mint.token_id(Uint128::new(n)))
+        .collect())
+}
+
+/// Whether every tier has a limit and all of its tokens have been sold.
+pub(crate) fn all_tiers_sold_out(storage: &dyn Storage) -> Result<bool, ContractError> {
+    for item in TIERS.range(storage, None, None, Order::Ascending) {
+        let (_, tier) = item?;
+        let remaining = get_tier_response(storage, tier)?.remaining;
+        if remaining.map_or(true, |remaining| !remaining.is_zero()) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+pub(crate) fn get_milestones(
+    storage: &dyn Storage,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<Milestone>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.u64()));
+    MILESTONES
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// The escrow of the given campaign, or the current escrow if no campaign is given.
+pub(crate) fn get_escrow(
+    storage: &dyn Storage,
+    campaign_id: Option<Uint64>,
+) -> Result<Option<Escrow>, ContractError> {
+    let escrow = ESCROW.may_load(storage)?;
+    match campaign_id {
+        Some(campaign_id)
+            if escrow
+                .as_ref()
+                .map_or(true, |e| e.campaign_id != campaign_id) =>
+        {
+            Ok(UNCLAIMED_ESCROWS.may_load(storage, campaign_id.u64())?)
+        }
+        _ => Ok(escrow),
+    }
+}
+
+/// Removes the escrow contributions of the campaign.
+pub(crate) fn clear_escrow_contributions(
+    storage: &mut dyn Storage,
+    campaign_id: u64,
+) -> Result<(), ContractError> {
+    let purchasers = ESCROW_CONTRIBUTIONS
+        .prefix(campaign_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<String>, _>>()?;
+    for purchaser in purchasers {
+        ESCROW_CONTRIBUTIONS.remove(storage, (campaign_id, &purchaser));
+    }
+    Ok(())
+}
+
+/// The id of the ongoing campaign, or of the last one if there is no ongoing sale.
+pub(crate) fn get_latest_campaign_id(storage: &dyn Storage) -> Result<Option<u64>, ContractError> {
+    if let Some(state) = STATE.may_load(storage)? {
+        return Ok(Some(state.campaign_id.u64()));
+    }
+    Ok(CAMPAIGNS
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?)
+}
+
+pub(crate) fn get_campaigns(
+    storage: &dyn Storage,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<Campaign>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.u64()));
+    CAMPAIGNS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub(crate) fn get_contributors(
+    storage: &dyn Storage,
+    campaign_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<Contributor>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    contributors()
+        .prefix(campaign_id)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub(crate) fn get_referrals(
+    storage: &dyn Storage,
+    campaign_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<Referral>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    REFERRALS
+        .prefix(campaign_id)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub(crate) fn get_top_contributors(
+    storage: &dyn Storage,
+    campaign_id: u64,
+    limit: Option<u32>,
+) -> Result<Vec<Contributor>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    contributors()
+        .idx
+        .amount
+        .sub_prefix(campaign_id)
+        .range(storage, None, None, Order::Descending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
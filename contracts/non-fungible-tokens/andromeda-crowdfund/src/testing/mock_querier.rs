@@ -1,3 +1,4 @@
+use andromeda_modules::address_list::{IncludesAddressResponse, QueryMsg as AddressListQueryMsg};
 use andromeda_std::ado_base::hooks::{AndromedaHook, HookMsg, OnFundsTransferResponse};
 use andromeda_std::ado_base::InstantiateMsg;
 use andromeda_std::ado_contract::ADOContract;
@@ -8,9 +9,10 @@ use cosmwasm_std::{
     from_json,
     testing::{mock_env, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR},
     to_json_binary, Binary, Coin, ContractResult, OwnedDeps, Querier, QuerierResult, QueryRequest,
-    SystemError, SystemResult, WasmQuery,
+    SystemError, SystemResult, WasmMsg, WasmQuery,
 };
 use cosmwasm_std::{BankMsg, CosmosMsg, QuerierWrapper, Response, SubMsg, Uint128};
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
 use cw721::{ContractInfoResponse, Cw721QueryMsg, TokensResponse};
 
 pub use andromeda_std::testing::mock_querier::{
@@ -171,12 +173,35 @@ impl WasmMockQuerier {
                                 })),
                             ],
                         ),
-                        Funds::Cw20(_) => {
-                            let resp: Response = Response::default();
-                            return SystemResult::Ok(ContractResult::Ok(
-                                to_json_binary(&resp).unwrap(),
-                            ));
-                        }
+                        Funds::Cw20(ref cw20_coin) => (
+                            Funds::Cw20(Cw20Coin {
+                                // Deduct royalty of 10%.
+                                amount: cw20_coin.amount.multiply_ratio(90u128, 100u128),
+                                address: cw20_coin.address.clone(),
+                            }),
+                            vec![
+                                SubMsg::new(WasmMsg::Execute {
+                                    contract_addr: cw20_coin.address.clone(),
+                                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                                        recipient: MOCK_ROYALTY_RECIPIENT.to_owned(),
+                                        // Royalty of 10%
+                                        amount: cw20_coin.amount.multiply_ratio(10u128, 100u128),
+                                    })
+                                    .unwrap(),
+                                    funds: vec![],
+                                }),
+                                SubMsg::new(WasmMsg::Execute {
+                                    contract_addr: cw20_coin.address.clone(),
+                                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                                        recipient: MOCK_TAX_RECIPIENT.to_owned(),
+                                        // Flat tax of 50
+                                        amount: Uint128::from(50u128),
+                                    })
+                                    .unwrap(),
+                                    funds: vec![],
+                                }),
+                            ],
+                        ),
                     };
                     let response = OnFundsTransferResponse {
                         msgs,
@@ -193,10 +218,22 @@ impl WasmMockQuerier {
     }
 
     fn handle_addresslist_query(&self, msg: &Binary) -> QuerierResult {
+        let whitelisted_addresses = ["sender"];
+        match from_json(msg) {
+            Ok(AddressListQueryMsg::IncludesAddress { address }) => {
+                let res = IncludesAddressResponse {
+                    included: whitelisted_addresses.contains(&address.as_str()),
+                };
+                return SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()));
+            }
+            Ok(AddressListQueryMsg::IsInclusive {}) => {
+                return SystemResult::Ok(ContractResult::Ok(to_json_binary(&true).unwrap()));
+            }
+            _ => {}
+        }
         match from_json(msg).unwrap() {
             HookMsg::AndrHook(hook_msg) => match hook_msg {
                 AndromedaHook::OnExecute { sender, payload: _ } => {
-                    let whitelisted_addresses = ["sender"];
                     let response: Response = Response::default();
                     if whitelisted_addresses.contains(&sender.as_str()) {
                         SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
@@ -1,8 +1,9 @@
 use crate::{
-    contract::{execute, instantiate, query, MAX_MINT_LIMIT},
+    contract::{execute, instantiate, query, reply, MAX_MINT_LIMIT},
     state::{
-        Purchase, AVAILABLE_TOKENS, CONFIG, NUMBER_OF_TOKENS_AVAILABLE, PURCHASES, SALE_CONDUCTED,
-        STATE,
+        get_lazy_token_ids, Purchase, AVAILABLE_TOKENS, CONFIG, ESCROW, ESCROW_CONTRIBUTIONS,
+        LAZY_MINT, LAZY_MINT_SALES, MILESTONES, NUMBER_OF_TOKENS_AVAILABLE, PURCHASES, REFERRALS,
+        SALE_CONDUCTED, STATE, TIER_SALES,
     },
     testing::mock_querier::{
         mock_dependencies_custom, MOCK_ADDRESS_LIST_CONTRACT, MOCK_APP_CONTRACT,
@@ -11,7 +12,12 @@ use crate::{
     },
 };
 use andromeda_non_fungible_tokens::{
-    crowdfund::{Config, CrowdfundMintMsg, ExecuteMsg, InstantiateMsg, QueryMsg, State},
+    crowdfund::{
+        Campaign, CampaignOutcome, Cancellation, Config, Contributor, CrowdfundMintMsg,
+        Cw20HookMsg, Escrow, ExecuteMsg, InstantiateMsg, LazyMint, LazyMintResponse, Milestone,
+        MilestoneMsg, MilestoneStatus, Presale, PresaleMsg, Pricing, PurchaseResponse, QueryMsg,
+        Referral, State, SummaryResponse, Tier, TierMetaData, TierResponse, Whitelist,
+    },
     cw721::{ExecuteMsg as Cw721ExecuteMsg, TokenExtension},
 };
 use andromeda_std::{
@@ -20,16 +26,18 @@ use andromeda_std::{
     common::{
         encode_binary,
         expiration::{expiration_from_milliseconds, MILLISECONDS_TO_NANOSECONDS_RATIO},
-        Milliseconds,
+        Funds, Milliseconds,
     },
     error::ContractError,
 };
 use andromeda_testing::economics_msg::generate_economics_message;
 use cosmwasm_std::{
-    coin, coins, from_json,
+    attr, coin, coins, from_json,
     testing::{mock_env, mock_info},
-    Addr, BankMsg, Coin, CosmosMsg, DepsMut, Response, StdError, SubMsg, Uint128, WasmMsg,
+    Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Event, Reply, Response, StdError, SubMsg,
+    SubMsgResult, Uint128, Uint64, WasmMsg,
 };
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw_utils::Expiration;
 
 use super::mock_querier::MOCK_KERNEL_CONTRACT;
@@ -40,7 +48,11 @@ const RATES: &str = "rates";
 fn get_purchase(token_id: impl Into<String>, purchaser: impl Into<String>) -> Purchase {
     Purchase {
         token_id: token_id.into(),
+        tier_id: None,
+        denom: "uusd".to_string(),
+        price: Uint128::new(100),
         purchaser: purchaser.into(),
+        referrer: None,
         tax_amount: Uint128::from(50u128),
         msgs: get_rates_messages(),
     }
@@ -79,6 +91,12 @@ fn get_burn_message(token_id: impl Into<String>) -> CosmosMsg {
     })
 }
 
+fn get_refund_event(purchaser: &str, amount: &str) -> Event {
+    Event::new("refund")
+        .add_attribute("purchaser", purchaser)
+        .add_attribute("amount", amount)
+}
+
 fn get_transfer_message(token_id: impl Into<String>, recipient: AndrAddr) -> CosmosMsg {
     CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
@@ -91,6 +109,26 @@ fn get_transfer_message(token_id: impl Into<String>, recipient: AndrAddr) -> Cos
     })
 }
 
+fn get_tier(id: u64, price: u128, limit: Option<u128>) -> Tier {
+    Tier {
+        id: Uint64::new(id),
+        label: format!("tier {id}"),
+        price: Uint128::new(price),
+        limit: limit.map(Uint128::new),
+        metadata: TierMetaData {
+            token_uri: None,
+            extension: TokenExtension {
+                publisher: "publisher".to_string(),
+            },
+        },
+    }
+}
+
+fn add_tier(deps: DepsMut, tier: Tier) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::AddTier { tier };
+    execute(deps, mock_env(), mock_info("owner", &[]), msg)
+}
+
 fn init(deps: DepsMut, modules: Option<Vec<Module>>) -> Response {
     let msg = InstantiateMsg {
         token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
@@ -186,10 +224,15 @@ fn test_mint_sale_started() {
     let msg = ExecuteMsg::StartSale {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: Some(5),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -380,10 +423,15 @@ fn test_start_sale_end_time_zero() {
     let msg = ExecuteMsg::StartSale {
         start_time: Some(Milliseconds(one_minute_in_future)),
         end_time: Milliseconds::zero(),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: None,
         recipient: Recipient::from_string("recipient".to_string()),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -400,10 +448,15 @@ fn test_start_sale_unauthorized() {
     let msg = ExecuteMsg::StartSale {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time + 1) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: None,
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("anyone", &[]);
@@ -422,10 +475,15 @@ fn test_start_sale_start_time_in_past() {
     let msg = ExecuteMsg::StartSale {
         start_time: Some(Milliseconds(one_minute_in_past)),
         end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: None,
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -450,10 +508,15 @@ fn test_start_sale_start_time_in_future() {
     let msg = ExecuteMsg::StartSale {
         start_time: Some(Milliseconds(one_minute_in_future)),
         end_time: Milliseconds::from_nanos((one_minute_in_future + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: None,
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -470,26 +533,32 @@ fn test_start_sale_max_default() {
     let msg = ExecuteMsg::StartSale {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: None,
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("owner", &[]);
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
     // Using current time since start time wasn't provided
     let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time + 1)).unwrap();
+    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time)).unwrap();
     let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
 
     assert_eq!(
         Response::new()
             .add_attribute("action", "start_sale")
+            .add_attribute("campaign_id", "1")
             .add_attribute("start_time", start_expiration.to_string())
             .add_attribute("end_time", end_expiration.to_string())
-            .add_attribute("price", "100uusd")
-            .add_attribute("min_tokens_sold", "1")
+            .add_attribute("prices", "100uusd")
+            .add_attribute("soft_cap", "100uusd")
             .add_attribute("max_amount_per_wallet", "1")
             .add_submessage(generate_economics_message("owner", "StartSale")),
         res
@@ -497,14 +566,24 @@ fn test_start_sale_max_default() {
 
     assert_eq!(
         State {
+            campaign_id: Uint64::one(),
+            start_time: start_expiration,
             end_time: end_expiration,
-            price: coin(100, "uusd"),
-            min_tokens_sold: Uint128::from(1u128),
+            prices: vec![Funds::Native(coin(100, "uusd"))],
+            pricing: None,
+            soft_cap: coin(100, "uusd"),
+            hard_cap: None,
+            referral_rate: None,
             max_amount_per_wallet: 1,
             amount_sold: Uint128::zero(),
-            amount_to_send: Uint128::zero(),
+            amount_raised: Uint128::zero(),
+            amounts_raised: vec![],
+            taxes_paid: vec![],
+            amount_to_send: vec![],
             amount_transferred: Uint128::zero(),
             recipient: Recipient::from_string("recipient"),
+            presale: None,
+            cancellation: None,
         },
         STATE.load(deps.as_ref().storage).unwrap()
     );
@@ -524,14 +603,19 @@ fn test_start_sale_max_modified() {
     let msg = ExecuteMsg::StartSale {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: Some(5),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
     // Using current time since start time wasn't provided
     let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time + 1)).unwrap();
+    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time)).unwrap();
     let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
 
     let info = mock_info("owner", &[]);
@@ -539,10 +623,11 @@ fn test_start_sale_max_modified() {
     assert_eq!(
         Response::new()
             .add_attribute("action", "start_sale")
+            .add_attribute("campaign_id", "1")
             .add_attribute("start_time", start_expiration.to_string())
             .add_attribute("end_time", end_expiration.to_string())
-            .add_attribute("price", "100uusd")
-            .add_attribute("min_tokens_sold", "1")
+            .add_attribute("prices", "100uusd")
+            .add_attribute("soft_cap", "100uusd")
             .add_attribute("max_amount_per_wallet", "5")
             .add_submessage(generate_economics_message("owner", "StartSale")),
         res
@@ -550,14 +635,24 @@ fn test_start_sale_max_modified() {
 
     assert_eq!(
         State {
+            campaign_id: Uint64::one(),
+            start_time: start_expiration,
             end_time: end_expiration,
-            price: coin(100, "uusd"),
-            min_tokens_sold: Uint128::from(1u128),
+            prices: vec![Funds::Native(coin(100, "uusd"))],
+            pricing: None,
+            soft_cap: coin(100, "uusd"),
+            hard_cap: None,
+            referral_rate: None,
             max_amount_per_wallet: 5,
             amount_sold: Uint128::zero(),
-            amount_to_send: Uint128::zero(),
+            amount_raised: Uint128::zero(),
+            amounts_raised: vec![],
+            taxes_paid: vec![],
+            amount_to_send: vec![],
             amount_transferred: Uint128::zero(),
             recipient: Recipient::from_string("recipient"),
+            presale: None,
+            cancellation: None,
         },
         STATE.load(deps.as_ref().storage).unwrap()
     );
@@ -570,6 +665,9 @@ fn test_purchase_sale_not_started() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let info = mock_info("sender", &[]);
@@ -578,6 +676,8 @@ fn test_purchase_sale_not_started() {
 
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -593,14 +693,24 @@ fn test_purchase_sale_not_ended() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height - 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -609,6 +719,9 @@ fn test_purchase_sale_not_ended() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -616,6 +729,8 @@ fn test_purchase_sale_not_ended() {
 
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -633,14 +748,24 @@ fn test_purchase_no_funds() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -649,12 +774,17 @@ fn test_purchase_no_funds() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
 
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
@@ -671,14 +801,24 @@ fn test_purchase_wrong_denom() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -687,12 +827,17 @@ fn test_purchase_wrong_denom() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
 
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
@@ -714,14 +859,24 @@ fn test_purchase_not_enough_for_price() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -730,12 +885,17 @@ fn test_purchase_not_enough_for_price() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
 
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
@@ -761,14 +921,24 @@ fn test_purchase_not_enough_for_tax() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -777,6 +947,9 @@ fn test_purchase_not_enough_for_tax() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
@@ -791,6 +964,8 @@ fn test_purchase_not_enough_for_tax() {
 
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
@@ -812,14 +987,24 @@ fn test_purchase_by_token_id_not_available() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -828,6 +1013,8 @@ fn test_purchase_by_token_id_not_available() {
 
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[1].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     assert_eq!(ContractError::TokenNotAvailable {}, res.unwrap_err());
@@ -847,14 +1034,24 @@ fn test_purchase_by_token_id() {
     mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[1]).unwrap();
 
     let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
         end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: 1,
         amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
         amount_transferred: Uint128::zero(),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
     };
 
     STATE.save(deps.as_mut().storage, &state).unwrap();
@@ -864,6 +1061,8 @@ fn test_purchase_by_token_id() {
     // Purchase a token.
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
     assert_eq!(
@@ -874,8 +1073,11 @@ fn test_purchase_by_token_id() {
         res
     );
 
-    state.amount_to_send += Uint128::from(90u128);
+    state.amount_to_send = coins(90, "uusd");
     state.amount_sold += Uint128::from(1u128);
+    state.amount_raised += Uint128::from(100u128);
+    state.amounts_raised = coins(100, "uusd");
+    state.taxes_paid = coins(50, "uusd");
     assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
 
     assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[0]));
@@ -889,6 +1091,8 @@ fn test_purchase_by_token_id() {
     // Purchase a second one.
     let msg = ExecuteMsg::PurchaseByTokenId {
         token_id: MOCK_TOKENS_FOR_SALE[1].to_owned(),
+        refund_recipient: None,
+        referrer: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
 
@@ -944,17 +1148,30 @@ fn test_multiple_purchases() {
     // Purchase 2 tokens
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(2),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
         end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: 3,
         amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
         amount_transferred: Uint128::zero(),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
     };
     STATE.save(deps.as_mut().storage, &state).unwrap();
 
@@ -970,8 +1187,11 @@ fn test_multiple_purchases() {
         res
     );
 
-    state.amount_to_send += Uint128::from(180u128);
+    state.amount_to_send = coins(180, "uusd");
     state.amount_sold += Uint128::from(2u128);
+    state.amount_raised += Uint128::from(200u128);
+    state.amounts_raised = coins(200, "uusd");
+    state.taxes_paid = coins(100, "uusd");
     assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
 
     assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[0]));
@@ -988,6 +1208,9 @@ fn test_multiple_purchases() {
     // Purchase max number of tokens.
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let info = mock_info("sender", &coins(300u128, "uusd"));
@@ -1008,8 +1231,11 @@ fn test_multiple_purchases() {
     );
 
     assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[2]));
-    state.amount_to_send += Uint128::from(90u128);
+    state.amount_to_send = coins(270, "uusd");
     state.amount_sold += Uint128::from(1u128);
+    state.amount_raised += Uint128::from(100u128);
+    state.amounts_raised = coins(300, "uusd");
+    state.taxes_paid = coins(150, "uusd");
     assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
 
     assert_eq!(
@@ -1024,6 +1250,9 @@ fn test_multiple_purchases() {
     // Try to purchase an additional token when limit has already been reached.
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -1033,6 +1262,9 @@ fn test_multiple_purchases() {
     // User 2 tries to purchase 2 but only 1 is left.
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(2),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let info = mock_info("user2", &coins(300, "uusd"));
@@ -1057,8 +1289,11 @@ fn test_multiple_purchases() {
         PURCHASES.load(deps.as_ref().storage, "user2").unwrap()
     );
     assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[3]));
-    state.amount_to_send += Uint128::from(90u128);
+    state.amount_to_send = coins(360, "uusd");
     state.amount_sold += Uint128::from(1u128);
+    state.amount_raised += Uint128::from(100u128);
+    state.amounts_raised = coins(400, "uusd");
+    state.taxes_paid = coins(200, "uusd");
     assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
 
     assert_eq!(
@@ -1071,6 +1306,9 @@ fn test_multiple_purchases() {
     // User 2 tries to purchase again.
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let info = mock_info("user2", &coins(150, "uusd"));
@@ -1098,17 +1336,30 @@ fn test_purchase_more_than_allowed_per_wallet() {
     // Try to purchase 4
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(4),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
 
     let state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
         end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: 3,
         amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
         amount_transferred: Uint128::zero(),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
     };
     STATE.save(deps.as_mut().storage, &state).unwrap();
 
@@ -1136,14 +1387,24 @@ fn test_end_sale_not_expired() {
     init(deps.as_mut(), None);
 
     let state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
         end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: 2,
         amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
         amount_transferred: Uint128::zero(),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
     };
     STATE.save(deps.as_mut().storage, &state).unwrap();
     NUMBER_OF_TOKENS_AVAILABLE
@@ -1195,10 +1456,15 @@ fn test_integration_conditions_not_met() {
     let msg = ExecuteMsg::StartSale {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(5u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: Some(2),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -1210,40 +1476,63 @@ fn test_integration_conditions_not_met() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("A", &coins(150, "uusd"));
     let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("B", &coins(150, "uusd"));
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("C", &coins(150, "uusd"));
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     // Using current time since start time wasn't provided
     let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time)).unwrap();
     let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
 
     let state = State {
+        campaign_id: Uint64::one(),
+        start_time: start_expiration,
         end_time: end_expiration,
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(5u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: 2,
         amount_sold: Uint128::from(4u128),
-        amount_to_send: Uint128::from(360u128),
+        amount_raised: Uint128::from(400u128),
+        amounts_raised: coins(400, "uusd"),
+        taxes_paid: coins(200, "uusd"),
+        amount_to_send: coins(360, "uusd"),
         amount_transferred: Uint128::zero(),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
     };
     assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
 
@@ -1280,7 +1569,7 @@ fn test_integration_conditions_not_met() {
     env.block.time = env.block.time.plus_hours(1);
 
     // User B claims their own refund.
-    let msg = ExecuteMsg::ClaimRefund {};
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
     let info = mock_info("B", &[]);
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
     assert_eq!(
@@ -1290,6 +1579,7 @@ fn test_integration_conditions_not_met() {
                 to_address: "B".to_string(),
                 amount: coins(150, "uusd"),
             }))
+            .add_event(get_refund_event("B", "150uusd"))
             .add_submessage(generate_economics_message("B", "ClaimRefund")),
         res
     );
@@ -1328,6 +1618,8 @@ fn test_integration_conditions_not_met() {
             .add_attribute("action", "issue_refunds_and_burn_tokens")
             .add_messages(refund_msgs)
             .add_messages(burn_msgs)
+            .add_event(get_refund_event("A", "300uusd"))
+            .add_event(get_refund_event("C", "150uusd"))
             .add_submessage(generate_economics_message("anyone", "EndSale")),
         res
     );
@@ -1374,10 +1666,15 @@ fn test_integration_conditions_met() {
     let msg = ExecuteMsg::StartSale {
         start_time: None,
         end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(3u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(300, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: Some(2),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -1385,44 +1682,70 @@ fn test_integration_conditions_met() {
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("A", &coins(150, "uusd"));
     let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("B", &coins(150, "uusd"));
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("C", &coins(150, "uusd"));
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("D", &coins(150, "uusd"));
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
     // Using current time since start time wasn't provided
     let current_time = env.block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time)).unwrap();
     let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
     let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: start_expiration,
         end_time: end_expiration,
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(3u128),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(300, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
         max_amount_per_wallet: 2,
         amount_sold: Uint128::from(5u128),
-        amount_to_send: Uint128::from(450u128),
+        amount_raised: Uint128::from(500u128),
+        amounts_raised: coins(500, "uusd"),
+        taxes_paid: coins(250, "uusd"),
+        amount_to_send: coins(450, "uusd"),
         amount_transferred: Uint128::zero(),
         recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
     };
     assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
 
@@ -1580,7 +1903,7 @@ fn test_integration_conditions_met() {
     //     res
     // );
 
-    state.amount_to_send = Uint128::zero();
+    state.amount_to_send = vec![];
     assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
 
     // Burned tokens removed.
@@ -1607,14 +1930,24 @@ fn test_end_sale_single_purchase() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height - 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::from(1u128),
-                amount_to_send: Uint128::from(100u128),
+                amount_raised: Uint128::from(100u128),
+                amounts_raised: coins(100, "uusd"),
+                taxes_paid: vec![],
+                amount_to_send: coins(100, "uusd"),
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -1625,9 +1958,13 @@ fn test_end_sale_single_purchase() {
             "A",
             &vec![Purchase {
                 token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+                tier_id: None,
+                denom: "uusd".to_string(),
+                price: Uint128::new(100),
                 purchaser: "A".to_string(),
                 tax_amount: Uint128::zero(),
                 msgs: vec![],
+                referrer: None,
             }],
         )
         .unwrap();
@@ -1658,15 +1995,25 @@ fn test_end_sale_all_tokens_sold() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
                 // Sale has not expired yet.
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::from(1u128),
-                amount_to_send: Uint128::from(100u128),
+                amount_raised: Uint128::from(100u128),
+                amounts_raised: coins(100, "uusd"),
+                taxes_paid: vec![],
+                amount_to_send: coins(100, "uusd"),
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -1677,9 +2024,13 @@ fn test_end_sale_all_tokens_sold() {
             "A",
             &vec![Purchase {
                 token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+                tier_id: None,
+                denom: "uusd".to_string(),
+                price: Uint128::new(100),
                 purchaser: "A".to_string(),
                 tax_amount: Uint128::zero(),
                 msgs: vec![],
+                referrer: None,
             }],
         )
         .unwrap();
@@ -1714,15 +2065,25 @@ fn test_end_sale_some_tokens_sold_threshold_met() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
                 // Sale has not expired yet.
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::from(2u128),
-                amount_to_send: Uint128::from(100u128),
+                amount_raised: Uint128::from(200u128),
+                amounts_raised: coins(200, "uusd"),
+                taxes_paid: vec![],
+                amount_to_send: coins(100, "uusd"),
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -1733,9 +2094,13 @@ fn test_end_sale_some_tokens_sold_threshold_met() {
             "A",
             &vec![Purchase {
                 token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+                tier_id: None,
+                denom: "uusd".to_string(),
+                price: Uint128::new(100),
                 purchaser: "A".to_string(),
                 tax_amount: Uint128::zero(),
                 msgs: vec![],
+                referrer: None,
             }],
         )
         .unwrap();
@@ -1776,15 +2141,25 @@ fn test_end_sale_some_tokens_sold_threshold_not_met() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
                 // Sale has not expired yet.
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(2u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(200, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::from(0u128),
-                amount_to_send: Uint128::from(100u128),
+                amount_raised: Uint128::from(0u128),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: coins(100, "uusd"),
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -1795,9 +2170,13 @@ fn test_end_sale_some_tokens_sold_threshold_not_met() {
             "A",
             &vec![Purchase {
                 token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+                tier_id: None,
+                denom: "uusd".to_string(),
+                price: Uint128::new(100),
                 purchaser: "A".to_string(),
                 tax_amount: Uint128::zero(),
                 msgs: vec![],
+                referrer: None,
             }],
         )
         .unwrap();
@@ -1814,6 +2193,451 @@ fn test_end_sale_some_tokens_sold_threshold_not_met() {
     assert_eq!(err, ContractError::SaleNotEnded {});
 }
 
+#[test]
+fn test_end_sale_referral_rewards() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..3] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(300, "uusd"),
+        hard_cap: None,
+        referral_rate: Some(Decimal::percent(10)),
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let purchase = |number_of_tokens: u32, referrer: Option<&str>| ExecuteMsg::Purchase {
+        number_of_tokens: Some(number_of_tokens),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: referrer.map(str::to_string),
+    };
+
+    let info = mock_info("A", &coins(100, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, purchase(1, Some("A")));
+    assert_eq!(
+        ContractError::InvalidReferrer {
+            msg: "Purchasers cannot refer themselves".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let info = mock_info("A", &coins(200, "uusd"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        purchase(2, Some("referrer")),
+    )
+    .unwrap();
+    let info = mock_info("B", &coins(100, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, purchase(1, None)).unwrap();
+
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    state.end_time = Expiration::AtHeight(mock_env().block.height - 1);
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let info = mock_info("anyone", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "transfer_tokens_and_send_funds")
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[0],
+                AndrAddr::from_string("A")
+            ))
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[1],
+                AndrAddr::from_string("A")
+            ))
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[2],
+                AndrAddr::from_string("B")
+            ))
+            .add_message(BankMsg::Send {
+                to_address: "referrer".to_string(),
+                amount: coins(20, "uusd"),
+            })
+            .add_submessage(generate_economics_message("anyone", "EndSale")),
+        res
+    );
+
+    // The remainder is sent to the recipient.
+    let state = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(coins(280, "uusd"), state.amount_to_send);
+
+    let msg = QueryMsg::Referrals {
+        campaign_id: None,
+        start_after: None,
+        limit: None,
+    };
+    let res: Vec<Referral> = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        vec![Referral {
+            referrer: "referrer".to_string(),
+            number_of_tokens: 2,
+            volume: coins(200, "uusd"),
+            rewards_paid: coins(20, "uusd"),
+        }],
+        res
+    );
+}
+
+#[test]
+fn test_end_sale_referral_rewards_after_royalties() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    let state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 2),
+        end_time: Expiration::AtHeight(mock_env().block.height - 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        // Together with the 10% royalty this is more than the price.
+        referral_rate: Some(Decimal::percent(95)),
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::one(),
+        amount_raised: Uint128::new(100),
+        amounts_raised: coins(100, "uusd"),
+        taxes_paid: coins(50, "uusd"),
+        amount_to_send: coins(90, "uusd"),
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+    let purchase = Purchase {
+        referrer: Some("referrer".to_string()),
+        ..get_purchase(MOCK_TOKENS_FOR_SALE[0], "A")
+    };
+    PURCHASES
+        .save(deps.as_mut().storage, "A", &vec![purchase])
+        .unwrap();
+    REFERRALS
+        .save(
+            deps.as_mut().storage,
+            (1, "referrer"),
+            &Referral {
+                referrer: "referrer".to_string(),
+                number_of_tokens: 1,
+                volume: coins(100, "uusd"),
+                rewards_paid: vec![],
+            },
+        )
+        .unwrap();
+
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let info = mock_info("anyone", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "transfer_tokens_and_send_funds")
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[0],
+                AndrAddr::from_string("A")
+            ))
+            // 95% of the 90 left after the royalty.
+            .add_message(BankMsg::Send {
+                to_address: "referrer".to_string(),
+                amount: coins(85, "uusd"),
+            })
+            .add_submessages(get_rates_messages())
+            .add_submessage(generate_economics_message("anyone", "EndSale")),
+        res
+    );
+
+    let state = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(coins(5, "uusd"), state.amount_to_send);
+}
+
+fn get_lazy_mint() -> LazyMint {
+    LazyMint {
+        token_id_template: "edition-{id}".to_string(),
+        first_id: Uint128::one(),
+        last_id: Uint128::new(3),
+        token_uri_template: Some("ipfs://edition/{id}.json".to_string()),
+        extension: TokenExtension {
+            publisher: "publisher".to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_set_lazy_mint() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    let info = mock_info("owner", &[]);
+
+    let msg = ExecuteMsg::SetLazyMint {
+        lazy_mint: Some(LazyMint {
+            token_id_template: "edition".to_string(),
+            ..get_lazy_mint()
+        }),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidLazyMint {
+            msg: "Token id template must contain {id} once".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = ExecuteMsg::SetLazyMint {
+        lazy_mint: Some(get_lazy_mint()),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    );
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    assert_eq!(
+        ContractError::InvalidLazyMint {
+            msg: "Cannot lazy mint while pre-minted tokens are available".to_string(),
+        },
+        res.unwrap_err()
+    );
+    NUMBER_OF_TOKENS_AVAILABLE
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "set_lazy_mint")
+            .add_attribute("token_id_template", "edition-{id}")
+            .add_attribute("first_id", "1")
+            .add_attribute("last_id", "3")
+            .add_submessage(generate_economics_message("owner", "SetLazyMint")),
+        res
+    );
+
+    let msg = QueryMsg::LazyMint {};
+    let res: Option<LazyMintResponse> =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        Some(LazyMintResponse {
+            lazy_mint: get_lazy_mint(),
+            sold_amount: Uint128::zero(),
+            remaining: Uint128::new(3),
+        }),
+        res
+    );
+
+    let res = mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[1]);
+    assert_eq!(
+        ContractError::InvalidLazyMint {
+            msg: "Tokens cannot be pre-minted while lazy minting".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    // Ids that were sold cannot be reused by a new range, even after the range was removed.
+    LAZY_MINT_SALES
+        .save(deps.as_mut().storage, &Uint128::new(2))
+        .unwrap();
+    let msg = ExecuteMsg::SetLazyMint { lazy_mint: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = QueryMsg::LazyMint {};
+    let res: Option<LazyMintResponse> =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(None, res);
+
+    let msg = ExecuteMsg::SetLazyMint {
+        lazy_mint: Some(LazyMint {
+            first_id: Uint128::new(2),
+            last_id: Uint128::new(10),
+            ..get_lazy_mint()
+        }),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidLazyMint {
+            msg: "First id must be at least 3 as lower ids were already sold".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = ExecuteMsg::SetLazyMint {
+        lazy_mint: Some(LazyMint {
+            first_id: Uint128::new(3),
+            last_id: Uint128::new(10),
+            ..get_lazy_mint()
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let lazy_mint = LAZY_MINT.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        vec!["edition-3".to_string()],
+        get_lazy_token_ids(deps.as_ref().storage, &lazy_mint, 1).unwrap()
+    );
+}
+
+#[test]
+fn test_end_sale_lazy_mint() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    let msg = ExecuteMsg::SetLazyMint {
+        lazy_mint: Some(get_lazy_mint()),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(200, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(2),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(200, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let purchases = PURCHASES.load(deps.as_ref().storage, "A").unwrap();
+    assert_eq!(
+        vec!["edition-1", "edition-2"],
+        purchases
+            .iter()
+            .map(|purchase| purchase.token_id.as_str())
+            .collect::<Vec<&str>>()
+    );
+    let res: SummaryResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Summary {}).unwrap()).unwrap();
+    assert_eq!(Some(Uint128::one()), res.tokens_remaining);
+
+    // The tokens are minted to the purchaser rather than transferred.
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    let get_mint_message = |id: u128| WasmMsg::Execute {
+        contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
+        msg: encode_binary(&Cw721ExecuteMsg::Mint {
+            token_id: format!("edition-{id}"),
+            owner: "A".to_string(),
+            token_uri: Some(format!("ipfs://edition/{id}.json")),
+            extension: TokenExtension {
+                publisher: "publisher".to_string(),
+            },
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "transfer_tokens_and_send_funds")
+            .add_message(get_mint_message(1))
+            .add_message(get_mint_message(2))
+            .add_submessage(generate_economics_message("owner", "EndSale")),
+        res
+    );
+}
+
+#[test]
+fn test_claim_refund_lazy_mint() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    let msg = ExecuteMsg::SetLazyMint {
+        lazy_mint: Some(get_lazy_mint()),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    // Only the tokens left in the range can be purchased.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(5),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(500, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        Uint128::new(3),
+        LAZY_MINT_SALES.load(deps.as_ref().storage).unwrap()
+    );
+
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    state.end_time = Expiration::AtHeight(mock_env().block.height - 1);
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    // Nothing was minted, so the range is available again once refunded.
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg).unwrap();
+    assert_eq!(
+        Uint128::zero(),
+        LAZY_MINT_SALES.load(deps.as_ref().storage).unwrap()
+    );
+}
+
 #[test]
 fn test_end_sale_limit_zero() {
     let mut deps = mock_dependencies_custom(&[]);
@@ -1823,14 +2647,24 @@ fn test_end_sale_limit_zero() {
         .save(
             deps.as_mut().storage,
             &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
                 end_time: Expiration::AtHeight(mock_env().block.height - 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
                 max_amount_per_wallet: 5,
                 amount_sold: Uint128::from(1u128),
-                amount_to_send: Uint128::from(100u128),
+                amount_raised: Uint128::from(100u128),
+                amounts_raised: coins(100, "uusd"),
+                taxes_paid: vec![],
+                amount_to_send: coins(100, "uusd"),
                 amount_transferred: Uint128::zero(),
                 recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
             },
         )
         .unwrap();
@@ -1844,9 +2678,13 @@ fn test_end_sale_limit_zero() {
             "A",
             &vec![Purchase {
                 token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+                tier_id: None,
+                denom: "uusd".to_string(),
+                price: Uint128::new(100),
                 purchaser: "A".to_string(),
                 tax_amount: Uint128::zero(),
                 msgs: vec![],
+                referrer: None,
             }],
         )
         .unwrap();
@@ -1909,6 +2747,9 @@ fn test_addresslist() {
     // Not whitelisted user
     let msg = ExecuteMsg::Purchase {
         number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
     };
     let info = mock_info("not_whitelisted", &[]);
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -2011,3 +2852,2562 @@ fn test_update_token_contract_not_cw721() {
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
     assert_eq!(ContractError::Unauthorized {}, res);
 }
+
+#[test]
+fn test_add_tier() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    let msg = ExecuteMsg::AddTier {
+        tier: get_tier(1, 100, Some(2)),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg);
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    let res = add_tier(deps.as_mut(), get_tier(1, 0, Some(2)));
+    assert_eq!(
+        ContractError::InvalidTier {
+            operation: "validate".to_string(),
+            msg: "Tier price must be greater than zero".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let res = add_tier(deps.as_mut(), get_tier(1, 100, Some(2))).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "add_tier")
+            .add_attribute("tier_id", "1")
+            .add_attribute("label", "tier 1")
+            .add_attribute("price", "100")
+            .add_submessage(generate_economics_message("owner", "AddTier")),
+        res
+    );
+
+    let res = add_tier(deps.as_mut(), get_tier(1, 200, None));
+    assert_eq!(
+        ContractError::InvalidTier {
+            operation: "add".to_string(),
+            msg: "Tier with id 1 already exists".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    // Tiers cannot be modified once the sale has started.
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+    let msg = ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: None,
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let res = add_tier(deps.as_mut(), get_tier(2, 100, None));
+    assert_eq!(ContractError::SaleStarted {}, res.unwrap_err());
+}
+
+#[test]
+fn test_update_and_remove_tier() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    let msg = ExecuteMsg::UpdateTier {
+        tier: get_tier(1, 100, None),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(
+        ContractError::InvalidTier {
+            operation: "update".to_string(),
+            msg: "Tier with id 1 does not exist".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    add_tier(deps.as_mut(), get_tier(1, 100, None)).unwrap();
+    add_tier(deps.as_mut(), get_tier(2, 200, Some(5))).unwrap();
+
+    let msg = ExecuteMsg::UpdateTier {
+        tier: get_tier(1, 150, Some(10)),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // The limit cannot go below what has already been sold.
+    TIER_SALES
+        .save(deps.as_mut().storage, 2, &Uint128::new(3))
+        .unwrap();
+    let msg = ExecuteMsg::UpdateTier {
+        tier: get_tier(2, 200, Some(2)),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(
+        ContractError::InvalidTier {
+            operation: "update".to_string(),
+            msg: "Tier limit cannot be lower than the amount already sold".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = ExecuteMsg::RemoveTier {
+        tier_id: Uint64::new(2),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(
+        ContractError::InvalidTier {
+            operation: "remove".to_string(),
+            msg: "Cannot remove a tier that has sold tokens".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = ExecuteMsg::RemoveTier {
+        tier_id: Uint64::new(1),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let msg = QueryMsg::Tiers {
+        start_after: None,
+        limit: None,
+    };
+    let res: Vec<TierResponse> = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        vec![TierResponse {
+            tier: get_tier(2, 200, Some(5)),
+            sold_amount: Uint128::new(3),
+            remaining: Some(Uint128::new(2)),
+        }],
+        res
+    );
+}
+
+#[test]
+fn test_purchase_tier() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    add_tier(deps.as_mut(), get_tier(1, 200, Some(2))).unwrap();
+
+    let state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: Some(Uint64::new(2)),
+        refund_recipient: None,
+        referrer: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("A", &coins(200, "uusd")),
+        msg,
+    );
+    assert_eq!(
+        ContractError::InvalidTier {
+            operation: "purchase".to_string(),
+            msg: "Tier with id 2 does not exist".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    // Tier price is used instead of the sale price.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: Some(Uint64::new(1)),
+        refund_recipient: None,
+        referrer: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("A", &coins(100, "uusd")),
+        msg,
+    );
+    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+
+    // Wants 3 but only 2 are left in the tier.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(3),
+        tier_id: Some(Uint64::new(1)),
+        refund_recipient: None,
+        referrer: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("A", &coins(600, "uusd")),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(200, "uusd")
+            })
+            .add_attribute("action", "purchase")
+            .add_attribute("number_of_tokens_wanted", "3")
+            .add_attribute("number_of_tokens_purchased", "2")
+            .add_submessage(generate_economics_message("A", "Purchase")),
+        res
+    );
+
+    let purchases = PURCHASES.load(deps.as_ref().storage, "A").unwrap();
+    assert_eq!(
+        vec!["tier-1-1".to_string(), "tier-1-2".to_string()],
+        purchases
+            .iter()
+            .map(|p| p.token_id.clone())
+            .collect::<Vec<String>>()
+    );
+
+    let msg = QueryMsg::Tier {
+        tier_id: Uint64::new(1),
+    };
+    let res: TierResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(Uint128::new(2), res.sold_amount);
+    assert_eq!(Some(Uint128::zero()), res.remaining);
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: None,
+        tier_id: Some(Uint64::new(1)),
+        refund_recipient: None,
+        referrer: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("B", &coins(200, "uusd")),
+        msg,
+    );
+    assert_eq!(ContractError::AllTokensPurchased {}, res.unwrap_err());
+
+    // All tiers and pre-minted tokens are sold out, so anyone can end the sale and the tier
+    // tokens are minted to the purchaser.
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+    let mint_msg = |token_id: &str| {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
+            msg: encode_binary(&Cw721ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: "A".to_string(),
+                token_uri: None,
+                extension: TokenExtension {
+                    publisher: "publisher".to_string(),
+                },
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "transfer_tokens_and_send_funds")
+            .add_message(mint_msg("tier-1-1"))
+            .add_message(mint_msg("tier-1-2"))
+            .add_submessage(generate_economics_message("anyone", "EndSale")),
+        res
+    );
+}
+
+#[test]
+fn test_purchase_tier_refund() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    add_tier(deps.as_mut(), get_tier(1, 200, Some(2))).unwrap();
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(400, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: Some(Uint64::new(1)),
+        refund_recipient: None,
+        referrer: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("A", &coins(200, "uusd")),
+        msg,
+    )
+    .unwrap();
+
+    // Sale expires without meeting the minimum.
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    state.end_time = Expiration::AtHeight(mock_env().block.height - 1);
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "A".to_string(),
+            amount: coins(200, "uusd"),
+        }),
+        res.messages[0].msg
+    );
+
+    // The refunded token is available again.
+    assert_eq!(
+        Uint128::zero(),
+        TIER_SALES.load(deps.as_ref().storage, 1).unwrap()
+    );
+}
+
+#[test]
+fn test_start_sale_invalid_funding_goal() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+
+    let start_sale = |soft_cap: Coin, hard_cap: Option<Coin>| ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap,
+        hard_cap,
+        referral_rate: None,
+        max_amount_per_wallet: None,
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+    let info = mock_info("owner", &[]);
+
+    let msg = start_sale(coin(100, "uluna"), None);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidFundingGoal {
+            msg: "Soft cap must be in the base denom".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(coin(100, "uusd"), Some(coin(1000, "uluna")));
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidFundingGoal {
+            msg: "Hard cap must be in the base denom".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(coin(500, "uusd"), Some(coin(400, "uusd")));
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidFundingGoal {
+            msg: "Hard cap must be at least the soft cap and the price".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(coin(500, "uusd"), Some(coin(1000, "uusd")));
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "hard_cap" && attr.value == "1000uusd"));
+}
+
+#[test]
+fn test_purchase_hard_cap() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..4] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    STATE
+        .save(
+            deps.as_mut().storage,
+            &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
+                end_time: Expiration::AtHeight(mock_env().block.height + 1),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: Some(coin(200, "uusd")),
+                referral_rate: None,
+                max_amount_per_wallet: 5,
+                amount_sold: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
+                amount_transferred: Uint128::zero(),
+                recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
+            },
+        )
+        .unwrap();
+
+    // Only two tokens fit under the hard cap, the rest is refunded.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(3),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(300, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(100, "uusd")
+            })
+            .add_attribute("action", "purchase")
+            .add_attribute("number_of_tokens_wanted", "2")
+            .add_attribute("number_of_tokens_purchased", "2")
+            .add_submessage(generate_economics_message("A", "Purchase")),
+        res
+    );
+    assert_eq!(
+        Uint128::new(200),
+        STATE.load(deps.as_ref().storage).unwrap().amount_raised
+    );
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("B", &coins(100, "uusd")),
+        msg,
+    );
+    assert_eq!(ContractError::HardCapReached {}, res.unwrap_err());
+
+    let msg = ExecuteMsg::PurchaseByTokenId {
+        token_id: MOCK_TOKENS_FOR_SALE[3].to_owned(),
+        refund_recipient: None,
+        referrer: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("B", &coins(100, "uusd")),
+        msg,
+    );
+    assert_eq!(ContractError::HardCapReached {}, res.unwrap_err());
+
+    // Anyone can end the sale once the hard cap is reached.
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "transfer_tokens_and_send_funds")
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[0],
+                AndrAddr::from_string("A")
+            ))
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[1],
+                AndrAddr::from_string("A")
+            ))
+            .add_submessage(generate_economics_message("anyone", "EndSale")),
+        res
+    );
+}
+
+#[test]
+fn test_purchase_cw20() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..2] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Cw20(Cw20Coin {
+            address: "cw20".to_string(),
+            amount: Uint128::new(100),
+        })],
+        pricing: None,
+        soft_cap: coin(100, "cw20"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    // Native funds cannot be used to purchase tokens.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("A", &coins(100, "uusd")),
+        msg,
+    );
+    assert_eq!(
+        ContractError::InvalidFunds {
+            msg: "Sale must be paid for with CW20 tokens".to_string()
+        },
+        res.unwrap_err()
+    );
+
+    let hook_msg = Cw20HookMsg::Purchase {
+        number_of_tokens: None,
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "A".to_string(),
+        amount: Uint128::new(300),
+        msg: encode_binary(&hook_msg).unwrap(),
+    });
+
+    // Only the CW20 the sale is priced in is accepted.
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other_cw20", &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        ContractError::InvalidFunds {
+            msg: "Sale cannot be paid for with other_cw20".to_string()
+        },
+        res.unwrap_err()
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("cw20", &[]), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(WasmMsg::Execute {
+                contract_addr: "cw20".to_string(),
+                msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "A".to_string(),
+                    amount: Uint128::new(100),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+            .add_attribute("action", "purchase")
+            .add_attribute("number_of_tokens_wanted", "5")
+            .add_attribute("number_of_tokens_purchased", "2")
+            .add_submessage(generate_economics_message("cw20", "Receive")),
+        res
+    );
+    assert_eq!(2, PURCHASES.load(deps.as_ref().storage, "A").unwrap().len());
+
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(Uint128::new(200), state.amount_raised);
+    assert_eq!(coins(200, "cw20"), state.amount_to_send);
+
+    // The raised CW20 tokens are sent to the recipient once the sale ends.
+    let msg = ExecuteMsg::EndSale { limit: None };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw20".to_string(),
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "recipient".to_string(),
+                amount: Uint128::new(200),
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        res.messages[0].msg
+    );
+}
+
+#[test]
+fn test_purchase_cw20_with_tax_and_royalty() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let modules = vec![Module {
+        name: Some(RATES.to_owned()),
+        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
+        is_mutable: false,
+    }];
+    init(deps.as_mut(), Some(modules));
+
+    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Cw20(Cw20Coin {
+            address: "cw20".to_string(),
+            amount: Uint128::new(100),
+        })],
+        pricing: None,
+        soft_cap: coin(100, "cw20"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let hook_msg = Cw20HookMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "sender".to_string(),
+        amount: Uint128::new(150),
+        msg: encode_binary(&hook_msg).unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), mock_info("cw20", &[]), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "purchase")
+            .add_attribute("number_of_tokens_wanted", "1")
+            .add_attribute("number_of_tokens_purchased", "1")
+            .add_submessage(generate_economics_message("cw20", "Receive")),
+        res
+    );
+
+    state.amount_to_send = coins(90, "cw20");
+    state.amount_sold += Uint128::from(1u128);
+    state.amount_raised += Uint128::from(100u128);
+    state.amounts_raised = coins(100, "cw20");
+    state.taxes_paid = coins(50, "cw20");
+    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
+
+    // The rates are paid out in the CW20 once the sale ends.
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+    let royalty_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "cw20".to_string(),
+        msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: MOCK_ROYALTY_RECIPIENT.to_string(),
+            amount: Uint128::new(10),
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    let tax_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "cw20".to_string(),
+        msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: MOCK_TAX_RECIPIENT.to_string(),
+            amount: Uint128::new(50),
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+    assert!(messages.contains(&royalty_msg));
+    assert!(messages.contains(&tax_msg));
+}
+
+#[test]
+fn test_claim_refund_cw20() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    STATE
+        .save(
+            deps.as_mut().storage,
+            &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
+                end_time: Expiration::AtHeight(mock_env().block.height - 1),
+                prices: vec![Funds::Cw20(Cw20Coin {
+                    address: "cw20".to_string(),
+                    amount: Uint128::new(100),
+                })],
+                pricing: None,
+                soft_cap: coin(500, "cw20"),
+                hard_cap: None,
+                referral_rate: None,
+                max_amount_per_wallet: 5,
+                amount_sold: Uint128::new(1),
+                amount_raised: Uint128::new(100),
+                amounts_raised: coins(100, "cw20"),
+                taxes_paid: vec![],
+                amount_to_send: coins(100, "cw20"),
+                amount_transferred: Uint128::zero(),
+                recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
+            },
+        )
+        .unwrap();
+    PURCHASES
+        .save(
+            deps.as_mut().storage,
+            "A",
+            &vec![Purchase {
+                denom: "cw20".to_string(),
+                ..get_purchase(MOCK_TOKENS_FOR_SALE[0], "A")
+            }],
+        )
+        .unwrap();
+
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw20".to_string(),
+            // Price plus the tax paid.
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "A".to_string(),
+                amount: Uint128::new(150),
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        res.messages[0].msg
+    );
+}
+
+#[test]
+fn test_start_sale_invalid_prices() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+
+    let start_sale = |prices: Vec<Funds>| ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
+        prices,
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: None,
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+    let info = mock_info("owner", &[]);
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), start_sale(vec![]));
+    assert_eq!(
+        ContractError::InvalidPrices {
+            msg: "At least one price must be provided".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(vec![
+        Funds::Native(coin(100, "uusd")),
+        Funds::Native(coin(200, "uusd")),
+    ]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(ContractError::DuplicateCoinDenoms {}, res.unwrap_err());
+
+    let msg = start_sale(vec![
+        Funds::Native(coin(100, "uusd")),
+        Funds::Native(coin(0, "uluna")),
+    ]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidPrices {
+            msg: "Prices must be greater than zero when accepting multiple denoms".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(vec![
+        Funds::Native(coin(100, "uusd")),
+        Funds::Native(coin(200, "uluna")),
+        Funds::Cw20(Cw20Coin {
+            address: "cw20".to_string(),
+            amount: Uint128::new(50),
+        }),
+    ]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "prices" && attr.value == "100uusd,200uluna,50cw20"));
+}
+
+#[test]
+fn test_purchase_multiple_denoms() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..2] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+    add_tier(deps.as_mut(), get_tier(1, 200, None)).unwrap();
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![
+            Funds::Native(coin(100, "uusd")),
+            Funds::Native(coin(200, "uluna")),
+            Funds::Cw20(Cw20Coin {
+                address: "cw20".to_string(),
+                amount: Uint128::new(50),
+            }),
+        ],
+        pricing: None,
+        soft_cap: coin(400, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+
+    // Purchases can only be paid for in a single denom.
+    let info = mock_info("A", &[coin(100, "uusd"), coin(200, "uluna")]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(
+        ContractError::InvalidFunds {
+            msg: "Purchases must be paid for in a single denom".to_string()
+        },
+        res.unwrap_err()
+    );
+
+    // Denoms that are not accepted are ignored.
+    let info = mock_info("A", &coins(100, "uatom"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+
+    let info = mock_info("A", &coins(100, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    let info = mock_info("B", &coins(200, "uluna"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // The tier price is converted to the denom being paid in.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: Some(Uint64::new(1)),
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("B", &coins(400, "uluna"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "C".to_string(),
+        amount: Uint128::new(50),
+        msg: encode_binary(&Cw20HookMsg::Purchase {
+            number_of_tokens: Some(1),
+            tier_id: Some(Uint64::new(1)),
+            refund_recipient: None,
+            referrer: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), mock_info("cw20", &[]), msg);
+    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "C".to_string(),
+        amount: Uint128::new(100),
+        msg: encode_binary(&Cw20HookMsg::Purchase {
+            number_of_tokens: Some(1),
+            tier_id: Some(Uint64::new(1)),
+            refund_recipient: None,
+            referrer: None,
+        })
+        .unwrap(),
+    });
+    execute(deps.as_mut(), mock_env(), mock_info("cw20", &[]), msg).unwrap();
+
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    // 100 + 100 + 200 + 200 in the base denom.
+    assert_eq!(Uint128::new(600), state.amount_raised);
+    assert_eq!(
+        vec![coin(100, "uusd"), coin(600, "uluna"), coin(100, "cw20")],
+        state.amounts_raised
+    );
+    assert_eq!(state.amounts_raised, state.amount_to_send);
+
+    let res: State =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(state.amounts_raised, res.amounts_raised);
+
+    // Each denom is sent to the recipient once all tokens have been transferred.
+    let msg = ExecuteMsg::EndSale { limit: None };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw20".to_string(),
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "recipient".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        res.messages[0].msg
+    );
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: vec![coin(100, "uusd"), coin(600, "uluna")],
+        }),
+        res.messages[1].msg
+    );
+}
+
+#[test]
+fn test_claim_refund_multiple_denoms() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..2] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![
+            Funds::Native(coin(100, "uusd")),
+            Funds::Native(coin(200, "uluna")),
+        ],
+        pricing: None,
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(100, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    let info = mock_info("A", &coins(200, "uluna"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // Sale expires without meeting the soft cap.
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    state.end_time = Expiration::AtHeight(mock_env().block.height - 1);
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(100, "uusd"),
+            })
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(200, "uluna"),
+            })
+            .add_attribute("action", "claim_refund")
+            .add_event(get_refund_event("A", "100uusd,200uluna"))
+            .add_submessage(generate_economics_message("A", "ClaimRefund")),
+        res
+    );
+}
+
+#[test]
+fn test_claim_refund_to_refund_recipient() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..2] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    // The refund is sent with the crowdfund's authority, so it cannot be used to mint tokens.
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "stolen".to_string(),
+        owner: "A".to_string(),
+        token_uri: None,
+        extension: TokenExtension {
+            publisher: "A".to_string(),
+        },
+    };
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(2),
+        tier_id: None,
+        refund_recipient: Some(Recipient::from_string(MOCK_TOKEN_CONTRACT).with_msg(mint_msg)),
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(200, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidRecipientType {
+            msg: "Refund recipients cannot have a message".to_string()
+        },
+        res.unwrap_err()
+    );
+
+    let refund_recipient = Recipient::from_string("refund_recipient");
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(2),
+        tier_id: None,
+        refund_recipient: Some(refund_recipient),
+        referrer: None,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // Sale expires without meeting the soft cap.
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    state.end_time = Expiration::AtHeight(mock_env().block.height - 1);
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg).unwrap();
+
+    let refund_msg = SubMsg::reply_always(
+        BankMsg::Send {
+            to_address: "refund_recipient".to_string(),
+            amount: coins(200, "uusd"),
+        },
+        1000,
+    );
+    assert_eq!(
+        Response::new()
+            .add_submessage(refund_msg)
+            .add_attribute("action", "claim_refund")
+            .add_event(get_refund_event("A", "200uusd"))
+            .add_submessage(generate_economics_message("A", "ClaimRefund")),
+        res
+    );
+
+    // The refund recipient rejects the funds, so they are kept for the purchaser.
+    let reply_msg = Reply {
+        id: 1000,
+        result: SubMsgResult::Err("rejected".to_string()),
+    };
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "refund_failed")
+            .add_attribute("purchaser", "A")
+            .add_attribute("error", "rejected"),
+        res
+    );
+
+    let failed_refunds: Vec<Funds> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FailedRefunds {
+                purchaser: "A".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(vec![Funds::Native(coin(200, "uusd"))], failed_refunds);
+
+    let msg = ExecuteMsg::ClaimFailedRefund {};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg.clone()).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(200, "uusd"),
+            })
+            .add_attribute("action", "claim_failed_refund")
+            .add_attribute("purchaser", "A")
+            .add_submessage(generate_economics_message("A", "ClaimFailedRefund")),
+        res
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg);
+    assert_eq!(ContractError::NoPurchases {}, res.unwrap_err());
+}
+
+#[test]
+fn test_start_sale_invalid_pricing() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+
+    let start_sale = |pricing: Pricing| ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: Some(pricing),
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: None,
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+    let info = mock_info("owner", &[]);
+
+    let msg = start_sale(Pricing::LinearDecay {
+        floor_price: Uint128::new(100),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidPrices {
+            msg: "Floor price must be lower than the base price".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(Pricing::Step {
+        step: Uint128::zero(),
+        increment: Uint128::new(10),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert_eq!(
+        ContractError::InvalidPrices {
+            msg: "Step and increment must be greater than zero".to_string(),
+        },
+        res.unwrap_err()
+    );
+}
+
+#[test]
+fn test_purchase_linear_decay_pricing() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..2] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    // Halfway through the sale.
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 5),
+        end_time: Expiration::AtHeight(mock_env().block.height + 5),
+        prices: vec![
+            Funds::Native(coin(100, "uusd")),
+            Funds::Native(coin(200, "uluna")),
+        ],
+        pricing: Some(Pricing::LinearDecay {
+            floor_price: Uint128::new(50),
+        }),
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = QueryMsg::CurrentPrice {};
+    let res: Vec<Funds> = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        vec![
+            Funds::Native(coin(75, "uusd")),
+            Funds::Native(coin(150, "uluna")),
+        ],
+        res
+    );
+
+    // The tokens cost 75uusd each at this point, so the price paid at the start no longer
+    // purchases two of them.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(2),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(150, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let purchases = PURCHASES.load(deps.as_ref().storage, "A").unwrap();
+    assert_eq!(2, purchases.len());
+    assert!(purchases
+        .iter()
+        .all(|purchase| purchase.price == Uint128::new(75)));
+    state = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(Uint128::new(150), state.amount_raised);
+
+    // The sale fails and the purchaser is refunded the price they paid even though it decayed
+    // further.
+    state.end_time = Expiration::AtHeight(mock_env().block.height - 1);
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(150, "uusd"),
+            })
+            .add_attribute("action", "claim_refund")
+            .add_event(get_refund_event("A", "150uusd"))
+            .add_submessage(generate_economics_message("A", "ClaimRefund")),
+        res
+    );
+}
+
+#[test]
+fn test_purchase_step_pricing() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..3] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    let state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height - 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 1),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: Some(Pricing::Step {
+            step: Uint128::new(2),
+            increment: Uint128::new(10),
+        }),
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    // The purchase is truncated at the end of the first step and the excess is refunded.
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(3),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(300, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(100, "uusd"),
+            })
+            .add_attribute("action", "purchase")
+            .add_attribute("number_of_tokens_wanted", "2")
+            .add_attribute("number_of_tokens_purchased", "2")
+            .add_submessage(generate_economics_message("A", "Purchase")),
+        res
+    );
+
+    let msg = QueryMsg::CurrentPrice {};
+    let res: Vec<Funds> = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(vec![Funds::Native(coin(110, "uusd"))], res);
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("B", &coins(100, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+
+    let info = mock_info("B", &coins(110, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let purchases = PURCHASES.load(deps.as_ref().storage, "B").unwrap();
+    assert_eq!(Uint128::new(110), purchases[0].price);
+    let state = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(Uint128::new(310), state.amount_raised);
+}
+
+#[test]
+fn test_start_sale_presale() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+
+    let start_sale = |presale: PresaleMsg| ExecuteMsg::StartSale {
+        start_time: Some(Milliseconds(current_time + 10)),
+        end_time: Milliseconds(current_time + 20),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: Some(5),
+        recipient: Recipient::from_string("recipient"),
+        presale: Some(presale),
+        milestones: None,
+    };
+    let presale = PresaleMsg {
+        start_time: None,
+        whitelist: Whitelist::Addresses(vec!["whitelisted".to_string()]),
+        prices: None,
+        max_amount_per_wallet: None,
+    };
+    let info = mock_info("owner", &[]);
+
+    let msg = start_sale(PresaleMsg {
+        start_time: Some(Milliseconds(current_time + 10)),
+        ..presale.clone()
+    });
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidPresale {
+            msg: "Presale must start before the sale".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(PresaleMsg {
+        whitelist: Whitelist::Addresses(vec![]),
+        ..presale.clone()
+    });
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidPresale {
+            msg: "Whitelist cannot be empty".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(PresaleMsg {
+        prices: Some(vec![Funds::Native(coin(50, "uluna"))]),
+        ..presale.clone()
+    });
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidPresale {
+            msg: "Presale base denom must be the same as the sale".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), info, start_sale(presale)).unwrap();
+    let presale_start = expiration_from_milliseconds(Milliseconds(current_time)).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "presale_start_time" && attr.value == presale_start.to_string()));
+
+    // The presale defaults to the sale terms.
+    assert_eq!(
+        Some(Presale {
+            start_time: presale_start,
+            whitelist: Whitelist::Addresses(vec!["whitelisted".to_string()]),
+            prices: vec![Funds::Native(coin(100, "uusd"))],
+            max_amount_per_wallet: 5,
+        }),
+        STATE.load(deps.as_ref().storage).unwrap().presale
+    );
+}
+
+#[test]
+fn test_purchase_presale() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    for &token_id in &MOCK_TOKENS_FOR_SALE[..3] {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    let mut state = State {
+        campaign_id: Uint64::one(),
+        start_time: Expiration::AtHeight(mock_env().block.height + 1),
+        end_time: Expiration::AtHeight(mock_env().block.height + 2),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: 5,
+        amount_sold: Uint128::zero(),
+        amount_raised: Uint128::zero(),
+        amounts_raised: vec![],
+        taxes_paid: vec![],
+        amount_to_send: vec![],
+        amount_transferred: Uint128::zero(),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        cancellation: None,
+    };
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+
+    // Nobody can purchase before the sale starts without a presale.
+    let info = mock_info("A", &coins(100, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(ContractError::SaleNotStarted {}, res.unwrap_err());
+
+    state.presale = Some(Presale {
+        start_time: Expiration::AtHeight(mock_env().block.height),
+        whitelist: Whitelist::Addresses(vec!["A".to_string()]),
+        prices: vec![Funds::Native(coin(50, "uusd"))],
+        max_amount_per_wallet: 1,
+    });
+    STATE.save(deps.as_mut().storage, &state).unwrap();
+
+    let info = mock_info("B", &coins(100, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    // Whitelisted addresses purchase at the presale price, up to the presale limit.
+    let info = mock_info("A", &coins(100, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(50, "uusd"),
+            })
+            .add_attribute("action", "purchase")
+            .add_attribute("number_of_tokens_wanted", "1")
+            .add_attribute("number_of_tokens_purchased", "1")
+            .add_submessage(generate_economics_message("A", "Purchase")),
+        res
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    assert_eq!(ContractError::PurchaseLimitReached {}, res.unwrap_err());
+
+    // Once the sale starts the sale terms apply to everyone.
+    let mut env = mock_env();
+    env.block.height += 1;
+    execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
+    let info = mock_info("B", &coins(100, "uusd"));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        Uint128::new(250),
+        STATE.load(deps.as_ref().storage).unwrap().amount_raised
+    );
+
+    // Refunds return the price that was paid.
+    env.block.height += 1;
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    let res = execute(deps.as_mut(), env, mock_info("A", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "A".to_string(),
+            amount: coins(150, "uusd"),
+        }),
+        res.messages[0].msg
+    );
+}
+
+#[test]
+fn test_purchase_presale_address_list() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
+
+    STATE
+        .save(
+            deps.as_mut().storage,
+            &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height + 1),
+                end_time: Expiration::AtHeight(mock_env().block.height + 2),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
+                max_amount_per_wallet: 5,
+                amount_sold: Uint128::zero(),
+                amount_raised: Uint128::zero(),
+                amounts_raised: vec![],
+                taxes_paid: vec![],
+                amount_to_send: vec![],
+                amount_transferred: Uint128::zero(),
+                recipient: Recipient::from_string("recipient"),
+                presale: Some(Presale {
+                    start_time: Expiration::AtHeight(mock_env().block.height),
+                    whitelist: Whitelist::AddressList(AndrAddr::from_string(
+                        MOCK_ADDRESS_LIST_CONTRACT,
+                    )),
+                    prices: vec![Funds::Native(coin(100, "uusd"))],
+                    max_amount_per_wallet: 5,
+                }),
+                cancellation: None,
+            },
+        )
+        .unwrap();
+
+    let msg = ExecuteMsg::PurchaseByTokenId {
+        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("not_whitelisted", &coins(100, "uusd"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    let info = mock_info("sender", &coins(100, "uusd"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        1,
+        PURCHASES
+            .load(deps.as_ref().storage, "sender")
+            .unwrap()
+            .len()
+    );
+}
+
+fn get_milestone(id: u64, percent: Decimal, deadline: Option<Expiration>) -> Milestone {
+    Milestone {
+        id: Uint64::new(id),
+        description: format!("Milestone {id}"),
+        percent,
+        recipient: Recipient::from_string("recipient"),
+        deadline,
+        status: MilestoneStatus::Pending,
+        released: vec![],
+    }
+}
+
+#[test]
+fn test_start_sale_milestones() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+
+    let start_sale = |milestones: Vec<MilestoneMsg>| ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds(current_time + 10),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: None,
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: Some(milestones),
+    };
+    let milestone = |percent: u64, deadline: Option<Milliseconds>| MilestoneMsg {
+        description: "Milestone".to_string(),
+        percent: Decimal::percent(percent),
+        recipient: None,
+        deadline,
+    };
+    let info = mock_info("owner", &[]);
+
+    let msg = start_sale(vec![milestone(40, None), milestone(50, None)]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidMilestone {
+            msg: "Milestone percentages must add up to one".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(vec![milestone(100, Some(Milliseconds(current_time + 5)))]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(
+        ContractError::InvalidMilestone {
+            msg: "Milestone deadline must be after the end of the sale".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = start_sale(vec![
+        milestone(40, None),
+        MilestoneMsg {
+            recipient: Some(Recipient::from_string("other")),
+            ..milestone(60, Some(Milliseconds(current_time + 20)))
+        },
+    ]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "milestones" && attr.value == "2"));
+
+    let milestones: Vec<Milestone> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Milestones {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            Milestone {
+                description: "Milestone".to_string(),
+                ..get_milestone(1, Decimal::percent(40), None)
+            },
+            Milestone {
+                description: "Milestone".to_string(),
+                recipient: Recipient::from_string("other"),
+                ..get_milestone(
+                    2,
+                    Decimal::percent(60),
+                    Some(expiration_from_milliseconds(Milliseconds(current_time + 20)).unwrap())
+                )
+            },
+        ],
+        milestones
+    );
+}
+
+#[test]
+fn test_milestones_escrow_and_cancel() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    deps.querier.tokens_left_to_burn = 0;
+
+    STATE
+        .save(
+            deps.as_mut().storage,
+            &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
+                end_time: Expiration::AtHeight(mock_env().block.height - 1),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
+                max_amount_per_wallet: 5,
+                amount_sold: Uint128::new(2),
+                amount_raised: Uint128::new(200),
+                amounts_raised: coins(200, "uusd"),
+                taxes_paid: vec![],
+                // Royalties have already been taken out.
+                amount_to_send: coins(180, "uusd"),
+                amount_transferred: Uint128::zero(),
+                recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
+            },
+        )
+        .unwrap();
+    for (purchaser, token_id) in [
+        ("A", MOCK_TOKENS_FOR_SALE[0]),
+        ("B", MOCK_TOKENS_FOR_SALE[1]),
+    ] {
+        PURCHASES
+            .save(
+                deps.as_mut().storage,
+                purchaser,
+                &vec![get_purchase(token_id, purchaser)],
+            )
+            .unwrap();
+    }
+    for milestone in [
+        get_milestone(1, Decimal::percent(40), None),
+        get_milestone(2, Decimal::percent(60), None),
+    ] {
+        MILESTONES
+            .save(deps.as_mut().storage, milestone.id.u64(), &milestone)
+            .unwrap();
+    }
+
+    // The tokens are transferred as usual but the funds are kept in escrow.
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(MOCK_CONDITIONS_MET_CONTRACT);
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let info = mock_info("anyone", &[]);
+    execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "transfer_tokens_and_send_funds")
+            .add_submessage(generate_economics_message("anyone", "EndSale")),
+        res
+    );
+    assert!(STATE.may_load(deps.as_ref().storage).unwrap().is_none());
+
+    let escrow: Option<Escrow> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Escrow { campaign_id: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Escrow {
+            campaign_id: Uint64::one(),
+            total: coins(180, "uusd"),
+            released: vec![],
+            contributions: coins(200, "uusd"),
+            refundable: vec![],
+            prices: vec![Funds::Native(coin(100, "uusd"))],
+            cancelled: false,
+        }),
+        escrow
+    );
+    assert_eq!(
+        coins(100, "uusd"),
+        ESCROW_CONTRIBUTIONS
+            .load(deps.as_ref().storage, (1, "A"))
+            .unwrap()
+    );
+
+    // Only the owner can attest a milestone before its deadline.
+    let msg = ExecuteMsg::ReleaseMilestone {
+        milestone_id: Uint64::new(1),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    );
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "release_milestone")
+            .add_attribute("milestone_id", "1")
+            .add_attribute("amount", "72uusd")
+            .add_message(BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: coins(72, "uusd"),
+            })
+            .add_submessage(generate_economics_message("owner", "ReleaseMilestone")),
+        res
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(
+        ContractError::InvalidMilestone {
+            msg: "Milestone is not pending".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    let msg = ExecuteMsg::CancelMilestones {};
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    );
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "cancel_milestones")
+            .add_attribute("refundable", "108uusd")
+            .add_submessage(generate_economics_message("owner", "CancelMilestones")),
+        res
+    );
+    assert_eq!(
+        MilestoneStatus::Cancelled,
+        MILESTONES.load(deps.as_ref().storage, 2).unwrap().status
+    );
+
+    // Refunds that are not claimed by the next sale remain claimable through their campaign.
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+    let start_sale = ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds(current_time + 10),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: None,
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+
+    // Purchasers are refunded pro rata to what they paid.
+    let msg = ExecuteMsg::ClaimRefund { campaign_id: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg.clone()).unwrap();
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "A".to_string(),
+            amount: coins(54, "uusd"),
+        }),
+        res.messages[0].msg
+    );
+    let res = execute(deps.as_mut(), mock_env(), mock_info("A", &[]), msg);
+    assert_eq!(ContractError::NoPurchases {}, res.unwrap_err());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        start_sale,
+    )
+    .unwrap();
+    assert!(ESCROW.may_load(deps.as_ref().storage).unwrap().is_none());
+    assert!(MILESTONES.is_empty(deps.as_ref().storage));
+
+    let msg = QueryMsg::Escrow {
+        campaign_id: Some(Uint64::one()),
+    };
+    let escrow: Option<Escrow> =
+        from_json(query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(coins(108, "uusd"), escrow.unwrap().refundable);
+
+    let claim_refund = ExecuteMsg::ClaimRefund {
+        campaign_id: Some(Uint64::one()),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("B", &[]), claim_refund).unwrap();
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "B".to_string(),
+            amount: coins(54, "uusd"),
+        }),
+        res.messages[0].msg
+    );
+    // The escrow is removed once every refund is claimed.
+    let escrow: Option<Escrow> = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(escrow.is_none());
+}
+
+#[test]
+fn test_release_milestone_after_deadline() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    let deadline = Expiration::AtHeight(mock_env().block.height + 1);
+    for milestone in [
+        get_milestone(1, Decimal::percent(50), None),
+        Milestone {
+            recipient: Recipient::from_string("other"),
+            ..get_milestone(2, Decimal::percent(50), Some(deadline))
+        },
+    ] {
+        MILESTONES
+            .save(deps.as_mut().storage, milestone.id.u64(), &milestone)
+            .unwrap();
+    }
+
+    // Milestones cannot be released before the funds are escrowed.
+    let msg = ExecuteMsg::ReleaseMilestone {
+        milestone_id: Uint64::new(1),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        ContractError::InvalidMilestone {
+            msg: "Milestone funds are not escrowed".to_string(),
+        },
+        res.unwrap_err()
+    );
+
+    ESCROW
+        .save(
+            deps.as_mut().storage,
+            &Escrow {
+                campaign_id: Uint64::one(),
+                total: coins(101, "uusd"),
+                released: vec![],
+                contributions: coins(101, "uusd"),
+                refundable: vec![],
+                prices: vec![Funds::Native(coin(101, "uusd"))],
+                cancelled: false,
+            },
+        )
+        .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::ReleaseMilestone {
+        milestone_id: Uint64::new(2),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    );
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    // Anyone can release the milestone once the deadline has passed, the last milestone receives
+    // what is left after rounding.
+    let mut env = mock_env();
+    env.block.height += 1;
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "other".to_string(),
+            amount: coins(51, "uusd"),
+        }),
+        res.messages[0].msg
+    );
+
+    let milestone: Milestone = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Milestone {
+                milestone_id: Uint64::new(2),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(MilestoneStatus::Released, milestone.status);
+    assert_eq!(coins(51, "uusd"), milestone.released);
+    assert_eq!(
+        coins(101, "uusd"),
+        ESCROW.load(deps.as_ref().storage).unwrap().released
+    );
+}
+
+#[test]
+fn test_cancel_sale() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let modules = vec![Module {
+        name: Some(RATES.to_owned()),
+        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
+        is_mutable: false,
+    }];
+    init(deps.as_mut(), Some(modules));
+    for &token_id in MOCK_TOKENS_FOR_SALE {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(MOCK_CONDITIONS_NOT_MET_CONTRACT);
+    let current_time = env.block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+    let msg = ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds(current_time + 10),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(100, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: Some(2),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    // The soft cap is met, so without cancelling the sale would go through.
+    let purchase_msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let info = mock_info("A", &coins(150, "uusd"));
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        purchase_msg.clone(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::CancelSale {
+        return_unsold_to: Some(AndrAddr::from_string("owner")),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("A", &[]), msg.clone());
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let cancelled_at = Milliseconds::from_nanos(env.block.time.nanos());
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "cancel_sale")
+            .add_event(
+                Event::new("sale_cancelled")
+                    .add_attribute("cancelled_at", cancelled_at.to_string())
+                    .add_attribute("amount_sold", "1")
+                    .add_attribute("amount_raised", "100")
+                    .add_attribute("return_unsold_to", "owner")
+            )
+            .add_submessage(generate_economics_message("owner", "CancelSale")),
+        res
+    );
+    assert_eq!(
+        Some(Cancellation {
+            cancelled_at,
+            return_unsold_to: Some(AndrAddr::from_string("owner")),
+        }),
+        STATE.load(deps.as_ref().storage).unwrap().cancellation
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg);
+    assert_eq!(ContractError::SaleCancelled {}, res.unwrap_err());
+
+    let res = execute(deps.as_mut(), env.clone(), info, purchase_msg);
+    assert_eq!(ContractError::SaleCancelled {}, res.unwrap_err());
+
+    // Refunds, including taxes, can be claimed before the end of the sale.
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("A", &[]),
+        ExecuteMsg::ClaimRefund { campaign_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "A".to_string(),
+                amount: coins(150, "uusd"),
+            })
+            .add_attribute("action", "claim_refund")
+            .add_event(get_refund_event("A", "150uusd"))
+            .add_submessage(generate_economics_message("A", "ClaimRefund")),
+        res
+    );
+
+    // Unsold tokens are returned instead of burned.
+    deps.querier.tokens_left_to_burn = 2;
+    let msg = ExecuteMsg::EndSale { limit: None };
+    let info = mock_info("anyone", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "issue_refunds_and_burn_tokens")
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[5],
+                AndrAddr::from_string("owner")
+            ))
+            .add_message(get_transfer_message(
+                MOCK_TOKENS_FOR_SALE[6],
+                AndrAddr::from_string("owner")
+            ))
+            .add_submessage(generate_economics_message("anyone", "EndSale")),
+        res
+    );
+
+    deps.querier.tokens_left_to_burn = 0;
+    execute(deps.as_mut(), env, info, msg).unwrap();
+    assert!(STATE.may_load(deps.as_ref().storage).unwrap().is_none());
+}
+
+#[test]
+fn test_cancel_sale_after_transfer() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+
+    let msg = ExecuteMsg::CancelSale {
+        return_unsold_to: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    );
+    assert_eq!(ContractError::NoOngoingSale {}, res.unwrap_err());
+
+    STATE
+        .save(
+            deps.as_mut().storage,
+            &State {
+                campaign_id: Uint64::one(),
+                start_time: Expiration::AtHeight(mock_env().block.height - 1),
+                end_time: Expiration::AtHeight(mock_env().block.height - 1),
+                prices: vec![Funds::Native(coin(100, "uusd"))],
+                pricing: None,
+                soft_cap: coin(100, "uusd"),
+                hard_cap: None,
+                referral_rate: None,
+                max_amount_per_wallet: 5,
+                amount_sold: Uint128::new(2),
+                amount_raised: Uint128::new(200),
+                amounts_raised: coins(200, "uusd"),
+                taxes_paid: vec![],
+                amount_to_send: coins(200, "uusd"),
+                amount_transferred: Uint128::one(),
+                recipient: Recipient::from_string("recipient"),
+                presale: None,
+                cancellation: None,
+            },
+        )
+        .unwrap();
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(ContractError::SaleExecuted {}, res.unwrap_err());
+}
+
+#[test]
+fn test_query_contributors_and_summary() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let modules = vec![Module {
+        name: Some(RATES.to_owned()),
+        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
+        is_mutable: false,
+    }];
+    init(deps.as_mut(), Some(modules));
+
+    for &token_id in MOCK_TOKENS_FOR_SALE {
+        mint(deps.as_mut(), token_id).unwrap();
+    }
+    add_tier(deps.as_mut(), get_tier(1, 200, Some(2))).unwrap();
+
+    // No sale has been started yet.
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Summary {});
+    assert_eq!(ContractError::NoOngoingSale {}, res.unwrap_err());
+
+    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+    let end_time = Milliseconds(current_time + 10_000);
+    let msg = ExecuteMsg::StartSale {
+        start_time: None,
+        end_time,
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(500, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: Some(5),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    for (purchaser, number_of_tokens) in [("A", 1u32), ("B", 2), ("C", 3)] {
+        let msg = ExecuteMsg::Purchase {
+            number_of_tokens: Some(number_of_tokens),
+            tier_id: None,
+            refund_recipient: None,
+            referrer: None,
+        };
+        let info = mock_info(purchaser, &coins(150 * number_of_tokens as u128, "uusd"));
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    let purchases = PURCHASES.load(deps.as_ref().storage, "B").unwrap();
+    let res: Vec<PurchaseResponse> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Purchases {
+                purchaser: "B".to_string(),
+                campaign_id: None,
+                start_after: Some(purchases[0].token_id.clone()),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![PurchaseResponse {
+            token_id: purchases[1].token_id.clone(),
+            tier_id: None,
+            denom: "uusd".to_string(),
+            price: Uint128::new(100),
+            tax_amount: Uint128::new(50),
+        }],
+        res
+    );
+
+    let get_contributor = |purchaser: &str, number_of_tokens: u32| Contributor {
+        purchaser: purchaser.to_string(),
+        number_of_tokens,
+        amount: Uint128::from(100 * number_of_tokens as u128),
+        amounts_paid: coins(100 * number_of_tokens as u128, "uusd"),
+        taxes_paid: coins(50 * number_of_tokens as u128, "uusd"),
+    };
+
+    let res: Vec<Contributor> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Contributors {
+                campaign_id: None,
+                start_after: Some("A".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(vec![get_contributor("B", 2), get_contributor("C", 3)], res);
+
+    let res: Vec<Contributor> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TopContributors {
+                campaign_id: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(vec![get_contributor("C", 3), get_contributor("B", 2)], res);
+
+    let res: SummaryResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Summary {}).unwrap()).unwrap();
+    let end_time = expiration_from_milliseconds(end_time).unwrap();
+    let Expiration::AtTime(end_time) = end_time else {
+        panic!("end time should be time based");
+    };
+    assert_eq!(
+        SummaryResponse {
+            amount_raised: Uint128::new(600),
+            amounts_raised: coins(600, "uusd"),
+            taxes_paid: coins(300, "uusd"),
+            amount_sold: Uint128::new(6),
+            // One minted token and the two tier tokens are left.
+            tokens_remaining: Some(Uint128::new(3)),
+            soft_cap_reached: true,
+            time_left: Some(Milliseconds::from_nanos(
+                end_time.nanos() - mock_env().block.time.nanos()
+            )),
+        },
+        res
+    );
+}
+
+#[test]
+fn test_campaign_history() {
+    let mut deps = mock_dependencies_custom(&[]);
+    init(deps.as_mut(), None);
+    deps.querier.tokens_left_to_burn = 0;
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(MOCK_CONDITIONS_MET_CONTRACT);
+
+    let current_time = env.block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
+    let start_sale_msg = ExecuteMsg::StartSale {
+        start_time: None,
+        end_time: Milliseconds(current_time + 10_000),
+        prices: vec![Funds::Native(coin(100, "uusd"))],
+        pricing: None,
+        soft_cap: coin(200, "uusd"),
+        hard_cap: None,
+        referral_rate: None,
+        max_amount_per_wallet: Some(2),
+        recipient: Recipient::from_string("recipient"),
+        presale: None,
+        milestones: None,
+    };
+    let purchase_msg = ExecuteMsg::Purchase {
+        number_of_tokens: Some(1),
+        tier_id: None,
+        refund_recipient: None,
+        referrer: None,
+    };
+    let owner = mock_info("owner", &[]);
+
+    // The first campaign is cancelled.
+    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        start_sale_msg.clone(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("A", &coins(100, "uusd")),
+        purchase_msg.clone(),
+    )
+    .unwrap();
+    let msg = ExecuteMsg::CancelSale {
+        return_unsold_to: None,
+    };
+    execute(deps.as_mut(), env.clone(), owner.clone(), msg).unwrap();
+    let msg = ExecuteMsg::EndSale { limit: None };
+    execute(deps.as_mut(), env.clone(), owner.clone(), msg.clone()).unwrap();
+    execute(deps.as_mut(), env.clone(), owner.clone(), msg).unwrap();
+    assert!(STATE.may_load(deps.as_ref().storage).unwrap().is_none());
+
+    // The second campaign is ongoing.
+    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[1]).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), owner, start_sale_msg).unwrap();
+    assert!(res.attributes.contains(&attr("campaign_id", "2")));
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("B", &coins(100, "uusd")),
+        purchase_msg,
+    )
+    .unwrap();
+
+    let campaigns: Vec<Campaign> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Campaigns {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(2, campaigns.len());
+    assert_eq!(CampaignOutcome::Cancelled, campaigns[0].outcome);
+    assert_eq!(Uint128::one(), campaigns[0].state.amount_sold);
+    assert!(campaigns[0].state.cancellation.is_some());
+
+    // The ongoing campaign reflects the live state of the sale.
+    let campaign: Campaign = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Campaign { campaign_id: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(campaigns[1], campaign);
+    assert_eq!(Uint64::new(2), campaign.id);
+    assert_eq!(CampaignOutcome::Ongoing, campaign.outcome);
+    assert_eq!(STATE.load(deps.as_ref().storage).unwrap(), campaign.state);
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Campaign {
+            campaign_id: Some(Uint64::new(3)),
+        },
+    );
+    assert_eq!(ContractError::SaleDoesNotExist {}, res.unwrap_err());
+
+    // Purchases and contributors of past campaigns are kept.
+    let purchases: Vec<PurchaseResponse> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Purchases {
+                purchaser: "A".to_string(),
+                campaign_id: Some(Uint64::one()),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![PurchaseResponse {
+            token_id: MOCK_TOKENS_FOR_SALE[0].to_string(),
+            tier_id: None,
+            denom: "uusd".to_string(),
+            price: Uint128::new(100),
+            tax_amount: Uint128::zero(),
+        }],
+        purchases
+    );
+
+    let contributors: Vec<Contributor> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Contributors {
+                campaign_id: Some(Uint64::one()),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec!["A".to_string()],
+        contributors
+            .into_iter()
+            .map(|contributor| contributor.purchaser)
+            .collect::<Vec<String>>()
+    );
+
+    // Defaults to the ongoing campaign.
+    let contributors: Vec<Contributor> = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::TopContributors {
+                campaign_id: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec!["B".to_string()],
+        contributors
+            .into_iter()
+            .map(|contributor| contributor.purchaser)
+            .collect::<Vec<String>>()
+    );
+}
@@ -3,7 +3,8 @@ use crate::state::{
 };
 
 use andromeda_non_fungible_tokens::marketplace::{
-    Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SaleIdsResponse, SaleStateResponse, Status,
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SaleIdsResponse,
+    SaleStateResponse, Status,
 };
 use andromeda_std::ado_base::ownership::OwnershipMessage;
 use andromeda_std::ado_contract::ADOContract;
@@ -16,10 +17,11 @@ use andromeda_std::common::expiration::{
 };
 use andromeda_std::common::Milliseconds;
 use andromeda_std::{
-    ado_base::{hooks::AndromedaHook, InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
+    ado_base::{InstantiateMsg as BaseInstantiateMsg, MigrateMsg},
     common::{encode_binary, rates::get_tax_amount, Funds},
     error::ContractError,
 };
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
 use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg, OwnerOfResponse};
 
 #[cfg(not(feature = "library"))]
@@ -74,27 +76,30 @@ pub fn execute(
     let contract = ADOContract::default();
     let ctx = ExecuteContext::new(deps, info, env);
 
-    if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
+    let hook_response = if !matches!(msg, ExecuteMsg::UpdateAppContract { .. })
         && !matches!(
             msg,
             ExecuteMsg::Ownership(OwnershipMessage::UpdateOwner { .. })
-        )
-    {
-        contract.module_hook::<Response>(
-            &ctx.deps.as_ref(),
-            AndromedaHook::OnExecute {
-                sender: ctx.info.sender.to_string(),
-                payload: encode_binary(&msg)?,
-            },
-        )?;
-    }
+        ) {
+        // CW20 purchases are checked against the buyer rather than the CW20 contract.
+        let sender = match &msg {
+            ExecuteMsg::Receive(receive_msg) => receive_msg.sender.clone(),
+            _ => ctx.info.sender.to_string(),
+        };
+        contract.on_execute(&ctx.deps.as_ref(), sender, encode_binary(&msg)?)?
+    } else {
+        Response::new()
+    };
 
-    match msg {
+    let res = match msg {
         ExecuteMsg::AMPReceive(pkt) => {
             ADOContract::default().execute_amp_receive(ctx, pkt, handle_execute)
         }
         _ => handle_execute(ctx, msg),
-    }
+    }?;
+    Ok(res
+        .add_submessages(hook_response.messages)
+        .add_events(hook_response.events))
 }
 
 pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Response, ContractError> {
@@ -107,13 +112,23 @@ pub fn handle_execute(mut ctx: ExecuteContext, msg: ExecuteMsg) -> Result<Respon
     )?;
     let res = match msg {
         ExecuteMsg::ReceiveNft(msg) => handle_receive_cw721(ctx, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(ctx, msg),
         ExecuteMsg::UpdateSale {
             token_id,
             token_address,
             coin_denom,
+            uses_cw20,
+            price,
+            recipient,
+        } => execute_update_sale(
+            ctx,
+            token_id,
+            token_address,
             price,
+            coin_denom,
+            uses_cw20.unwrap_or(false),
             recipient,
-        } => execute_update_sale(ctx, token_id, token_address, price, coin_denom, recipient),
+        ),
         ExecuteMsg::Buy {
             token_id,
             token_address,
@@ -142,6 +157,7 @@ fn handle_receive_cw721(
         Cw721HookMsg::StartSale {
             price,
             coin_denom,
+            uses_cw20,
             start_time,
             duration,
             recipient,
@@ -153,6 +169,7 @@ fn handle_receive_cw721(
             info.sender.to_string(),
             price,
             coin_denom,
+            uses_cw20.unwrap_or(false),
             start_time,
             duration,
             recipient,
@@ -169,12 +186,16 @@ fn execute_start_sale(
     token_address: String,
     price: Uint128,
     coin_denom: String,
+    uses_cw20: bool,
     start_time: Option<Milliseconds>,
     duration: Option<Milliseconds>,
     recipient: Option<Recipient>,
 ) -> Result<Response, ContractError> {
     // Price can't be zero
     ensure!(price > Uint128::zero(), ContractError::InvalidZeroAmount {});
+    if uses_cw20 {
+        deps.api.addr_validate(&coin_denom)?;
+    }
     // If start time wasn't provided, it will be set as the current_time
     let (start_expiration, current_time) = get_and_validate_start_time(&env, start_time)?;
 
@@ -197,6 +218,7 @@ fn execute_start_sale(
         sale_id.u128(),
         &TokenSaleState {
             coin_denom: coin_denom.clone(),
+            uses_cw20,
             sale_id,
             owner: sender,
             token_id: token_id.clone(),
@@ -212,6 +234,7 @@ fn execute_start_sale(
         attr("action", "start_sale"),
         attr("status", "Open"),
         attr("coin_denom", coin_denom),
+        attr("uses_cw20", uses_cw20.to_string()),
         attr("price", price),
         attr("sale_id", sale_id.to_string()),
         attr("token_id", token_id),
@@ -228,11 +251,15 @@ fn execute_update_sale(
     token_address: String,
     price: Uint128,
     coin_denom: String,
+    uses_cw20: bool,
     recipient: Option<Recipient>,
 ) -> Result<Response, ContractError> {
     let ExecuteContext { deps, info, .. } = ctx;
 
     nonpayable(&info)?;
+    if uses_cw20 {
+        deps.api.addr_validate(&coin_denom)?;
+    }
 
     let mut token_sale_state =
         get_existing_token_sale_state(deps.storage, &token_id, &token_address)?;
@@ -248,6 +275,7 @@ fn execute_update_sale(
 
     token_sale_state.price = price;
     token_sale_state.coin_denom = coin_denom.clone();
+    token_sale_state.uses_cw20 = uses_cw20;
     token_sale_state.recipient = recipient;
     TOKEN_SALE_STATE.save(
         deps.storage,
@@ -257,6 +285,7 @@ fn execute_update_sale(
     Ok(Response::new().add_attributes(vec![
         attr("action", "update_sale"),
         attr("coin_denom", coin_denom),
+        attr("uses_cw20", uses_cw20.to_string()),
         attr("price", price),
         attr("sale_id", token_sale_state.sale_id.to_string()),
         attr("token_id", token_id),
@@ -275,7 +304,59 @@ fn execute_buy(
         env,
         ..
     } = ctx;
+    let payment: Vec<Funds> = info.funds.into_iter().map(Funds::Native).collect();
+
+    buy(
+        &mut deps,
+        &env,
+        info.sender.to_string(),
+        payment,
+        token_id,
+        token_address,
+    )
+}
 
+fn execute_receive(
+    ctx: ExecuteContext,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        mut deps,
+        info,
+        env,
+        ..
+    } = ctx;
+    nonpayable(&info)?;
+
+    match from_json(&receive_msg.msg)? {
+        Cw20HookMsg::Buy {
+            token_id,
+            token_address,
+        } => {
+            let payment = Funds::Cw20(Cw20Coin {
+                address: info.sender.to_string(),
+                amount: receive_msg.amount,
+            });
+            buy(
+                &mut deps,
+                &env,
+                receive_msg.sender,
+                vec![payment],
+                token_id,
+                token_address,
+            )
+        }
+    }
+}
+
+fn buy(
+    deps: &mut DepsMut,
+    env: &Env,
+    purchaser: String,
+    payment: Vec<Funds>,
+    token_id: String,
+    token_address: String,
+) -> Result<Response, ContractError> {
     let mut token_sale_state =
         get_existing_token_sale_state(deps.storage, &token_id, &token_address)?;
 
@@ -302,13 +383,13 @@ fn execute_buy(
 
     // The owner can't buy his own NFT
     ensure!(
-        token_sale_state.owner != info.sender,
+        token_sale_state.owner != purchaser,
         ContractError::TokenOwnerCannotBuy {}
     );
 
     // Only one coin can be sent
     ensure!(
-        info.funds.len() == 1,
+        payment.len() == 1,
         ContractError::InvalidFunds {
             msg: "Sales ensure! exactly one coin to be sent.".to_string(),
         }
@@ -328,11 +409,20 @@ fn execute_buy(
     );
 
     let coin_denom = token_sale_state.coin_denom.clone();
-    let payment: &Coin = &info.funds[0];
+    let (is_valid_denom, amount_sent) = match &payment[0] {
+        Funds::Native(coin) => (
+            !token_sale_state.uses_cw20 && coin.denom == coin_denom,
+            coin.amount,
+        ),
+        Funds::Cw20(cw20_coin) => (
+            token_sale_state.uses_cw20 && cw20_coin.address == coin_denom,
+            cw20_coin.amount,
+        ),
+    };
 
     // Make sure funds are equal to the price and in the correct denomination
     ensure!(
-        payment.denom == coin_denom,
+        is_valid_denom,
         ContractError::InvalidFunds {
             msg: format!("No {coin_denom} assets are provided to sale"),
         }
@@ -344,14 +434,15 @@ fn execute_buy(
     TOKEN_SALE_STATE.save(deps.storage, key, &token_sale_state)?;
 
     // Calculate the funds to be received after tax
-    let after_tax_payment = purchase_token(&mut deps, &info, token_sale_state.clone())?;
+    let after_tax_payment =
+        purchase_token(deps, &purchaser, amount_sent, token_sale_state.clone())?;
     let mut resp = Response::new()
         .add_submessages(after_tax_payment.1)
         // Send NFT to buyer.
         .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: token_sale_state.token_address.clone(),
             msg: encode_binary(&Cw721ExecuteMsg::TransferNft {
-                recipient: info.sender.to_string(),
+                recipient: purchaser.clone(),
                 token_id: token_id.clone(),
             })?,
             funds: vec![],
@@ -359,15 +450,24 @@ fn execute_buy(
         .add_attribute("action", "buy")
         .add_attribute("token_id", token_id)
         .add_attribute("token_contract", token_sale_state.token_address)
-        .add_attribute("recipient", info.sender.to_string())
+        .add_attribute("recipient", purchaser)
         .add_attribute("sale_id", token_sale_state.sale_id);
     if !after_tax_payment.0.amount.is_zero() {
         let recipient = token_sale_state
             .recipient
             .unwrap_or(Recipient::from_string(token_sale_state.owner));
-        resp = resp.add_submessage(
-            recipient.generate_direct_msg(&deps.as_ref(), vec![after_tax_payment.0])?,
-        )
+        let msg = if token_sale_state.uses_cw20 {
+            recipient.generate_msg_cw20(
+                &deps.as_ref(),
+                Cw20Coin {
+                    address: after_tax_payment.0.denom,
+                    amount: after_tax_payment.0.amount,
+                },
+            )?
+        } else {
+            recipient.generate_direct_msg(&deps.as_ref(), vec![after_tax_payment.0])?
+        };
+        resp = resp.add_submessage(msg)
     }
 
     Ok(resp)
@@ -423,21 +523,32 @@ fn execute_cancel(
 
 fn purchase_token(
     deps: &mut DepsMut,
-    info: &MessageInfo,
+    purchaser: &str,
+    amount_sent: Uint128,
     state: TokenSaleState,
 ) -> Result<(Coin, Vec<SubMsg>), ContractError> {
-    let total_cost = Coin::new(state.price.u128(), state.coin_denom.clone());
+    let total_cost = if state.uses_cw20 {
+        Funds::Cw20(Cw20Coin {
+            address: state.coin_denom.clone(),
+            amount: state.price,
+        })
+    } else {
+        Funds::Native(Coin::new(state.price.u128(), state.coin_denom.clone()))
+    };
 
     let mut total_tax_amount = Uint128::zero();
 
     let (msgs, _events, remainder) = ADOContract::default().on_funds_transfer(
         &deps.as_ref(),
-        info.sender.to_string(),
-        Funds::Native(total_cost),
+        purchaser.to_owned(),
+        total_cost,
         encode_binary(&"")?,
     )?;
 
-    let remaining_amount = remainder.try_get_coin()?;
+    let remaining_amount = match remainder {
+        Funds::Native(coin) => coin,
+        Funds::Cw20(cw20_coin) => Coin::new(cw20_coin.amount.u128(), cw20_coin.address),
+    };
 
     let tax_amount = get_tax_amount(&msgs, state.price, remaining_amount.amount);
 
@@ -449,12 +560,11 @@ fn purchase_token(
         amount: state.price + total_tax_amount,
     };
     ensure!(
-        // has_coins(&info.funds, &required_payment),
-        info.funds[0].amount.eq(&required_payment.amount),
+        amount_sent.eq(&required_payment.amount),
         ContractError::InvalidFunds {
             msg: format!(
-                "Invalid funds provided, expected: {}, received: {}",
-                required_payment, info.funds[0]
+                "Invalid funds provided, expected: {}, received: {}{}",
+                required_payment, amount_sent, state.coin_denom
             )
         }
     );
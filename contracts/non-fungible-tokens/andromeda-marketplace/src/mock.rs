@@ -2,7 +2,7 @@
 
 use crate::contract::{execute, instantiate, query};
 use andromeda_non_fungible_tokens::marketplace::{
-    Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
 };
 use andromeda_std::amp::messages::AMPPkt;
 
@@ -71,6 +71,7 @@ pub fn mock_marketplace_instantiate_msg(
 pub fn mock_start_sale(
     price: Uint128,
     coin_denom: impl Into<String>,
+    uses_cw20: bool,
     duration: Option<Milliseconds>,
     start_time: Option<Milliseconds>,
     recipient: Option<Recipient>,
@@ -78,6 +79,7 @@ pub fn mock_start_sale(
     Cw721HookMsg::StartSale {
         price,
         coin_denom: coin_denom.into(),
+        uses_cw20: Some(uses_cw20),
         start_time,
         duration,
         recipient,
@@ -91,6 +93,16 @@ pub fn mock_buy_token(token_address: impl Into<String>, token_id: impl Into<Stri
     }
 }
 
+pub fn mock_buy_token_cw20(
+    token_address: impl Into<String>,
+    token_id: impl Into<String>,
+) -> Cw20HookMsg {
+    Cw20HookMsg::Buy {
+        token_id: token_id.into(),
+        token_address: token_address.into(),
+    }
+}
+
 pub fn mock_receive_packet(packet: AMPPkt) -> ExecuteMsg {
     ExecuteMsg::AMPReceive(packet)
 }
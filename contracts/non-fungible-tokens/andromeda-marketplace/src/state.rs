@@ -12,6 +12,8 @@ const DEFAULT_LIMIT: u64 = 10;
 #[cw_serde]
 pub struct TokenSaleState {
     pub coin_denom: String,
+    #[serde(default)]
+    pub uses_cw20: bool,
     pub sale_id: Uint128,
     pub owner: String,
     pub token_id: String,
@@ -57,6 +59,7 @@ impl From<TokenSaleState> for SaleStateResponse {
     fn from(token_sale_state: TokenSaleState) -> SaleStateResponse {
         SaleStateResponse {
             coin_denom: token_sale_state.coin_denom,
+            uses_cw20: token_sale_state.uses_cw20,
             sale_id: token_sale_state.sale_id,
             status: token_sale_state.status,
             price: token_sale_state.price,
@@ -1,5 +1,5 @@
 use andromeda_non_fungible_tokens::marketplace::{
-    Cw721HookMsg, ExecuteMsg, InstantiateMsg, Status,
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, Status,
 };
 use andromeda_std::{
     ado_base::modules::Module,
@@ -19,6 +19,7 @@ use cosmwasm_std::{
     to_json_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Env, Response, SubMsg, Uint128,
     WasmMsg,
 };
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
 use cw_utils::Expiration;
 
@@ -27,14 +28,15 @@ use crate::{
     contract::{execute, instantiate},
     state::{sale_infos, SaleInfo, TokenSaleState, TOKEN_SALE_STATE},
     testing::mock_querier::{
-        mock_dependencies_custom, MOCK_RATES_CONTRACT, MOCK_TOKEN_ADDR, MOCK_TOKEN_OWNER,
-        MOCK_UNCLAIMED_TOKEN, RATES,
+        mock_dependencies_custom, MOCK_CW20_CONTRACT, MOCK_RATES_CONTRACT, MOCK_TOKEN_ADDR,
+        MOCK_TOKEN_OWNER, MOCK_UNCLAIMED_TOKEN, RATES,
     },
 };
 
 fn start_sale(deps: DepsMut) {
     let hook_msg = Cw721HookMsg::StartSale {
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         price: Uint128::new(100),
         start_time: None,
         duration: None,
@@ -55,6 +57,7 @@ fn start_sale_future_start(deps: DepsMut, env: Env) {
     let current_time = env.block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
     let hook_msg = Cw721HookMsg::StartSale {
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         price: Uint128::new(100),
         // Add one to the current time to have it set in the future
         start_time: Some(Milliseconds(current_time + 1)),
@@ -76,6 +79,7 @@ fn start_sale_future_start_with_duration(deps: DepsMut, env: Env) {
     let current_time = env.block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
     let hook_msg = Cw721HookMsg::StartSale {
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         price: Uint128::new(100),
         // Add one to the current time to have it set in the future
         start_time: Some(Milliseconds(current_time + 1)),
@@ -112,6 +116,7 @@ fn assert_sale_created(deps: Deps, env: Env) {
     assert_eq!(
         TokenSaleState {
             coin_denom: "uusd".to_string(),
+            uses_cw20: false,
             sale_id: 1u128.into(),
             owner: MOCK_TOKEN_OWNER.to_string(),
             token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
@@ -149,6 +154,7 @@ fn assert_sale_created_future_start(deps: Deps, env: Env) {
     assert_eq!(
         TokenSaleState {
             coin_denom: "uusd".to_string(),
+            uses_cw20: false,
             sale_id: 1u128.into(),
             owner: MOCK_TOKEN_OWNER.to_string(),
             token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
@@ -353,6 +359,123 @@ fn test_execute_buy_works() {
     let _res = execute(deps.as_mut(), env, info, msg).unwrap();
 }
 
+fn start_sale_cw20(deps: DepsMut) {
+    let hook_msg = Cw721HookMsg::StartSale {
+        coin_denom: MOCK_CW20_CONTRACT.to_string(),
+        uses_cw20: Some(true),
+        price: Uint128::new(100),
+        start_time: None,
+        duration: None,
+        recipient: None,
+    };
+    let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: MOCK_TOKEN_OWNER.to_owned(),
+        token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+        msg: encode_binary(&hook_msg).unwrap(),
+    });
+
+    let info = mock_info(MOCK_TOKEN_ADDR, &[]);
+    let _res = execute(deps, mock_env(), info, msg).unwrap();
+}
+
+#[test]
+fn test_execute_buy_cw20_works() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+
+    let _res = init(deps.as_mut(), None);
+
+    start_sale_cw20(deps.as_mut());
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "someone".to_string(),
+        amount: Uint128::new(100),
+        msg: encode_binary(&Cw20HookMsg::Buy {
+            token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+            token_address: MOCK_TOKEN_ADDR.to_string(),
+        })
+        .unwrap(),
+    });
+
+    let info = mock_info(MOCK_CW20_CONTRACT, &[]);
+    // Add one second so that the start_time expires
+    env.block.time = env.block.time.plus_seconds(1);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+
+    let expected: Vec<SubMsg<_>> = vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_TOKEN_ADDR.to_string(),
+            msg: encode_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: "someone".to_string(),
+                token_id: MOCK_UNCLAIMED_TOKEN.to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CW20_CONTRACT.to_string(),
+            msg: encode_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: MOCK_TOKEN_OWNER.to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })),
+        SubMsg::reply_on_error(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "economics_contract".to_string(),
+                msg: to_json_binary(&EconomicsExecuteMsg::PayFee {
+                    payee: Addr::unchecked(MOCK_CW20_CONTRACT),
+                    action: "Receive".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            ReplyId::PayFee.repr(),
+        ),
+    ];
+    assert_eq!(res.messages, expected)
+}
+
+#[test]
+fn test_execute_buy_cw20_invalid_funds() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    // Add one second so that the start_time expires
+    env.block.time = env.block.time.plus_seconds(1);
+
+    let _res = init(deps.as_mut(), None);
+
+    start_sale_cw20(deps.as_mut());
+
+    let error = ContractError::InvalidFunds {
+        msg: format!("No {MOCK_CW20_CONTRACT} assets are provided to sale"),
+    };
+
+    // Native funds sent to a CW20 sale
+    let msg = ExecuteMsg::Buy {
+        token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+        token_address: MOCK_TOKEN_ADDR.to_string(),
+    };
+    let info = mock_info("someone", &coins(100, MOCK_CW20_CONTRACT));
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert_eq!(error, res.unwrap_err());
+
+    // Tokens sent from a different CW20
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "someone".to_string(),
+        amount: Uint128::new(100),
+        msg: encode_binary(&Cw20HookMsg::Buy {
+            token_id: MOCK_UNCLAIMED_TOKEN.to_owned(),
+            token_address: MOCK_TOKEN_ADDR.to_string(),
+        })
+        .unwrap(),
+    });
+    let info = mock_info("other_cw20", &[]);
+    let res = execute(deps.as_mut(), env, info, msg);
+    assert_eq!(error, res.unwrap_err());
+}
+
 #[test]
 fn test_execute_buy_future_start() {
     let mut deps = mock_dependencies_custom(&[]);
@@ -411,6 +534,7 @@ fn test_execute_update_sale_unauthorized() {
         token_address: MOCK_TOKEN_ADDR.to_string(),
         price: Uint128::new(11),
         coin_denom: "juno".to_string(),
+        uses_cw20: None,
         recipient: None,
     };
 
@@ -434,6 +558,7 @@ fn test_execute_update_sale_invalid_price() {
         token_address: MOCK_TOKEN_ADDR.to_string(),
         price: Uint128::zero(),
         coin_denom: "juno".to_string(),
+        uses_cw20: None,
         recipient: None,
     };
 
@@ -449,6 +574,7 @@ fn test_execute_start_sale_invalid_price() {
 
     let hook_msg = Cw721HookMsg::StartSale {
         coin_denom: "uusd".to_string(),
+        uses_cw20: None,
         price: Uint128::zero(),
         start_time: None,
         duration: None,
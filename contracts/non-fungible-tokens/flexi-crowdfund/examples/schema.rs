@@ -1,6 +1,6 @@
 use cosmwasm_schema::write_api;
 
-use andromeda_non_fungible_tokens::crowdfund::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use andromeda_non_fungible_tokens::flexi_crowdfund::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 fn main() {
     write_api! {
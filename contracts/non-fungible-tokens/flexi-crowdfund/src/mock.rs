@@ -1,15 +1,10 @@
 #![cfg(all(not(target_arch = "wasm32"), feature = "testing"))]
 
 use crate::contract::{execute, instantiate, query, reply};
-use andromeda_non_fungible_tokens::{
-    crowdfund::{CrowdfundMintMsg, ExecuteMsg, InstantiateMsg, QueryMsg},
-    cw721::TokenExtension,
-};
-use andromeda_std::{
-    ado_base::modules::Module,
-    amp::{AndrAddr, Recipient},
-    common::Milliseconds,
+use andromeda_non_fungible_tokens::flexi_crowdfund::{
+    ContributionResponse, ExecuteMsg, InstantiateMsg, Pledge, QueryMsg, State,
 };
+use andromeda_std::{ado_base::modules::Module, amp::Recipient, common::Milliseconds};
 use andromeda_testing::{
     mock::MockApp,
     mock_ado,
@@ -18,24 +13,28 @@ use andromeda_testing::{
 use cosmwasm_std::{Addr, Coin, Empty, Uint128};
 use cw_multi_test::{Contract, ContractWrapper, Executor};
 
-pub struct MockCrowdfund(Addr);
-mock_ado!(MockCrowdfund, ExecuteMsg, QueryMsg);
+pub struct MockFlexiCrowdfund(Addr);
+mock_ado!(MockFlexiCrowdfund, ExecuteMsg, QueryMsg);
 
-impl MockCrowdfund {
+impl MockFlexiCrowdfund {
     #[allow(clippy::too_many_arguments)]
     pub fn instantiate(
         code_id: u64,
         sender: Addr,
         app: &mut MockApp,
-        token_address: AndrAddr,
-        can_mint_after_sale: bool,
+        denom: impl Into<String>,
+        recipient: Option<Recipient>,
+        min_contribution: Option<Uint128>,
+        end_time: Option<Milliseconds>,
         modules: Option<Vec<Module>>,
         kernel_address: impl Into<String>,
         owner: Option<String>,
-    ) -> MockCrowdfund {
-        let msg = mock_crowdfund_instantiate_msg(
-            token_address,
-            can_mint_after_sale,
+    ) -> MockFlexiCrowdfund {
+        let msg = mock_flexi_crowdfund_instantiate_msg(
+            denom,
+            recipient,
+            min_contribution,
+            end_time,
             modules,
             kernel_address,
             owner,
@@ -46,155 +45,132 @@ impl MockCrowdfund {
                 sender.clone(),
                 &msg,
                 &[],
-                "Andromeda Crowdfund Contract",
+                "Flexi Crowdfund Contract",
                 Some(sender.to_string()),
             )
             .unwrap();
-        MockCrowdfund(Addr::unchecked(addr))
+        MockFlexiCrowdfund(Addr::unchecked(addr))
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn execute_start_sale(
+    pub fn execute_contribute(
         &self,
         sender: Addr,
         app: &mut MockApp,
-        start_time: Option<Milliseconds>,
-        end_time: Milliseconds,
-        price: Coin,
-        min_tokens_sold: Uint128,
-        max_amount_per_wallet: Option<u32>,
-        recipient: Recipient,
+        funds: &[Coin],
     ) -> ExecuteResult {
-        let msg = mock_start_crowdfund_msg(
-            start_time,
-            end_time,
-            price,
-            min_tokens_sold,
-            max_amount_per_wallet,
-            recipient,
-        );
-        self.execute(app, &msg, sender, &[])
+        self.execute(app, &mock_contribute_msg(), sender, funds)
     }
 
-    pub fn execute_end_sale(
+    pub fn execute_withdraw(
         &self,
         sender: Addr,
         app: &mut MockApp,
-        limit: Option<u32>,
+        amount: Option<Uint128>,
+        recipient: Option<Recipient>,
     ) -> ExecuteResult {
-        let msg = mock_end_crowdfund_msg(limit);
-        self.execute(app, &msg, sender, &[])
+        self.execute(app, &mock_withdraw_msg(amount, recipient), sender, &[])
     }
 
-    pub fn execute_mint(
+    pub fn execute_pledge(
         &self,
         sender: Addr,
         app: &mut MockApp,
-        token_id: String,
-        extension: TokenExtension,
-        token_uri: Option<String>,
-        owner: Option<String>,
+        amount_per_period: Uint128,
+        period: Milliseconds,
+        funds: &[Coin],
     ) -> ExecuteResult {
-        let msg = ExecuteMsg::Mint(vec![mock_crowdfund_mint_msg(
-            token_id, extension, token_uri, owner,
-        )]);
-        self.execute(app, &msg, sender, &[])
+        self.execute(
+            app,
+            &mock_pledge_msg(amount_per_period, period),
+            sender,
+            funds,
+        )
     }
 
-    pub fn execute_quick_mint(
+    pub fn execute_collect_pledges(
         &self,
         sender: Addr,
         app: &mut MockApp,
-        amount: u32,
-        publisher: String,
+        limit: Option<u32>,
     ) -> ExecuteResult {
-        let msg = mock_crowdfund_quick_mint_msg(amount, publisher);
-        self.execute(app, &msg, sender, &[])
+        self.execute(app, &mock_collect_pledges_msg(limit), sender, &[])
+    }
+
+    pub fn execute_cancel_pledge(&self, sender: Addr, app: &mut MockApp) -> ExecuteResult {
+        self.execute(app, &mock_cancel_pledge_msg(), sender, &[])
+    }
+
+    pub fn query_state(&self, app: &mut MockApp) -> State {
+        self.query::<State>(app, QueryMsg::State {})
     }
 
-    pub fn execute_purchase(
+    pub fn query_contribution(
         &self,
-        sender: Addr,
         app: &mut MockApp,
-        number_of_tokens: Option<u32>,
-        funds: &[Coin],
-    ) -> ExecuteResult {
-        let msg = mock_purchase_msg(number_of_tokens);
-        self.execute(app, &msg, sender, funds)
+        address: impl Into<String>,
+    ) -> ContributionResponse {
+        self.query::<ContributionResponse>(
+            app,
+            QueryMsg::Contribution {
+                address: address.into(),
+            },
+        )
+    }
+
+    pub fn query_pledge(&self, app: &mut MockApp, address: impl Into<String>) -> Option<Pledge> {
+        self.query::<Option<Pledge>>(
+            app,
+            QueryMsg::Pledge {
+                address: address.into(),
+            },
+        )
     }
 }
 
-pub fn mock_andromeda_crowdfund() -> Box<dyn Contract<Empty>> {
+pub fn mock_andromeda_flexi_crowdfund() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new_with_empty(execute, instantiate, query).with_reply(reply);
     Box::new(contract)
 }
 
-pub fn mock_crowdfund_instantiate_msg(
-    token_address: AndrAddr,
-    can_mint_after_sale: bool,
+pub fn mock_flexi_crowdfund_instantiate_msg(
+    denom: impl Into<String>,
+    recipient: Option<Recipient>,
+    min_contribution: Option<Uint128>,
+    end_time: Option<Milliseconds>,
     modules: Option<Vec<Module>>,
     kernel_address: impl Into<String>,
     owner: Option<String>,
 ) -> InstantiateMsg {
     InstantiateMsg {
-        token_address,
-        can_mint_after_sale,
+        denom: denom.into(),
+        recipient,
+        min_contribution,
+        end_time,
         modules,
         kernel_address: kernel_address.into(),
         owner,
     }
 }
 
-pub fn mock_start_crowdfund_msg(
-    start_time: Option<Milliseconds>,
-    end_time: Milliseconds,
-    price: Coin,
-    min_tokens_sold: Uint128,
-    max_amount_per_wallet: Option<u32>,
-    recipient: Recipient,
-) -> ExecuteMsg {
-    ExecuteMsg::StartSale {
-        start_time,
-        end_time,
-        price,
-        min_tokens_sold,
-        max_amount_per_wallet,
-        recipient,
-    }
+pub fn mock_contribute_msg() -> ExecuteMsg {
+    ExecuteMsg::Contribute {}
 }
 
-pub fn mock_end_crowdfund_msg(limit: Option<u32>) -> ExecuteMsg {
-    ExecuteMsg::EndSale { limit }
+pub fn mock_withdraw_msg(amount: Option<Uint128>, recipient: Option<Recipient>) -> ExecuteMsg {
+    ExecuteMsg::Withdraw { amount, recipient }
 }
 
-pub fn mock_crowdfund_mint_msg(
-    token_id: String,
-    extension: TokenExtension,
-    token_uri: Option<String>,
-    owner: Option<String>,
-) -> CrowdfundMintMsg {
-    CrowdfundMintMsg {
-        token_id,
-        owner,
-        token_uri,
-        extension,
+pub fn mock_pledge_msg(amount_per_period: Uint128, period: Milliseconds) -> ExecuteMsg {
+    ExecuteMsg::Pledge {
+        amount_per_period,
+        period,
     }
 }
 
-pub fn mock_crowdfund_quick_mint_msg(amount: u32, publisher: String) -> ExecuteMsg {
-    let mut mint_msgs: Vec<CrowdfundMintMsg> = Vec::new();
-    for i in 0..amount {
-        let extension = TokenExtension {
-            publisher: publisher.clone(),
-        };
-
-        let msg = mock_crowdfund_mint_msg(i.to_string(), extension, None, None);
-        mint_msgs.push(msg);
-    }
-
-    ExecuteMsg::Mint(mint_msgs)
+pub fn mock_collect_pledges_msg(limit: Option<u32>) -> ExecuteMsg {
+    ExecuteMsg::CollectPledges { limit }
 }
 
-pub fn mock_purchase_msg(number_of_tokens: Option<u32>) -> ExecuteMsg {
-    ExecuteMsg::Purchase { number_of_tokens }
+pub fn mock_cancel_pledge_msg() -> ExecuteMsg {
+    ExecuteMsg::CancelPledge {}
 }
@@ -1,53 +1,96 @@
-use andromeda_non_fungible_tokens::crowdfund::{Config, State};
+use andromeda_non_fungible_tokens::flexi_crowdfund::{Config, ContributionResponse, Pledge, State};
 use andromeda_std::error::ContractError;
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Order, Storage, SubMsg, Uint128};
-use cw_storage_plus::{Bound, Item, Map};
+use cosmwasm_std::{Order, Storage, Uint128};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 /// The config.
 pub const CONFIG: Item<Config> = Item::new("config");
 
-/// The number of tokens available for sale.
-pub const NUMBER_OF_TOKENS_AVAILABLE: Item<Uint128> = Item::new("number_of_tokens_available");
-
-/// Sale started if and only if STATE.may_load is Some and !duration.is_expired()
+/// The running totals of the campaign.
 pub const STATE: Item<State> = Item::new("state");
 
-/// Relates buyer address to vector of purchases.
-pub const PURCHASES: Map<&str, Vec<Purchase>> = Map::new("buyers");
-
-/// Contains token ids that have not been purchased.
-pub const AVAILABLE_TOKENS: Map<&str, bool> = Map::new("available_tokens");
-
-/// Is set to true when at least one sale has been conducted. This is used to disallow minting if
-/// config.can_mint_after_sale is false.
-pub const SALE_CONDUCTED: Item<bool> = Item::new("sale_conducted");
-
-#[cw_serde]
-pub struct Purchase {
-    /// The token id being purchased.
-    pub token_id: String,
-    /// Amount of tax paid.
-    pub tax_amount: Uint128,
-    /// sub messages for sending funds for rates.
-    pub msgs: Vec<SubMsg>,
-    /// The purchaser of the token.
-    pub purchaser: String,
+/// Relates contributor address to the total amount they have contributed.
+pub const CONTRIBUTIONS: Map<&str, Uint128> = Map::new("contributions");
+
+pub struct PledgeIndices<'a> {
+    /// PK: backer
+    /// Secondary key: when the next period is due in milliseconds
+    pub next_collection: MultiIndex<'a, u64, Pledge, &'a str>,
+}
+
+impl<'a> IndexList<Pledge> for PledgeIndices<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Pledge>> + '_> {
+        let v: Vec<&dyn Index<Pledge>> = vec![&self.next_collection];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Relates backer address to their pledge.
+pub fn pledges<'a>() -> IndexedMap<'a, &'a str, Pledge, PledgeIndices<'a>> {
+    let indexes = PledgeIndices {
+        next_collection: MultiIndex::new(
+            |_pk: &[u8], p| p.next_collection.milliseconds(),
+            "pledges",
+            "pledges__next_collection",
+        ),
+    };
+    IndexedMap::new("pledges", indexes)
 }
 
 const MAX_LIMIT: u32 = 50;
 const DEFAULT_LIMIT: u32 = 20;
-pub(crate) fn get_available_tokens(
+pub(crate) fn get_contributions(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<ContributionResponse>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    CONTRIBUTIONS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (contributor, amount) = item?;
+            Ok(ContributionResponse {
+                contributor,
+                amount,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn get_pledges(
     storage: &dyn Storage,
     start_after: Option<String>,
     limit: Option<u32>,
-) -> Result<Vec<String>, ContractError> {
+) -> Result<Vec<Pledge>, ContractError> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.as_deref().map(Bound::exclusive);
-    let tokens: Result<Vec<String>, ContractError> = AVAILABLE_TOKENS
-        .keys(storage, start, None, Order::Ascending)
+    pledges()
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Returns up to `limit` pledges whose next period is due at or before `time`, the longest overdue
+/// first.
+pub(crate) fn get_due_pledges(
+    storage: &dyn Storage,
+    time: u64,
+    limit: Option<u32>,
+) -> Result<Vec<Pledge>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    pledges()
+        .idx
+        .next_collection
+        .range(
+            storage,
+            None,
+            Some(Bound::exclusive((time + 1, ""))),
+            Order::Ascending,
+        )
         .take(limit)
-        .map(|token| Ok(token?))
-        .collect();
-    tokens
+        .map(|item| Ok(item?.1))
+        .collect()
 }
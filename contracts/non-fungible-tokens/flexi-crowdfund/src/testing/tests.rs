@@ -1,102 +1,44 @@
 use crate::{
-    contract::{execute, instantiate, query, MAX_MINT_LIMIT},
-    state::{
-        Purchase, AVAILABLE_TOKENS, CONFIG, NUMBER_OF_TOKENS_AVAILABLE, PURCHASES, SALE_CONDUCTED,
-        STATE,
-    },
+    contract::{execute, instantiate, query},
+    state::{pledges, CONFIG, CONTRIBUTIONS, STATE},
     testing::mock_querier::{
-        mock_dependencies_custom, MOCK_ADDRESS_LIST_CONTRACT, MOCK_APP_CONTRACT,
-        MOCK_CONDITIONS_MET_CONTRACT, MOCK_CONDITIONS_NOT_MET_CONTRACT, MOCK_RATES_CONTRACT,
-        MOCK_ROYALTY_RECIPIENT, MOCK_TAX_RECIPIENT, MOCK_TOKENS_FOR_SALE, MOCK_TOKEN_CONTRACT,
+        mock_dependencies_custom, MOCK_ADDRESS_LIST_CONTRACT, MOCK_KERNEL_CONTRACT,
+        UNWHITELISTED_ADDRESS,
     },
 };
-use andromeda_non_fungible_tokens::{
-    crowdfund::{Config, CrowdfundMintMsg, ExecuteMsg, InstantiateMsg, QueryMsg, State},
-    cw721::{ExecuteMsg as Cw721ExecuteMsg, TokenExtension},
+use andromeda_non_fungible_tokens::flexi_crowdfund::{
+    Config, ContributionResponse, ExecuteMsg, InstantiateMsg, Pledge, QueryMsg, State,
 };
 use andromeda_std::{
     ado_base::modules::Module,
     amp::{addresses::AndrAddr, recipient::Recipient},
-    common::{
-        encode_binary,
-        expiration::{expiration_from_milliseconds, MILLISECONDS_TO_NANOSECONDS_RATIO},
-        Milliseconds,
-    },
+    common::{expiration::expiration_from_milliseconds, Milliseconds},
     error::ContractError,
 };
 use andromeda_testing::economics_msg::generate_economics_message;
 use cosmwasm_std::{
     coin, coins, from_json,
     testing::{mock_env, mock_info},
-    Addr, BankMsg, Coin, CosmosMsg, DepsMut, Response, StdError, SubMsg, Uint128, WasmMsg,
+    BankMsg, CosmosMsg, DepsMut, Response, StdError, Uint128,
 };
-use cw_utils::Expiration;
-
-use super::mock_querier::MOCK_KERNEL_CONTRACT;
-
-const ADDRESS_LIST: &str = "addresslist";
-const RATES: &str = "rates";
-
-fn get_purchase(token_id: impl Into<String>, purchaser: impl Into<String>) -> Purchase {
-    Purchase {
-        token_id: token_id.into(),
-        purchaser: purchaser.into(),
-        tax_amount: Uint128::from(50u128),
-        msgs: get_rates_messages(),
-    }
-}
-
-fn get_rates_messages() -> Vec<SubMsg> {
-    let coin = coin(100u128, "uusd");
-    vec![
-        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
-            to_address: MOCK_ROYALTY_RECIPIENT.to_owned(),
-            amount: vec![Coin {
-                // Royalty of 10%
-                amount: coin.amount.multiply_ratio(10u128, 100u128),
-                denom: coin.denom.clone(),
-            }],
-        })),
-        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
-            to_address: MOCK_TAX_RECIPIENT.to_owned(),
-            amount: vec![Coin {
-                // Flat tax of 50
-                amount: Uint128::from(50u128),
-                denom: coin.denom,
-            }],
-        })),
-    ]
-}
+use cw_utils::{Expiration, PaymentError};
 
-fn get_burn_message(token_id: impl Into<String>) -> CosmosMsg {
-    CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
-        funds: vec![],
-        msg: encode_binary(&Cw721ExecuteMsg::Burn {
-            token_id: token_id.into(),
-        })
-        .unwrap(),
-    })
-}
-
-fn get_transfer_message(token_id: impl Into<String>, recipient: AndrAddr) -> CosmosMsg {
-    CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
-        msg: encode_binary(&Cw721ExecuteMsg::TransferNft {
-            recipient,
-            token_id: token_id.into(),
-        })
-        .unwrap(),
-        funds: vec![],
-    })
-}
+const DENOM: &str = "uusd";
 
-fn init(deps: DepsMut, modules: Option<Vec<Module>>) -> Response {
+fn init(
+    deps: DepsMut,
+    recipient: Option<Recipient>,
+    min_contribution: Option<Uint128>,
+    end_time: Option<Milliseconds>,
+    modules: Option<Vec<Module>>,
+) -> Response {
     let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
+        denom: DENOM.to_string(),
+        recipient,
+        min_contribution,
+        end_time,
         owner: None,
         modules,
-        can_mint_after_sale: true,
         kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
     };
 
@@ -104,1910 +46,604 @@ fn init(deps: DepsMut, modules: Option<Vec<Module>>) -> Response {
     instantiate(deps, mock_env(), info, msg).unwrap()
 }
 
+fn contribute(deps: DepsMut, sender: &str, amount: u128) -> Result<Response, ContractError> {
+    let info = mock_info(sender, &coins(amount, DENOM));
+    execute(deps, mock_env(), info, ExecuteMsg::Contribute {})
+}
+
+fn pledge(
+    deps: DepsMut,
+    sender: &str,
+    amount_per_period: u128,
+    period: Milliseconds,
+    deposit: u128,
+) -> Result<Response, ContractError> {
+    let info = mock_info(sender, &coins(deposit, DENOM));
+    let msg = ExecuteMsg::Pledge {
+        amount_per_period: Uint128::new(amount_per_period),
+        period,
+    };
+    execute(deps, mock_env(), info, msg)
+}
+
+fn now() -> Milliseconds {
+    Milliseconds::from_nanos(mock_env().block.time.nanos())
+}
+
 #[test]
 fn test_instantiate() {
     let mut deps = mock_dependencies_custom(&[]);
+    let end_time = Milliseconds::from_seconds(mock_env().block.time.seconds() + 100);
 
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-
-    let res = init(deps.as_mut(), Some(modules));
+    let res = init(
+        deps.as_mut(),
+        Some(Recipient::from_string("recipient")),
+        Some(Uint128::new(10)),
+        Some(end_time),
+        None,
+    );
 
     assert_eq!(
         Response::new()
             .add_attribute("method", "instantiate")
-            .add_attribute("type", "crowdfund")
+            .add_attribute("type", "flexi-crowdfund")
             .add_attribute("kernel_address", MOCK_KERNEL_CONTRACT)
-            .add_attribute("owner", "owner")
-            .add_attribute("action", "register_module")
-            .add_attribute("module_idx", "1"),
+            .add_attribute("owner", "owner"),
         res
     );
 
     assert_eq!(
         Config {
-            token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-            can_mint_after_sale: true
+            denom: DENOM.to_string(),
+            recipient: Some(Recipient::from_string("recipient")),
+            min_contribution: Uint128::new(10),
+            end_time: Some(expiration_from_milliseconds(end_time).unwrap()),
         },
-        CONFIG.load(deps.as_mut().storage).unwrap()
+        CONFIG.load(deps.as_ref().storage).unwrap()
     );
-
-    assert!(!SALE_CONDUCTED.load(deps.as_mut().storage).unwrap());
+    assert_eq!(State::default(), STATE.load(deps.as_ref().storage).unwrap());
 }
 
 #[test]
-fn test_mint_unauthorized() {
+fn test_instantiate_end_time_in_past() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    let msg = ExecuteMsg::Mint(vec![CrowdfundMintMsg {
-        token_id: "token_id".to_string(),
+    let msg = InstantiateMsg {
+        denom: DENOM.to_string(),
+        recipient: None,
+        min_contribution: None,
+        end_time: Some(Milliseconds::from_seconds(
+            mock_env().block.time.seconds() - 1,
+        )),
         owner: None,
-        token_uri: None,
-        extension: TokenExtension {
-            publisher: "publisher".to_string(),
-        },
-    }]);
-    let info = mock_info("not_owner", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-
-    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
-}
-
-#[test]
-fn test_mint_owner_not_crowdfund() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    let msg = ExecuteMsg::Mint(vec![CrowdfundMintMsg {
-        token_id: "token_id".to_string(),
-        owner: Some("not_crowdfund".to_string()),
-        token_uri: None,
-        extension: TokenExtension {
-            publisher: "publisher".to_string(),
-        },
-    }]);
-    let info = mock_info("owner", &[]);
-    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        modules: None,
+        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+    };
 
-    // Since token was minted to owner that is not the contract, it is not available for sale.
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, "token_id"));
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(ContractError::ExpirationInPast {}, res.unwrap_err());
 }
 
 #[test]
-fn test_mint_sale_started() {
+fn test_contribute() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let msg = ExecuteMsg::StartSale {
-        start_time: None,
-        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: Some(5),
-        recipient: Recipient::from_string("recipient"),
-    };
+    init(deps.as_mut(), None, None, None, None);
 
-    let info = mock_info("owner", &[]);
-    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let res = contribute(deps.as_mut(), "contributor", 100).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "contribute")
+            .add_attribute("contributor", "contributor")
+            .add_attribute("amount", "100")
+            .add_attribute("total_raised", "100")
+            .add_submessage(generate_economics_message("contributor", "Contribute")),
+        res
+    );
 
-    let res = mint(deps.as_mut(), "token_id");
+    // Contributing again adds to the existing contribution.
+    contribute(deps.as_mut(), "contributor", 50).unwrap();
+    contribute(deps.as_mut(), "other_contributor", 25).unwrap();
 
-    assert_eq!(ContractError::SaleStarted {}, res.unwrap_err());
+    assert_eq!(
+        Uint128::new(150),
+        CONTRIBUTIONS
+            .load(deps.as_ref().storage, "contributor")
+            .unwrap()
+    );
+    assert_eq!(
+        State {
+            total_raised: Uint128::new(175),
+            total_withdrawn: Uint128::zero(),
+            number_of_contributors: 2,
+            total_collected: Uint128::zero(),
+        },
+        STATE.load(deps.as_ref().storage).unwrap()
+    );
 }
 
 #[test]
-fn test_mint_sale_conducted_cant_mint_after_sale() {
+fn test_contribute_invalid_funds() {
     let mut deps = mock_dependencies_custom(&[]);
-    let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-        modules: None,
-        owner: None,
-        can_mint_after_sale: false,
-        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    init(deps.as_mut(), None, Some(Uint128::new(10)), None, None);
 
-    SALE_CONDUCTED.save(deps.as_mut().storage, &true).unwrap();
+    let info = mock_info("contributor", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Contribute {});
+    assert_eq!(
+        ContractError::Payment(PaymentError::NoFunds {}),
+        res.unwrap_err()
+    );
 
-    let res = mint(deps.as_mut(), "token_id");
+    let info = mock_info("contributor", &coins(100, "uluna"));
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Contribute {});
+    assert_eq!(
+        ContractError::Payment(PaymentError::MissingDenom(DENOM.to_string())),
+        res.unwrap_err()
+    );
 
+    let info = mock_info("contributor", &[coin(100, DENOM), coin(100, "uluna")]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Contribute {});
     assert_eq!(
-        ContractError::CannotMintAfterSaleConducted {},
+        ContractError::Payment(PaymentError::MultipleDenoms {}),
         res.unwrap_err()
     );
+
+    // Below the minimum contribution.
+    let res = contribute(deps.as_mut(), "contributor", 9);
+    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
 }
 
 #[test]
-fn test_mint_sale_conducted_can_mint_after_sale() {
+fn test_contribute_after_end_time() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
+    let end_time = Milliseconds::from_seconds(mock_env().block.time.seconds() + 100);
+    init(deps.as_mut(), None, None, Some(end_time), None);
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let info = mock_info("contributor", &coins(100, DENOM));
+    let res = execute(deps.as_mut(), env, info, ExecuteMsg::Contribute {});
+    assert_eq!(ContractError::SaleEnded {}, res.unwrap_err());
+}
 
-    SALE_CONDUCTED.save(deps.as_mut().storage, &true).unwrap();
+#[test]
+fn test_contribute_address_list() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let modules = vec![Module {
+        name: Some("address-list".to_owned()),
+        address: AndrAddr::from_string(MOCK_ADDRESS_LIST_CONTRACT.to_owned()),
+        is_mutable: false,
+    }];
+    init(deps.as_mut(), None, None, None, Some(modules));
 
-    let _res = mint(deps.as_mut(), "token_id").unwrap();
+    let res = contribute(deps.as_mut(), UNWHITELISTED_ADDRESS, 100);
+    assert_eq!(
+        ContractError::Std(StdError::generic_err(
+            "Querier contract error: Unwhitelisted Address"
+        )),
+        res.unwrap_err()
+    );
 
-    assert!(AVAILABLE_TOKENS.has(deps.as_ref().storage, "token_id"));
+    assert!(contribute(deps.as_mut(), "contributor", 100).is_ok());
 }
 
 #[test]
-fn test_mint_successful() {
+fn test_withdraw_unauthorized() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    let res = mint(deps.as_mut(), "token_id").unwrap();
+    init(deps.as_mut(), None, None, None, None);
+    contribute(deps.as_mut(), "contributor", 100).unwrap();
 
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: "token_id".to_string(),
-        owner: mock_env().contract.address.to_string(),
-        token_uri: None,
-        extension: TokenExtension {
-            publisher: "publisher".to_string(),
-        },
+    let msg = ExecuteMsg::Withdraw {
+        amount: None,
+        recipient: None,
     };
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "mint")
-            .add_message(WasmMsg::Execute {
-                contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
-                msg: encode_binary(&mint_msg).unwrap(),
-                funds: vec![],
-            })
-            .add_submessage(generate_economics_message("owner", "Mint")),
-        res
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("contributor", &[]),
+        msg,
     );
-
-    assert!(AVAILABLE_TOKENS.has(deps.as_ref().storage, "token_id"));
+    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
 }
 
 #[test]
-fn test_mint_multiple_successful() {
+fn test_withdraw() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    let mint_msgs = vec![
-        CrowdfundMintMsg {
-            token_id: "token_id1".to_string(),
-            owner: None,
-            token_uri: None,
-            extension: TokenExtension {
-                publisher: "publisher".to_string(),
-            },
-        },
-        CrowdfundMintMsg {
-            token_id: "token_id2".to_string(),
-            owner: None,
-            token_uri: None,
-            extension: TokenExtension {
-                publisher: "publisher".to_string(),
-            },
-        },
-    ];
+    init(
+        deps.as_mut(),
+        Some(Recipient::from_string("recipient")),
+        None,
+        None,
+        None,
+    );
 
-    let msg = ExecuteMsg::Mint(mint_msgs);
-    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    // Nothing to withdraw yet.
+    let msg = ExecuteMsg::Withdraw {
+        amount: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(ContractError::WithdrawalIsEmpty {}, res.unwrap_err());
 
+    contribute(deps.as_mut(), "contributor", 100).unwrap();
+
+    // Partial withdrawal to the configured recipient.
+    let msg = ExecuteMsg::Withdraw {
+        amount: Some(Uint128::new(40)),
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
     assert_eq!(
         Response::new()
-            .add_attribute("action", "mint")
-            .add_attribute("action", "mint")
-            .add_message(WasmMsg::Execute {
-                contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
-                msg: encode_binary(&Cw721ExecuteMsg::Mint {
-                    token_id: "token_id1".to_string(),
-                    owner: mock_env().contract.address.to_string(),
-                    token_uri: None,
-                    extension: TokenExtension {
-                        publisher: "publisher".to_string(),
-                    },
-                })
-                .unwrap(),
-                funds: vec![],
-            })
-            .add_message(WasmMsg::Execute {
-                contract_addr: MOCK_TOKEN_CONTRACT.to_owned(),
-                msg: encode_binary(&Cw721ExecuteMsg::Mint {
-                    token_id: "token_id2".to_string(),
-                    owner: mock_env().contract.address.to_string(),
-                    token_uri: None,
-                    extension: TokenExtension {
-                        publisher: "publisher".to_string(),
-                    },
-                })
-                .unwrap(),
-                funds: vec![],
+            .add_message(BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: coins(40, DENOM),
             })
-            .add_submessage(generate_economics_message("owner", "Mint")),
+            .add_attribute("action", "withdraw")
+            .add_attribute("recipient", "recipient")
+            .add_attribute("amount", "40")
+            .add_submessage(generate_economics_message("owner", "Withdraw")),
         res
     );
 
-    assert!(AVAILABLE_TOKENS.has(deps.as_ref().storage, "token_id1"));
-    assert!(AVAILABLE_TOKENS.has(deps.as_ref().storage, "token_id2"));
+    // Cannot withdraw more than what is left.
+    let msg = ExecuteMsg::Withdraw {
+        amount: Some(Uint128::new(61)),
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
 
+    // Withdraw the remainder to an overridden recipient.
+    let msg = ExecuteMsg::Withdraw {
+        amount: None,
+        recipient: Some(Recipient::from_string("other_recipient")),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
     assert_eq!(
-        NUMBER_OF_TOKENS_AVAILABLE
-            .load(deps.as_ref().storage)
-            .unwrap(),
-        Uint128::new(2)
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "other_recipient".to_string(),
+            amount: coins(60, DENOM),
+        }),
+        res.messages[0].msg
     );
+
+    let state = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(Uint128::new(100), state.total_withdrawn);
+    assert!(state.available().is_zero());
+
+    // Contributions keep flowing in after a withdrawal.
+    contribute(deps.as_mut(), "contributor", 10).unwrap();
+    let state = STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(Uint128::new(10), state.available());
 }
 
 #[test]
-fn test_mint_multiple_exceeds_limit() {
+fn test_withdraw_defaults_to_owner() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
+    init(deps.as_mut(), None, None, None, None);
+    contribute(deps.as_mut(), "contributor", 100).unwrap();
 
-    let mint_msg = CrowdfundMintMsg {
-        token_id: "token_id1".to_string(),
-        owner: None,
-        token_uri: None,
-        extension: TokenExtension {
-            publisher: "publisher".to_string(),
-        },
+    let msg = ExecuteMsg::Withdraw {
+        amount: None,
+        recipient: None,
     };
-
-    let mut mint_msgs: Vec<CrowdfundMintMsg> = vec![];
-
-    for _ in 0..MAX_MINT_LIMIT + 1 {
-        mint_msgs.push(mint_msg.clone());
-    }
-
-    let msg = ExecuteMsg::Mint(mint_msgs.clone());
-    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
-
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
     assert_eq!(
-        ContractError::TooManyMintMessages {
-            limit: MAX_MINT_LIMIT
-        },
-        res.unwrap_err()
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "owner".to_string(),
+            amount: coins(100, DENOM),
+        }),
+        res.messages[0].msg
     );
 }
 
 #[test]
-fn test_start_sale_end_time_zero() {
+fn test_query_contributions() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-    let one_minute_in_future =
-        mock_env().block.time.plus_minutes(1).nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let msg = ExecuteMsg::StartSale {
-        start_time: Some(Milliseconds(one_minute_in_future)),
-        end_time: Milliseconds::zero(),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: None,
-        recipient: Recipient::from_string("recipient".to_string()),
-    };
-
-    let info = mock_info("owner", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::StartTimeAfterEndTime {}, res.unwrap_err());
-}
+    init(deps.as_mut(), None, None, None, None);
+    contribute(deps.as_mut(), "a", 10).unwrap();
+    contribute(deps.as_mut(), "b", 20).unwrap();
+    contribute(deps.as_mut(), "c", 30).unwrap();
 
-#[test]
-fn test_start_sale_unauthorized() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let msg = ExecuteMsg::StartSale {
-        start_time: None,
-        end_time: Milliseconds::from_nanos((current_time + 1) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: None,
-        recipient: Recipient::from_string("recipient"),
-    };
+    let res: ContributionResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Contribution {
+                address: "b".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Uint128::new(20), res.amount);
+
+    let res: Vec<ContributionResponse> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Contributions {
+                start_after: Some("a".to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![ContributionResponse {
+            contributor: "b".to_string(),
+            amount: Uint128::new(20),
+        }],
+        res
+    );
 
-    let info = mock_info("anyone", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::Unauthorized {}, res.unwrap_err());
+    let res: State =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(Uint128::new(60), res.total_raised);
+    assert_eq!(3, res.number_of_contributors);
 }
 
 #[test]
-fn test_start_sale_start_time_in_past() {
+fn test_pledge_invalid() {
     let mut deps = mock_dependencies_custom(&[]);
-    let env = mock_env();
-    init(deps.as_mut(), None);
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let one_minute_in_past = env.block.time.minus_minutes(1).seconds();
-    let msg = ExecuteMsg::StartSale {
-        start_time: Some(Milliseconds(one_minute_in_past)),
-        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: None,
-        recipient: Recipient::from_string("recipient"),
-    };
+    init(deps.as_mut(), None, Some(Uint128::new(10)), None, None);
 
-    let info = mock_info("owner", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    let res = pledge(deps.as_mut(), "backer", 10, Milliseconds::zero(), 10);
     assert_eq!(
-        ContractError::StartTimeInThePast {
-            current_time: env.block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO,
-            current_block: env.block.height,
+        ContractError::InvalidPledge {
+            msg: "Period must be greater than zero".to_string()
         },
         res.unwrap_err()
     );
-}
 
-#[test]
-fn test_start_sale_start_time_in_future() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let env = mock_env();
-    init(deps.as_mut(), None);
-
-    let one_minute_in_future =
-        env.block.time.plus_minutes(1).nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-    let msg = ExecuteMsg::StartSale {
-        start_time: Some(Milliseconds(one_minute_in_future)),
-        end_time: Milliseconds::from_nanos((one_minute_in_future + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: None,
-        recipient: Recipient::from_string("recipient"),
-    };
+    let res = pledge(deps.as_mut(), "backer", 9, Milliseconds(100), 10);
+    assert_eq!(
+        ContractError::InvalidPledge {
+            msg: "Amount per period must be at least the minimum contribution".to_string()
+        },
+        res.unwrap_err()
+    );
 
-    let info = mock_info("owner", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert!(res.is_ok())
+    // The deposit has to cover at least one period.
+    let res = pledge(deps.as_mut(), "backer", 10, Milliseconds(100), 9);
+    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
 }
 
 #[test]
-fn test_start_sale_max_default() {
+fn test_pledge() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let msg = ExecuteMsg::StartSale {
-        start_time: None,
-        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: None,
-        recipient: Recipient::from_string("recipient"),
-    };
-
-    let info = mock_info("owner", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
-    // Using current time since start time wasn't provided
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time + 1)).unwrap();
-    let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
+    init(deps.as_mut(), None, None, None, None);
 
+    let res = pledge(deps.as_mut(), "backer", 10, Milliseconds(100), 25).unwrap();
     assert_eq!(
         Response::new()
-            .add_attribute("action", "start_sale")
-            .add_attribute("start_time", start_expiration.to_string())
-            .add_attribute("end_time", end_expiration.to_string())
-            .add_attribute("price", "100uusd")
-            .add_attribute("min_tokens_sold", "1")
-            .add_attribute("max_amount_per_wallet", "1")
-            .add_submessage(generate_economics_message("owner", "StartSale")),
+            .add_attribute("action", "pledge")
+            .add_attribute("backer", "backer")
+            .add_attribute("amount_per_period", "10")
+            .add_attribute("period", "100")
+            .add_attribute("balance", "25")
+            .add_submessage(generate_economics_message("backer", "Pledge")),
         res
     );
 
+    // Pledging again collects the due period on the previous terms, then tops up the deposit and
+    // replaces the terms.
+    let res = pledge(deps.as_mut(), "backer", 20, Milliseconds(200), 15).unwrap();
     assert_eq!(
-        State {
-            end_time: end_expiration,
-            price: coin(100, "uusd"),
-            min_tokens_sold: Uint128::from(1u128),
-            max_amount_per_wallet: 1,
-            amount_sold: Uint128::zero(),
-            amount_to_send: Uint128::zero(),
-            amount_transferred: Uint128::zero(),
-            recipient: Recipient::from_string("recipient"),
-        },
-        STATE.load(deps.as_ref().storage).unwrap()
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "owner".to_string(),
+            amount: coins(10, DENOM),
+        }),
+        res.messages[0].msg
+    );
+    let res: Option<Pledge> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pledge {
+                address: "backer".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Pledge {
+            backer: "backer".to_string(),
+            amount_per_period: Uint128::new(20),
+            period: Milliseconds(200),
+            balance: Uint128::new(30),
+            next_collection: now().plus_milliseconds(Milliseconds(100)),
+            total_collected: Uint128::new(10),
+        }),
+        res
     );
-
-    assert!(SALE_CONDUCTED.load(deps.as_ref().storage).unwrap());
-
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::SaleStarted {}, res.unwrap_err());
 }
 
 #[test]
-fn test_start_sale_max_modified() {
+fn test_collect_pledges() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let msg = ExecuteMsg::StartSale {
-        start_time: None,
-        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: Some(5),
-        recipient: Recipient::from_string("recipient"),
-    };
-    // Using current time since start time wasn't provided
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-    let start_expiration = expiration_from_milliseconds(Milliseconds(current_time + 1)).unwrap();
-    let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
+    init(
+        deps.as_mut(),
+        Some(Recipient::from_string("recipient")),
+        None,
+        None,
+        None,
+    );
+    pledge(deps.as_mut(), "a", 10, Milliseconds(100), 25).unwrap();
+    pledge(deps.as_mut(), "b", 20, Milliseconds(1000), 100).unwrap();
 
-    let info = mock_info("owner", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    // Collects in batches.
+    let msg = ExecuteMsg::CollectPledges { limit: Some(1) };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
     assert_eq!(
         Response::new()
-            .add_attribute("action", "start_sale")
-            .add_attribute("start_time", start_expiration.to_string())
-            .add_attribute("end_time", end_expiration.to_string())
-            .add_attribute("price", "100uusd")
-            .add_attribute("min_tokens_sold", "1")
-            .add_attribute("max_amount_per_wallet", "5")
-            .add_submessage(generate_economics_message("owner", "StartSale")),
+            .add_message(BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: coins(10, DENOM),
+            })
+            .add_attribute("action", "collect_pledges")
+            .add_attribute("pledges_collected", "1")
+            .add_attribute("amount", "10")
+            .add_submessage(generate_economics_message("anyone", "CollectPledges")),
         res
     );
 
+    let msg = ExecuteMsg::CollectPledges { limit: None };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    )
+    .unwrap();
     assert_eq!(
-        State {
-            end_time: end_expiration,
-            price: coin(100, "uusd"),
-            min_tokens_sold: Uint128::from(1u128),
-            max_amount_per_wallet: 5,
-            amount_sold: Uint128::zero(),
-            amount_to_send: Uint128::zero(),
-            amount_transferred: Uint128::zero(),
-            recipient: Recipient::from_string("recipient"),
-        },
-        STATE.load(deps.as_ref().storage).unwrap()
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(20, DENOM),
+        }),
+        res.messages[0].msg
     );
-}
-
-#[test]
-fn test_purchase_sale_not_started() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-
-    let info = mock_info("sender", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
-    assert_eq!(ContractError::NoOngoingSale {}, res.unwrap_err());
-
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-    };
-
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::NoOngoingSale {}, res.unwrap_err());
-}
-
-#[test]
-fn test_purchase_sale_not_ended() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height - 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
 
-    let info = mock_info("sender", &[]);
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
-    assert_eq!(ContractError::NoOngoingSale {}, res.unwrap_err());
+    // Nothing is due until the next period.
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![generate_economics_message("anyone", "CollectPledges")],
+        res.messages
+    );
 
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-    };
+    // Two periods of "a" are due but only 15 is left, after which the pledge is removed.
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_nanos(250_000_000);
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(15, DENOM),
+        }),
+        res.messages[0].msg
+    );
+    assert!(!pledges().has(deps.as_ref().storage, "a"));
 
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::NoOngoingSale {}, res.unwrap_err());
+    let b = pledges().load(deps.as_ref().storage, "b").unwrap();
+    assert_eq!(Uint128::new(80), b.balance);
+    assert_eq!(Uint128::new(20), b.total_collected);
+    assert_eq!(
+        now().plus_milliseconds(Milliseconds(1000)),
+        b.next_collection
+    );
+    assert_eq!(
+        Uint128::new(45),
+        STATE.load(deps.as_ref().storage).unwrap().total_collected
+    );
 }
 
 #[test]
-fn test_purchase_no_funds() {
+fn test_collect_pledges_after_end_time() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-
-    let info = mock_info("sender", &[]);
+    let end_time = Milliseconds::from_seconds(mock_env().block.time.seconds() + 100);
+    init(deps.as_mut(), None, None, Some(end_time), None);
+    pledge(
+        deps.as_mut(),
+        "backer",
+        10,
+        Milliseconds::from_seconds(60),
+        100,
+    )
+    .unwrap();
 
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+    // Only the periods starting before the end of the campaign are collected.
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(200);
+    let msg = ExecuteMsg::CollectPledges { limit: None };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "owner".to_string(),
+            amount: coins(20, DENOM),
+        }),
+        res.messages[0].msg
+    );
 
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
+    let msg = ExecuteMsg::Pledge {
+        amount_per_period: Uint128::new(10),
+        period: Milliseconds::from_seconds(60),
     };
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+    let info = mock_info("backer", &coins(100, DENOM));
+    let res = execute(deps.as_mut(), env, info, msg);
+    assert_eq!(ContractError::SaleEnded {}, res.unwrap_err());
 }
 
 #[test]
-fn test_purchase_wrong_denom() {
+fn test_collect_pledges_after_end_height() {
     let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
+    init(deps.as_mut(), None, None, None, None);
+    pledge(deps.as_mut(), "backer", 10, Milliseconds(100), 100).unwrap();
+    let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+    config.end_time = Some(Expiration::AtHeight(mock_env().block.height + 1));
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
 
-    let info = mock_info("sender", &coins(100, "uluna"));
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
-
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-    };
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+    // Nothing is collected once the end height is reached.
+    let mut env = mock_env();
+    env.block.height += 1;
+    env.block.time = env.block.time.plus_seconds(1);
+    let msg = ExecuteMsg::CollectPledges { limit: None };
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+    assert_eq!(
+        Response::new()
+            .add_attribute("action", "collect_pledges")
+            .add_attribute("pledges_collected", "0")
+            .add_attribute("amount", "0")
+            .add_submessage(generate_economics_message("anyone", "CollectPledges")),
+        res
+    );
 }
 
 #[test]
-fn test_purchase_not_enough_for_price() {
+fn test_cancel_pledge() {
     let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
+    init(deps.as_mut(), None, None, None, None);
 
-    let info = mock_info("sender", &coins(50u128, "uusd"));
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
+    let msg = ExecuteMsg::CancelPledge {};
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("backer", &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        ContractError::InvalidPledge {
+            msg: "Sender has no pledge".to_string()
+        },
+        res.unwrap_err()
+    );
 
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-    };
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
-}
-
-#[test]
-fn test_purchase_not_enough_for_tax() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-
-    NUMBER_OF_TOKENS_AVAILABLE
-        .save(deps.as_mut().storage, &Uint128::new(1))
-        .unwrap();
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-
-    let info = mock_info("sender", &coins(100u128, "uusd"));
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
-
-    // Reset the state since state does not roll back on failure in tests like it does in prod.
-    AVAILABLE_TOKENS
-        .save(deps.as_mut().storage, MOCK_TOKENS_FOR_SALE[0], &true)
-        .unwrap();
-    NUMBER_OF_TOKENS_AVAILABLE
-        .save(deps.as_mut().storage, &Uint128::new(1))
-        .unwrap();
-
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-    };
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::InsufficientFunds {}, res.unwrap_err());
-}
-
-#[test]
-fn test_purchase_by_token_id_not_available() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::zero(),
-                amount_to_send: Uint128::zero(),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-
-    let info = mock_info("sender", &coins(150, "uusd"));
-
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[1].to_owned(),
-    };
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::TokenNotAvailable {}, res.unwrap_err());
-}
-
-#[test]
-fn test_purchase_by_token_id() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[1]).unwrap();
-
-    let mut state = State {
-        end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: 1,
-        amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
-        amount_transferred: Uint128::zero(),
-        recipient: Recipient::from_string("recipient"),
-    };
-
-    STATE.save(deps.as_mut().storage, &state).unwrap();
-
-    let info = mock_info("sender", &coins(150, "uusd"));
-
-    // Purchase a token.
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-    };
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "purchase")
-            .add_attribute("token_id", MOCK_TOKENS_FOR_SALE[0])
-            .add_submessage(generate_economics_message("sender", "PurchaseByTokenId")),
-        res
-    );
-
-    state.amount_to_send += Uint128::from(90u128);
-    state.amount_sold += Uint128::from(1u128);
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[0]));
-    assert_eq!(
-        NUMBER_OF_TOKENS_AVAILABLE
-            .load(deps.as_ref().storage)
-            .unwrap(),
-        Uint128::new(1)
-    );
-
-    // Purchase a second one.
-    let msg = ExecuteMsg::PurchaseByTokenId {
-        token_id: MOCK_TOKENS_FOR_SALE[1].to_owned(),
-    };
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-
-    assert_eq!(ContractError::PurchaseLimitReached {}, res.unwrap_err());
-}
-
-#[test]
-fn test_multiple_purchases() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-
-    // Mint four tokens.
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[1]).unwrap();
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[2]).unwrap();
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[3]).unwrap();
-
-    // Query available tokens.
-    let msg = QueryMsg::AvailableTokens {
-        start_after: None,
-        limit: None,
-    };
-    let res: Vec<String> = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
-    assert_eq!(
-        vec![
-            MOCK_TOKENS_FOR_SALE[0],
-            MOCK_TOKENS_FOR_SALE[1],
-            MOCK_TOKENS_FOR_SALE[2],
-            MOCK_TOKENS_FOR_SALE[3]
-        ],
-        res
-    );
-
-    // Query if individual token is available
-    let msg = QueryMsg::IsTokenAvailable {
-        id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-    };
-    let res: bool = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
-    assert!(res);
-
-    // Query if another token is available
-    let msg = QueryMsg::IsTokenAvailable {
-        id: MOCK_TOKENS_FOR_SALE[4].to_owned(),
-    };
-    let res: bool = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
-    assert!(!res);
-
-    // Purchase 2 tokens
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(2),
-    };
-
-    let mut state = State {
-        end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: 3,
-        amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
-        amount_transferred: Uint128::zero(),
-        recipient: Recipient::from_string("recipient"),
-    };
-    STATE.save(deps.as_mut().storage, &state).unwrap();
-
-    let info = mock_info("sender", &coins(300u128, "uusd"));
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "purchase")
-            .add_attribute("number_of_tokens_wanted", "2")
-            .add_attribute("number_of_tokens_purchased", "2")
-            .add_submessage(generate_economics_message("sender", "Purchase")),
-        res
-    );
-
-    state.amount_to_send += Uint128::from(180u128);
-    state.amount_sold += Uint128::from(2u128);
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[0]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[1]));
-
-    assert_eq!(
-        vec![
-            get_purchase(MOCK_TOKENS_FOR_SALE[0], "sender"),
-            get_purchase(MOCK_TOKENS_FOR_SALE[1], "sender")
-        ],
-        PURCHASES.load(deps.as_ref().storage, "sender").unwrap()
-    );
-
-    // Purchase max number of tokens.
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-
-    let info = mock_info("sender", &coins(300u128, "uusd"));
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    pledge(deps.as_mut(), "backer", 10, Milliseconds(100), 50).unwrap();
 
+    // The due period goes to the campaign and the rest is returned.
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer", &[]), msg).unwrap();
     assert_eq!(
         Response::new()
             .add_message(BankMsg::Send {
-                to_address: "sender".to_string(),
-                // Refund sent back as they only were able to mint one.
-                amount: coins(150, "uusd")
+                to_address: "owner".to_string(),
+                amount: coins(10, DENOM),
             })
-            .add_attribute("action", "purchase")
-            .add_attribute("number_of_tokens_wanted", "1")
-            .add_attribute("number_of_tokens_purchased", "1")
-            .add_submessage(generate_economics_message("sender", "Purchase")),
-        res
-    );
-
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[2]));
-    state.amount_to_send += Uint128::from(90u128);
-    state.amount_sold += Uint128::from(1u128);
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    assert_eq!(
-        vec![
-            get_purchase(MOCK_TOKENS_FOR_SALE[0], "sender"),
-            get_purchase(MOCK_TOKENS_FOR_SALE[1], "sender"),
-            get_purchase(MOCK_TOKENS_FOR_SALE[2], "sender")
-        ],
-        PURCHASES.load(deps.as_ref().storage, "sender").unwrap()
-    );
-
-    // Try to purchase an additional token when limit has already been reached.
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-
-    assert_eq!(ContractError::PurchaseLimitReached {}, res.unwrap_err());
-
-    // User 2 tries to purchase 2 but only 1 is left.
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(2),
-    };
-
-    let info = mock_info("user2", &coins(300, "uusd"));
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    assert_eq!(
-        Response::new()
             .add_message(BankMsg::Send {
-                to_address: "user2".to_string(),
-                // Refund sent back as they only were able to mint one.
-                amount: coins(150, "uusd")
+                to_address: "backer".to_string(),
+                amount: coins(40, DENOM),
             })
-            .add_attribute("action", "purchase")
-            .add_attribute("number_of_tokens_wanted", "2")
-            .add_attribute("number_of_tokens_purchased", "1")
-            .add_submessage(generate_economics_message("user2", "Purchase")),
-        res
-    );
-
-    assert_eq!(
-        vec![get_purchase(MOCK_TOKENS_FOR_SALE[3], "user2"),],
-        PURCHASES.load(deps.as_ref().storage, "user2").unwrap()
-    );
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[3]));
-    state.amount_to_send += Uint128::from(90u128);
-    state.amount_sold += Uint128::from(1u128);
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    assert_eq!(
-        NUMBER_OF_TOKENS_AVAILABLE
-            .load(deps.as_ref().storage)
-            .unwrap(),
-        Uint128::zero()
-    );
-
-    // User 2 tries to purchase again.
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-
-    let info = mock_info("user2", &coins(150, "uusd"));
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-
-    assert_eq!(ContractError::AllTokensPurchased {}, res.unwrap_err());
-}
-
-#[test]
-fn test_purchase_more_than_allowed_per_wallet() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-
-    // Mint four tokens.
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[0]).unwrap();
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[1]).unwrap();
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[2]).unwrap();
-    mint(deps.as_mut(), MOCK_TOKENS_FOR_SALE[3]).unwrap();
-
-    // Try to purchase 4
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(4),
-    };
-
-    let state = State {
-        end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: 3,
-        amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
-        amount_transferred: Uint128::zero(),
-        recipient: Recipient::from_string("recipient"),
-    };
-    STATE.save(deps.as_mut().storage, &state).unwrap();
-
-    let info = mock_info("sender", &coins(600, "uusd"));
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_message(BankMsg::Send {
-                to_address: "sender".to_string(),
-                amount: coins(150, "uusd")
-            })
-            .add_attribute("action", "purchase")
-            // Number got truncated to 3 which is the max possible.
-            .add_attribute("number_of_tokens_wanted", "3")
-            .add_attribute("number_of_tokens_purchased", "3")
-            .add_submessage(generate_economics_message("sender", "Purchase")),
-        res
-    );
-}
-
-#[test]
-fn test_end_sale_not_expired() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    let state = State {
-        end_time: Expiration::AtHeight(mock_env().block.height + 1),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(1u128),
-        max_amount_per_wallet: 2,
-        amount_sold: Uint128::zero(),
-        amount_to_send: Uint128::zero(),
-        amount_transferred: Uint128::zero(),
-        recipient: Recipient::from_string("recipient"),
-    };
-    STATE.save(deps.as_mut().storage, &state).unwrap();
-    NUMBER_OF_TOKENS_AVAILABLE
-        .save(deps.as_mut().storage, &Uint128::new(1))
-        .unwrap();
-
-    let msg = ExecuteMsg::EndSale { limit: None };
-    let info = mock_info("anyone", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert_eq!(ContractError::SaleNotEnded {}, res.unwrap_err());
-}
-
-fn mint(deps: DepsMut, token_id: impl Into<String>) -> Result<Response, ContractError> {
-    let msg = ExecuteMsg::Mint(vec![CrowdfundMintMsg {
-        token_id: token_id.into(),
-        owner: None,
-        token_uri: None,
-        extension: TokenExtension {
-            publisher: "publisher".to_string(),
-        },
-    }]);
-    execute(deps, mock_env(), mock_info("owner", &[]), msg)
-}
-
-#[test]
-fn test_integration_conditions_not_met() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-
-    // Mint all tokens.
-    for &token_id in MOCK_TOKENS_FOR_SALE {
-        let _res = mint(deps.as_mut(), token_id).unwrap();
-        assert!(AVAILABLE_TOKENS.has(deps.as_ref().storage, token_id));
-    }
-
-    assert_eq!(
-        NUMBER_OF_TOKENS_AVAILABLE
-            .load(deps.as_ref().storage)
-            .unwrap(),
-        Uint128::new(7)
-    );
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let msg = ExecuteMsg::StartSale {
-        start_time: None,
-        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(5u128),
-        max_amount_per_wallet: Some(2),
-        recipient: Recipient::from_string("recipient"),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    // Can't mint once sale started.
-    let res = mint(deps.as_mut(), "token_id");
-    assert_eq!(ContractError::SaleStarted {}, res.unwrap_err());
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let info = mock_info("A", &coins(150, "uusd"));
-    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let info = mock_info("B", &coins(150, "uusd"));
-    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let info = mock_info("C", &coins(150, "uusd"));
-    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    // Using current time since start time wasn't provided
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-    let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
-
-    let state = State {
-        end_time: end_expiration,
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(5u128),
-        max_amount_per_wallet: 2,
-        amount_sold: Uint128::from(4u128),
-        amount_to_send: Uint128::from(360u128),
-        amount_transferred: Uint128::zero(),
-        recipient: Recipient::from_string("recipient"),
-    };
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    assert_eq!(
-        vec![
-            get_purchase(MOCK_TOKENS_FOR_SALE[0], "A"),
-            get_purchase(MOCK_TOKENS_FOR_SALE[1], "A")
-        ],
-        PURCHASES.load(deps.as_ref().storage, "A").unwrap()
-    );
-
-    assert_eq!(
-        vec![get_purchase(MOCK_TOKENS_FOR_SALE[2], "B"),],
-        PURCHASES.load(deps.as_ref().storage, "B").unwrap()
-    );
-
-    assert_eq!(
-        vec![get_purchase(MOCK_TOKENS_FOR_SALE[3], "C"),],
-        PURCHASES.load(deps.as_ref().storage, "C").unwrap()
-    );
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[0]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[1]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[2]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[3]));
-
-    assert_eq!(
-        NUMBER_OF_TOKENS_AVAILABLE
-            .load(deps.as_ref().storage)
-            .unwrap(),
-        Uint128::new(3)
-    );
-
-    let mut env = mock_env();
-    env.block.time = env.block.time.plus_hours(1);
-
-    // User B claims their own refund.
-    let msg = ExecuteMsg::ClaimRefund {};
-    let info = mock_info("B", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "claim_refund")
-            .add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: "B".to_string(),
-                amount: coins(150, "uusd"),
-            }))
-            .add_submessage(generate_economics_message("B", "ClaimRefund")),
-        res
-    );
-
-    assert!(!PURCHASES.has(deps.as_ref().storage, "B"));
-
-    env.contract.address = Addr::unchecked(MOCK_CONDITIONS_NOT_MET_CONTRACT);
-    deps.querier.tokens_left_to_burn = 7;
-    let msg = ExecuteMsg::EndSale { limit: None };
-    let info = mock_info("anyone", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
-    let refund_msgs: Vec<CosmosMsg> = vec![
-        // All of A's payments grouped into one message.
-        CosmosMsg::Bank(BankMsg::Send {
-            to_address: "A".to_string(),
-            amount: coins(300, "uusd"),
-        }),
-        CosmosMsg::Bank(BankMsg::Send {
-            to_address: "C".to_string(),
-            amount: coins(150, "uusd"),
-        }),
-    ];
-    let burn_msgs: Vec<CosmosMsg> = vec![
-        get_burn_message(MOCK_TOKENS_FOR_SALE[0]),
-        get_burn_message(MOCK_TOKENS_FOR_SALE[1]),
-        get_burn_message(MOCK_TOKENS_FOR_SALE[2]),
-        get_burn_message(MOCK_TOKENS_FOR_SALE[3]),
-        // Tokens that were not sold.
-        get_burn_message(MOCK_TOKENS_FOR_SALE[4]),
-        get_burn_message(MOCK_TOKENS_FOR_SALE[5]),
-        get_burn_message(MOCK_TOKENS_FOR_SALE[6]),
-    ];
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "issue_refunds_and_burn_tokens")
-            .add_messages(refund_msgs)
-            .add_messages(burn_msgs)
-            .add_submessage(generate_economics_message("anyone", "EndSale")),
-        res
-    );
-
-    assert!(!PURCHASES.has(deps.as_ref().storage, "A"));
-    assert!(!PURCHASES.has(deps.as_ref().storage, "C"));
-
-    // Burned tokens have been removed.
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[4]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[5]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[6]));
-
-    deps.querier.tokens_left_to_burn = 0;
-    let _res = execute(deps.as_mut(), env, info, msg).unwrap();
-    assert!(STATE.may_load(deps.as_mut().storage).unwrap().is_none());
-    assert_eq!(
-        NUMBER_OF_TOKENS_AVAILABLE
-            .load(deps.as_ref().storage)
-            .unwrap(),
-        Uint128::zero()
-    );
-}
-
-#[test]
-fn test_integration_conditions_met() {
-    let mut deps = mock_dependencies_custom(&[]);
-    deps.querier.contract_address = MOCK_CONDITIONS_MET_CONTRACT.to_string();
-    let modules = vec![Module {
-        name: Some(RATES.to_owned()),
-        address: AndrAddr::from_string(MOCK_RATES_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    init(deps.as_mut(), Some(modules));
-    let mut env = mock_env();
-    env.contract.address = Addr::unchecked(MOCK_CONDITIONS_MET_CONTRACT);
-
-    // Mint all tokens.
-    for &token_id in MOCK_TOKENS_FOR_SALE {
-        let _res = mint(deps.as_mut(), token_id).unwrap();
-        assert!(AVAILABLE_TOKENS.has(deps.as_ref().storage, token_id));
-    }
-    let current_time = mock_env().block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-
-    let msg = ExecuteMsg::StartSale {
-        start_time: None,
-        end_time: Milliseconds::from_nanos((current_time + 2) * 1_000_000),
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(3u128),
-        max_amount_per_wallet: Some(2),
-        recipient: Recipient::from_string("recipient"),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let info = mock_info("A", &coins(150, "uusd"));
-    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let info = mock_info("B", &coins(150, "uusd"));
-    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let info = mock_info("C", &coins(150, "uusd"));
-    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: Some(1),
-    };
-    let info = mock_info("D", &coins(150, "uusd"));
-    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    // Using current time since start time wasn't provided
-    let current_time = env.block.time.nanos() / MILLISECONDS_TO_NANOSECONDS_RATIO;
-    let end_expiration = expiration_from_milliseconds(Milliseconds(current_time + 2)).unwrap();
-    let mut state = State {
-        end_time: end_expiration,
-        price: coin(100, "uusd"),
-        min_tokens_sold: Uint128::from(3u128),
-        max_amount_per_wallet: 2,
-        amount_sold: Uint128::from(5u128),
-        amount_to_send: Uint128::from(450u128),
-        amount_transferred: Uint128::zero(),
-        recipient: Recipient::from_string("recipient"),
-    };
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    assert_eq!(
-        vec![
-            get_purchase(MOCK_TOKENS_FOR_SALE[0], "A"),
-            get_purchase(MOCK_TOKENS_FOR_SALE[1], "A")
-        ],
-        PURCHASES.load(deps.as_ref().storage, "A").unwrap()
-    );
-
-    assert_eq!(
-        vec![get_purchase(MOCK_TOKENS_FOR_SALE[2], "B"),],
-        PURCHASES.load(deps.as_ref().storage, "B").unwrap()
-    );
-    assert_eq!(
-        vec![get_purchase(MOCK_TOKENS_FOR_SALE[3], "C"),],
-        PURCHASES.load(deps.as_ref().storage, "C").unwrap()
-    );
-    assert_eq!(
-        vec![get_purchase(MOCK_TOKENS_FOR_SALE[4], "D"),],
-        PURCHASES.load(deps.as_ref().storage, "D").unwrap()
-    );
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[0]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[1]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[2]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[3]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[4]));
-
-    env.block.time = env.block.time.plus_hours(1);
-    env.contract.address = Addr::unchecked(MOCK_CONDITIONS_MET_CONTRACT);
-
-    let msg = ExecuteMsg::EndSale { limit: Some(1) };
-    let info = mock_info("anyone", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "transfer_tokens_and_send_funds")
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[0],
-                AndrAddr::from_string("A")
-            ))
-            .add_submessages(get_rates_messages())
-            .add_submessage(generate_economics_message("anyone", "EndSale")),
-        res
-    );
-
-    assert_eq!(
-        vec![get_purchase(MOCK_TOKENS_FOR_SALE[1], "A")],
-        PURCHASES.load(deps.as_ref().storage, "A").unwrap()
-    );
-
-    state.amount_transferred += Uint128::from(1u128);
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    let msg = ExecuteMsg::EndSale { limit: Some(2) };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "transfer_tokens_and_send_funds")
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[1],
-                AndrAddr::from_string("A")
-            ))
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[2],
-                AndrAddr::from_string("B")
-            ))
-            .add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: MOCK_ROYALTY_RECIPIENT.to_owned(),
-                amount: vec![Coin {
-                    // Royalty of 10% for A and B combined
-                    amount: Uint128::from(20u128),
-                    denom: "uusd".to_string(),
-                }],
-            }))
-            .add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: MOCK_TAX_RECIPIENT.to_owned(),
-                amount: vec![Coin {
-                    // Combined tax for both A and B
-                    amount: Uint128::from(100u128),
-                    denom: "uusd".to_string(),
-                }],
-            }))
-            .add_submessage(generate_economics_message("anyone", "EndSale")),
-        res
-    );
-
-    assert!(!PURCHASES.has(deps.as_ref().storage, "A"),);
-    assert!(!PURCHASES.has(deps.as_ref().storage, "B"),);
-    assert!(PURCHASES.has(deps.as_ref().storage, "C"),);
-    assert!(PURCHASES.has(deps.as_ref().storage, "D"),);
-
-    state.amount_transferred += Uint128::from(2u128);
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    let msg = ExecuteMsg::EndSale { limit: None };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-
-    assert!(!PURCHASES.has(deps.as_ref().storage, "C"),);
-    assert!(!PURCHASES.has(deps.as_ref().storage, "D"),);
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "transfer_tokens_and_send_funds")
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[3],
-                AndrAddr::from_string("C")
-            ))
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[4],
-                AndrAddr::from_string("D")
-            ))
-            .add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: MOCK_ROYALTY_RECIPIENT.to_owned(),
-                amount: vec![Coin {
-                    // Royalty of 10% for C and D combined
-                    amount: Uint128::from(20u128),
-                    denom: "uusd".to_string(),
-                }],
-            }))
-            .add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: MOCK_TAX_RECIPIENT.to_owned(),
-                amount: vec![Coin {
-                    // Combined tax for both C and D
-                    amount: Uint128::from(100u128),
-                    denom: "uusd".to_string(),
-                }],
-            }))
-            .add_submessage(generate_economics_message("anyone", "EndSale")),
-        res
-    );
-
-    state.amount_transferred += Uint128::from(2u128);
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    let msg = ExecuteMsg::EndSale { limit: None };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
-    // Added one for economics message
-    assert_eq!(3 + 1, res.messages.len());
-
-    // assert_eq!(
-    //     Response::new()
-    //         .add_attribute("action", "transfer_tokens_and_send_funds")
-    //         // Now that all tokens have been transfered, can send the funds to recipient.
-    //         .add_message(CosmosMsg::Bank(BankMsg::Send {
-    //             to_address: "recipient".to_string(),
-    //             amount: coins(450u128, "uusd")
-    //         }))
-    //         // Burn tokens that were not purchased
-    //         .add_message(get_burn_message(MOCK_TOKENS_FOR_SALE[5]))
-    //         .add_message(get_burn_message(MOCK_TOKENS_FOR_SALE[6])),
-    //     res
-    // );
-
-    state.amount_to_send = Uint128::zero();
-    assert_eq!(state, STATE.load(deps.as_ref().storage).unwrap());
-
-    // Burned tokens removed.
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[5]));
-    assert!(!AVAILABLE_TOKENS.has(deps.as_ref().storage, MOCK_TOKENS_FOR_SALE[6]));
-
-    deps.querier.tokens_left_to_burn = 0;
-    let _res = execute(deps.as_mut(), env, info, msg).unwrap();
-    assert!(STATE.may_load(deps.as_mut().storage).unwrap().is_none());
-    assert_eq!(
-        NUMBER_OF_TOKENS_AVAILABLE
-            .load(deps.as_ref().storage)
-            .unwrap(),
-        Uint128::zero()
-    );
-}
-
-#[test]
-fn test_end_sale_single_purchase() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height - 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::from(1u128),
-                amount_to_send: Uint128::from(100u128),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-
-    PURCHASES
-        .save(
-            deps.as_mut().storage,
-            "A",
-            &vec![Purchase {
-                token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-                purchaser: "A".to_string(),
-                tax_amount: Uint128::zero(),
-                msgs: vec![],
-            }],
-        )
-        .unwrap();
-
-    let msg = ExecuteMsg::EndSale { limit: None };
-    let info = mock_info("anyone", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "transfer_tokens_and_send_funds")
-            // Burn tokens that were not purchased
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[0],
-                AndrAddr::from_string("A")
-            ))
-            .add_submessage(generate_economics_message("anyone", "EndSale")),
-        res
-    );
-}
-
-#[test]
-fn test_end_sale_all_tokens_sold() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                // Sale has not expired yet.
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::from(1u128),
-                amount_to_send: Uint128::from(100u128),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-
-    PURCHASES
-        .save(
-            deps.as_mut().storage,
-            "A",
-            &vec![Purchase {
-                token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-                purchaser: "A".to_string(),
-                tax_amount: Uint128::zero(),
-                msgs: vec![],
-            }],
-        )
-        .unwrap();
-
-    NUMBER_OF_TOKENS_AVAILABLE
-        .save(deps.as_mut().storage, &Uint128::zero())
-        .unwrap();
-
-    let msg = ExecuteMsg::EndSale { limit: None };
-    let info = mock_info("anyone", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "transfer_tokens_and_send_funds")
-            // Burn tokens that were not purchased
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[0],
-                AndrAddr::from_string("A")
-            ))
-            .add_submessage(generate_economics_message("anyone", "EndSale")),
-        res
-    );
-}
-
-#[test]
-fn test_end_sale_some_tokens_sold_threshold_met() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                // Sale has not expired yet.
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::from(2u128),
-                amount_to_send: Uint128::from(100u128),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-
-    PURCHASES
-        .save(
-            deps.as_mut().storage,
-            "A",
-            &vec![Purchase {
-                token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-                purchaser: "A".to_string(),
-                tax_amount: Uint128::zero(),
-                msgs: vec![],
-            }],
-        )
-        .unwrap();
-
-    NUMBER_OF_TOKENS_AVAILABLE
-        .save(deps.as_mut().storage, &Uint128::one())
-        .unwrap();
-
-    let msg = ExecuteMsg::EndSale { limit: None };
-    // Only the owner can end the sale if only the minimum token threshold is met.
-    // Anyone can end the sale if it's expired or the remaining number of tokens available is zero.
-    let info = mock_info("anyone", &[]);
-    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
-    assert_eq!(err, ContractError::SaleNotEnded {});
-
-    let info = mock_info("owner", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "transfer_tokens_and_send_funds")
-            // Burn tokens that were not purchased
-            .add_message(get_transfer_message(
-                MOCK_TOKENS_FOR_SALE[0],
-                AndrAddr::from_string("A")
-            ))
-            .add_submessage(generate_economics_message("owner", "EndSale")),
+            .add_attribute("action", "cancel_pledge")
+            .add_attribute("backer", "backer")
+            .add_attribute("collected", "10")
+            .add_attribute("refunded", "40")
+            .add_submessage(generate_economics_message("backer", "CancelPledge")),
         res
     );
-}
-
-#[test]
-fn test_end_sale_some_tokens_sold_threshold_not_met() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                // Sale has not expired yet.
-                end_time: Expiration::AtHeight(mock_env().block.height + 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(2u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::from(0u128),
-                amount_to_send: Uint128::from(100u128),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-
-    PURCHASES
-        .save(
-            deps.as_mut().storage,
-            "A",
-            &vec![Purchase {
-                token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-                purchaser: "A".to_string(),
-                tax_amount: Uint128::zero(),
-                msgs: vec![],
-            }],
-        )
-        .unwrap();
-
-    NUMBER_OF_TOKENS_AVAILABLE
-        .save(deps.as_mut().storage, &Uint128::new(2))
-        .unwrap();
-
-    let msg = ExecuteMsg::EndSale { limit: None };
-
-    let info = mock_info("owner", &[]);
-    // Minimum sold is 2, actual sold is 0
-    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(err, ContractError::SaleNotEnded {});
-}
-
-#[test]
-fn test_end_sale_limit_zero() {
-    let mut deps = mock_dependencies_custom(&[]);
-    init(deps.as_mut(), None);
-
-    STATE
-        .save(
-            deps.as_mut().storage,
-            &State {
-                end_time: Expiration::AtHeight(mock_env().block.height - 1),
-                price: coin(100, "uusd"),
-                min_tokens_sold: Uint128::from(1u128),
-                max_amount_per_wallet: 5,
-                amount_sold: Uint128::from(1u128),
-                amount_to_send: Uint128::from(100u128),
-                amount_transferred: Uint128::zero(),
-                recipient: Recipient::from_string("recipient"),
-            },
-        )
-        .unwrap();
-    NUMBER_OF_TOKENS_AVAILABLE
-        .save(deps.as_mut().storage, &Uint128::new(1))
-        .unwrap();
-
-    PURCHASES
-        .save(
-            deps.as_mut().storage,
-            "A",
-            &vec![Purchase {
-                token_id: MOCK_TOKENS_FOR_SALE[0].to_owned(),
-                purchaser: "A".to_string(),
-                tax_amount: Uint128::zero(),
-                msgs: vec![],
-            }],
-        )
-        .unwrap();
-
-    let msg = ExecuteMsg::EndSale { limit: Some(0) };
-    let info = mock_info("anyone", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-
-    assert_eq!(ContractError::LimitMustNotBeZero {}, res.unwrap_err());
-}
-
-#[test]
-fn test_validate_andr_addresses_regular_address() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string("terra1asdf1ssdfadf".to_owned()),
-        owner: None,
-        modules: None,
-        can_mint_after_sale: true,
-        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-    let msg = ExecuteMsg::UpdateAppContract {
-        address: MOCK_APP_CONTRACT.to_owned(),
-    };
-
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    assert_eq!(
-        Response::new()
-            .add_attribute("action", "update_app_contract")
-            .add_attribute("address", MOCK_APP_CONTRACT)
-            .add_submessage(generate_economics_message("owner", "UpdateAppContract")),
-        res
-    );
-}
-
-#[test]
-fn test_addresslist() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let modules = vec![Module {
-        name: Some(ADDRESS_LIST.to_owned()),
-        address: AndrAddr::from_string(MOCK_ADDRESS_LIST_CONTRACT.to_owned()),
-        is_mutable: false,
-    }];
-    let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-        modules: Some(modules),
-        can_mint_after_sale: true,
-        owner: None,
-        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    // Not whitelisted user
-    let msg = ExecuteMsg::Purchase {
-        number_of_tokens: None,
-    };
-    let info = mock_info("not_whitelisted", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-
-    assert_eq!(
-        ContractError::Std(StdError::generic_err(
-            "Querier contract error: InvalidAddress"
-        )),
-        res.unwrap_err()
-    );
-}
-
-#[test]
-fn test_update_token_contract() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-        modules: None,
-        can_mint_after_sale: true,
-        owner: None,
-        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-    let msg = ExecuteMsg::UpdateTokenContract {
-        address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-    };
-
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
-    assert!(res.is_ok())
-}
-
-#[test]
-fn test_update_token_contract_unauthorized() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-        modules: None,
-        can_mint_after_sale: true,
-        owner: None,
-        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
-    };
-
-    let info = mock_info("app_contract", &[]);
-    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-    let msg = ExecuteMsg::UpdateTokenContract {
-        address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-    };
-
-    let unauth_info = mock_info("attacker", &[]);
-    let res = execute(deps.as_mut(), mock_env(), unauth_info, msg).unwrap_err();
-    assert_eq!(ContractError::Unauthorized {}, res);
-}
-
-#[test]
-fn test_update_token_contract_post_mint() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-        modules: None,
-        can_mint_after_sale: true,
-        owner: None,
-        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-    mint(deps.as_mut(), "1").unwrap();
-
-    let msg = ExecuteMsg::UpdateTokenContract {
-        address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-    };
-
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(ContractError::Unauthorized {}, res);
-}
-
-#[test]
-fn test_update_token_contract_not_cw721() {
-    let mut deps = mock_dependencies_custom(&[]);
-    let msg = InstantiateMsg {
-        token_address: AndrAddr::from_string(MOCK_TOKEN_CONTRACT.to_owned()),
-        modules: None,
-        can_mint_after_sale: true,
-        owner: None,
-        kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
-    };
-
-    let info = mock_info("owner", &[]);
-    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-    let msg = ExecuteMsg::UpdateTokenContract {
-        address: AndrAddr::from_string("not_a_token_contract".to_owned()),
-    };
-
-    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(ContractError::Unauthorized {}, res);
+    assert!(!pledges().has(deps.as_ref().storage, "backer"));
 }
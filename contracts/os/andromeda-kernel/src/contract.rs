@@ -11,8 +11,11 @@ use cosmwasm_std::{
 };
 
 use crate::ibc::{IBCLifecycleComplete, SudoMsg};
-use crate::reply::{on_reply_create_ado, on_reply_ibc_hooks_packet_send};
-use crate::state::CURR_CHAIN;
+use crate::reply::{
+    on_reply_batch_msg, on_reply_create_ado, on_reply_ibc_direct_packet_send,
+    on_reply_ibc_hooks_packet_send, on_reply_traced_amp_msg,
+};
+use crate::state::{CURR_CHAIN, DYNAMIC_REPLY_ID_START, PENDING_BATCH_MSGS};
 use crate::{execute, query, sudo};
 
 // version info for migration info
@@ -45,6 +48,10 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // Failed messages of best-effort batches are reported rather than reverting the batch
+    if PENDING_BATCH_MSGS.has(deps.storage, msg.id) {
+        return on_reply_batch_msg(deps, env, msg);
+    }
     if msg.result.is_err() {
         return Err(ContractError::Std(StdError::generic_err(format!(
             "{}:{}",
@@ -53,9 +60,14 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
         ))));
     }
 
+    if msg.id >= DYNAMIC_REPLY_ID_START {
+        return on_reply_traced_amp_msg(deps, msg);
+    }
+
     match ReplyId::from_repr(msg.id) {
         Some(ReplyId::CreateADO) => on_reply_create_ado(deps, env, msg),
         Some(ReplyId::IBCHooksPacketSend) => on_reply_ibc_hooks_packet_send(deps, msg),
+        Some(ReplyId::IBCDirectPacketSend) => on_reply_ibc_direct_packet_send(deps, msg),
         _ => Ok(Response::default()),
     }
 }
@@ -82,6 +94,18 @@ pub fn execute(
             packet,
         ),
         ExecuteMsg::Send { message } => execute::send(execute_env, message),
+        ExecuteMsg::SendBatch { messages, mode } => {
+            execute::send_batch(execute_env, messages, mode)
+        }
+        ExecuteMsg::Schedule {
+            message,
+            execute_after,
+            tip,
+        } => execute::schedule(execute_env, message, execute_after, tip),
+        ExecuteMsg::ExecuteScheduled { limit, ids } => {
+            execute::execute_scheduled(execute_env, limit, ids)
+        }
+        ExecuteMsg::CancelScheduled { id } => execute::cancel_scheduled(execute_env, id),
         ExecuteMsg::UpsertKeyAddress { key, value } => {
             execute::upsert_key_address(execute_env, key, value)
         }
@@ -96,17 +120,24 @@ pub fn execute(
             direct_channel_id,
             chain,
             kernel_address,
+            packet_timeout,
         } => execute::assign_channels(
             execute_env,
             ics20_channel_id,
             direct_channel_id,
             chain,
             kernel_address,
+            packet_timeout,
         ),
         ExecuteMsg::Recover {} => execute::recover(execute_env),
+        ExecuteMsg::Retry {
+            channel_id,
+            sequence,
+        } => execute::retry(execute_env, channel_id, sequence),
         ExecuteMsg::UpdateChainName { chain_name } => {
             execute::update_chain_name(execute_env, chain_name)
         }
+        ExecuteMsg::SetTracing { enabled } => execute::set_tracing(execute_env, enabled),
         ExecuteMsg::Internal(msg) => execute::internal(execute_env, msg),
         // Base message
         ExecuteMsg::Ownership(ownership_message) => ADOContract::default().execute_ownership(
@@ -148,6 +179,31 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
         QueryMsg::ChannelInfo { chain } => encode_binary(&query::channel_info(deps, chain)?),
         QueryMsg::Recoveries { addr } => encode_binary(&query::recoveries(deps, addr)?),
         QueryMsg::ChainName {} => encode_binary(&query::chain_name(deps)?),
+        QueryMsg::IbcPacket {
+            channel_id,
+            sequence,
+        } => encode_binary(&query::ibc_packet(deps, channel_id, sequence)?),
+        QueryMsg::IbcPackets {
+            sender,
+            start_after,
+            limit,
+        } => encode_binary(&query::ibc_packets_by_sender(
+            deps,
+            sender,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::TracingEnabled {} => encode_binary(&query::tracing_enabled(deps)?),
+        QueryMsg::Trace { packet_id } => encode_binary(&query::trace(deps, packet_id)?),
+        QueryMsg::TracesByOrigin {
+            origin,
+            start_after,
+            limit,
+        } => encode_binary(&query::traces_by_origin(deps, origin, start_after, limit)?),
+        QueryMsg::ScheduledMsg { id } => encode_binary(&query::scheduled_msg(deps, id)?),
+        QueryMsg::ScheduledMsgs { start_after, limit } => {
+            encode_binary(&query::scheduled_msgs(deps, start_after, limit)?)
+        }
         // Base queries
         QueryMsg::Version {} => encode_binary(&ADOContract::default().query_version(deps)?),
         QueryMsg::Type {} => encode_binary(&ADOContract::default().query_type(deps)?),
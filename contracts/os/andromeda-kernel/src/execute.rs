@@ -4,23 +4,31 @@ use andromeda_std::amp::messages::{AMPCtx, AMPMsg, AMPPkt, IBCConfig};
 use andromeda_std::amp::{ADO_DB_KEY, VFS_KEY};
 
 use andromeda_std::common::context::ExecuteContext;
-use andromeda_std::common::has_coins_merged;
+use andromeda_std::common::milliseconds::Milliseconds;
 use andromeda_std::common::reply::ReplyId;
+use andromeda_std::common::{has_coins_merged, merge_coins};
 use andromeda_std::error::ContractError;
 use andromeda_std::os::aos_querier::AOSQuerier;
-use andromeda_std::os::kernel::{ChannelInfo, IbcExecuteMsg, InternalMsg};
+use andromeda_std::os::kernel::{
+    BatchMode, ChannelInfo, ExecuteAfter, ExecuteMsg, HopResult, IbcExecuteMsg, IbcPacketStatus,
+    InternalMsg, ScheduledMsg, Trace, TraceHop,
+};
 
 use andromeda_std::os::vfs::vfs_resolve_symlink;
 use cosmwasm_std::{
     attr, ensure, to_json_binary, Addr, BankMsg, Binary, Coin, ContractInfoResponse, CosmosMsg,
-    DepsMut, Env, IbcMsg, MessageInfo, Response, StdError, SubMsg, WasmMsg,
+    DepsMut, Env, IbcMsg, MessageInfo, Response, StdError, Storage, SubMsg, Uint64, WasmMsg,
 };
 
-use crate::ibc::{generate_transfer_message, PACKET_LIFETIME};
+use crate::ibc::{generate_transfer_message, packet_timeout, MAX_PACKET_LIFETIME};
 use crate::query;
 use crate::state::{
-    IBCHooksPacketSendState, ADO_OWNER, CHAIN_TO_CHANNEL, CHANNEL_TO_CHAIN, CURR_CHAIN,
-    IBC_FUND_RECOVERY, KERNEL_ADDRESSES, OUTGOING_IBC_HOOKS_PACKETS,
+    add_trace_hop, add_trace_recipient, ibc_packets, next_reply_id, traces,
+    IBCDirectPacketSendState, IBCHooksPacketSendState, PendingBatchMsg, ADO_OWNER,
+    CHAIN_TO_CHANNEL, CHANNEL_TO_CHAIN, CURR_CHAIN, DEFAULT_SCHEDULED_LIMIT, IBC_FUND_RECOVERY,
+    KERNEL_ADDRESSES, MAX_SCHEDULED_LIMIT, NEXT_SCHEDULED_ID, NEXT_TRACE_ID,
+    OUTGOING_IBC_DIRECT_PACKETS, OUTGOING_IBC_HOOKS_PACKETS, PENDING_BATCH_MSGS,
+    SCHEDULED_BY_HEIGHT, SCHEDULED_BY_TIME, SCHEDULED_MSGS, TRACE_RECIPIENTS, TRACING_ENABLED,
 };
 
 pub fn send(ctx: ExecuteContext, message: AMPMsg) -> Result<Response, ContractError> {
@@ -28,10 +36,285 @@ pub fn send(ctx: ExecuteContext, message: AMPMsg) -> Result<Response, ContractEr
         has_coins_merged(ctx.info.funds.as_slice(), message.funds.as_slice()),
         ContractError::InsufficientFunds {}
     );
-    let res = MsgHandler(message).handle(ctx.deps, ctx.info, ctx.env, ctx.amp_ctx, 0)?;
+    let amp_ctx = start_trace(ctx.deps.storage, ctx.amp_ctx, &ctx.info.sender)?;
+    let res = MsgHandler(message).handle(ctx.deps, ctx.info, ctx.env, amp_ctx, 0)?;
+    Ok(res)
+}
+
+pub fn send_batch(
+    ctx: ExecuteContext,
+    messages: Vec<AMPMsg>,
+    mode: BatchMode,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        mut deps,
+        info,
+        env,
+        amp_ctx,
+    } = ctx;
+    ensure!(
+        !messages.is_empty(),
+        ContractError::InvalidPacket {
+            error: Some("No messages supplied".to_string())
+        }
+    );
+    let message_funds = messages
+        .iter()
+        .flat_map(|m| m.funds.clone())
+        .collect::<Vec<Coin>>();
+    ensure!(
+        has_coins_merged(info.funds.as_slice(), message_funds.as_slice()),
+        ContractError::InsufficientFunds {}
+    );
+
+    let amp_ctx = start_trace(deps.storage, amp_ctx, &info.sender)?;
+    let mut res = Response::default();
+    for (idx, message) in messages.into_iter().enumerate() {
+        let sequence = idx as u64;
+        if mode == BatchMode::BestEffort {
+            res.messages.push(best_effort_msg(
+                deps.storage,
+                &env,
+                info.sender.clone(),
+                amp_ctx.clone(),
+                message,
+                sequence,
+            )?);
+            continue;
+        }
+        let msg_res = MsgHandler::new(message).handle(
+            deps.branch(),
+            info.clone(),
+            env.clone(),
+            amp_ctx.clone(),
+            sequence,
+        )?;
+        res.messages.extend(msg_res.messages);
+        res.attributes.extend(msg_res.attributes);
+        res.events.extend(msg_res.events);
+    }
+
+    Ok(res
+        .add_attribute("action", "send_batch")
+        .add_attribute("mode", mode.to_string()))
+}
+
+/// Generates a message for the kernel to handle a message without failing the surrounding
+/// transaction. A failure reverts everything the message did and is reported through a
+/// `batch_msg_failed` event and a refund when the kernel receives the reply.
+fn best_effort_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: Addr,
+    amp_ctx: Option<AMPPkt>,
+    message: AMPMsg,
+    sequence: u64,
+) -> Result<SubMsg, ContractError> {
+    let reply_id = next_reply_id(storage)?;
+    PENDING_BATCH_MSGS.save(
+        storage,
+        reply_id,
+        &PendingBatchMsg {
+            sequence,
+            sender: sender.clone(),
+            recipient: message.recipient.clone(),
+            funds: message.funds.clone(),
+            packet_id: amp_ctx.as_ref().map_or(0, |pkt| pkt.ctx.id),
+        },
+    )?;
+    let funds = message.funds.clone();
+    let msg = ExecuteMsg::Internal(InternalMsg::BestEffortMsg {
+        message,
+        sender,
+        ctx: amp_ctx,
+        sequence: Uint64::new(sequence),
+    });
+    Ok(SubMsg::reply_always(
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&msg)?,
+            funds,
+        },
+        reply_id,
+    ))
+}
+
+/// Handles a message of a best-effort batch on behalf of the sender of the batch
+fn handle_best_effort_msg(
+    ctx: ExecuteContext,
+    message: AMPMsg,
+    sender: Addr,
+    amp_ctx: Option<AMPPkt>,
+    sequence: Uint64,
+) -> Result<Response, ContractError> {
+    ensure!(
+        ctx.info.sender == ctx.env.contract.address,
+        ContractError::Unauthorized {}
+    );
+    let info = MessageInfo {
+        sender,
+        funds: ctx.info.funds,
+    };
+    MsgHandler::new(message).handle(ctx.deps, info, ctx.env, amp_ctx, sequence.u64())
+}
+
+pub fn schedule(
+    ctx: ExecuteContext,
+    message: AMPMsg,
+    execute_after: ExecuteAfter,
+    tip: Option<Coin>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
+    ensure!(
+        message
+            .funds
+            .iter()
+            .chain(tip.iter())
+            .all(|coin| !coin.amount.is_zero()),
+        ContractError::InvalidZeroAmount {}
+    );
+    let required_funds = merge_coins(message.funds.clone(), tip.clone().into_iter().collect());
+    ensure!(
+        has_coins_merged(info.funds.as_slice(), required_funds.as_slice()),
+        ContractError::InsufficientFunds {}
+    );
+    ensure!(
+        !execute_after.is_due(&env.block),
+        ContractError::ExpirationInPast {}
+    );
+
+    let id = NEXT_SCHEDULED_ID.may_load(deps.storage)?.unwrap_or(1);
+    NEXT_SCHEDULED_ID.save(deps.storage, &(id + 1))?;
+    match execute_after {
+        ExecuteAfter::Time(time) => {
+            SCHEDULED_BY_TIME.save(deps.storage, (time.milliseconds(), id), &true)?
+        }
+        ExecuteAfter::Height(height) => {
+            SCHEDULED_BY_HEIGHT.save(deps.storage, (height, id), &true)?
+        }
+    }
+    SCHEDULED_MSGS.save(
+        deps.storage,
+        id,
+        &ScheduledMsg {
+            id: Uint64::new(id),
+            sender: info.sender.clone(),
+            message,
+            execute_after: execute_after.clone(),
+            tip,
+        },
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "schedule"),
+        attr("id", id.to_string()),
+        attr("sender", info.sender),
+        attr("execute_after", execute_after.to_string()),
+    ]))
+}
+
+pub fn execute_scheduled(
+    ctx: ExecuteContext,
+    limit: Option<u32>,
+    ids: Option<Vec<Uint64>>,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
+    let limit = limit
+        .unwrap_or(DEFAULT_SCHEDULED_LIMIT)
+        .min(MAX_SCHEDULED_LIMIT) as usize;
+    let due_ids = match ids {
+        // Explicit ids allow skipping over messages that cannot be executed
+        Some(ids) => {
+            for id in ids.iter() {
+                let scheduled = SCHEDULED_MSGS.load(deps.storage, id.u64())?;
+                ensure!(
+                    scheduled.execute_after.is_due(&env.block),
+                    ContractError::Std(StdError::generic_err(format!(
+                        "Scheduled message {id} is not due"
+                    )))
+                );
+            }
+            ids.into_iter().take(limit).map(|id| id.u64()).collect()
+        }
+        None => query::due_scheduled_ids(deps.as_ref(), &env.block, limit)?,
+    };
+    ensure!(
+        !due_ids.is_empty(),
+        ContractError::Std(StdError::generic_err("No scheduled messages are due"))
+    );
+
+    let mut res = Response::default();
+    let mut tips: Vec<Coin> = vec![];
+    for id in due_ids.iter() {
+        let scheduled = remove_scheduled_msg(deps.storage, *id)?;
+        tips = merge_coins(tips, scheduled.tip.into_iter().collect());
+        let amp_ctx = start_trace(deps.storage, None, &scheduled.sender)?;
+        // The message is sent on behalf of the account that scheduled it
+        res.messages.push(best_effort_msg(
+            deps.storage,
+            &env,
+            scheduled.sender,
+            amp_ctx,
+            scheduled.message,
+            *id,
+        )?);
+    }
+    if !tips.is_empty() {
+        res = res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: tips,
+        });
+    }
+
+    Ok(res
+        .add_attribute("action", "execute_scheduled")
+        .add_attribute(
+            "ids",
+            due_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ))
+}
+
+pub fn cancel_scheduled(ctx: ExecuteContext, id: Uint64) -> Result<Response, ContractError> {
+    let ExecuteContext { deps, info, .. } = ctx;
+    let scheduled = SCHEDULED_MSGS.load(deps.storage, id.u64())?;
+    ensure!(
+        scheduled.sender == info.sender,
+        ContractError::Unauthorized {}
+    );
+    remove_scheduled_msg(deps.storage, id.u64())?;
+
+    let mut res = Response::default().add_attributes(vec![
+        attr("action", "cancel_scheduled"),
+        attr("id", id.to_string()),
+    ]);
+    let refund = merge_coins(scheduled.message.funds, scheduled.tip.into_iter().collect());
+    if !refund.is_empty() {
+        res = res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: refund,
+        });
+    }
     Ok(res)
 }
 
+fn remove_scheduled_msg(storage: &mut dyn Storage, id: u64) -> Result<ScheduledMsg, ContractError> {
+    let scheduled = SCHEDULED_MSGS.load(storage, id)?;
+    SCHEDULED_MSGS.remove(storage, id);
+    match &scheduled.execute_after {
+        ExecuteAfter::Time(time) => SCHEDULED_BY_TIME.remove(storage, (time.milliseconds(), id)),
+        ExecuteAfter::Height(height) => SCHEDULED_BY_HEIGHT.remove(storage, (*height, id)),
+    }
+    Ok(scheduled)
+}
+
 pub fn amp_receive(
     deps: &mut DepsMut,
     info: MessageInfo,
@@ -43,12 +326,15 @@ pub fn amp_receive(
         query::verify_address(deps.as_ref(), info.sender.to_string(),)?,
         ContractError::Unauthorized {}
     );
+    // Packet IDs are only issued by the kernel when tracing packets, to the ADOs it forwards the
+    // packet to
     ensure!(
-        packet.ctx.id == 0,
+        packet.ctx.id == 0 || TRACE_RECIPIENTS.has(deps.storage, (packet.ctx.id, &info.sender)),
         ContractError::InvalidPacket {
             error: Some("Packet ID cannot be provided from outside the Kernel".into())
         }
     );
+    let packet = trace_packet(deps.storage, packet)?;
 
     let mut res = Response::default();
     ensure!(
@@ -196,6 +482,12 @@ pub fn internal(ctx: ExecuteContext, msg: InternalMsg) -> Result<Response, Contr
             address,
             chain,
         } => register_user_cross_chain(ctx, chain, username, address),
+        InternalMsg::BestEffortMsg {
+            message,
+            sender,
+            ctx: amp_ctx,
+            sequence,
+        } => handle_best_effort_msg(ctx, message, sender, amp_ctx, sequence),
     }
 }
 
@@ -222,6 +514,7 @@ pub fn register_user_cross_chain(
         username: username.clone(),
         address: address.clone(),
     };
+    let timeout = packet_timeout(&channel_info, execute_ctx.env.block.time)?;
     let channel_id = if let Some(direct_channel_id) = channel_info.direct_channel_id {
         Ok::<String, ContractError>(direct_channel_id)
     } else {
@@ -232,12 +525,7 @@ pub fn register_user_cross_chain(
     let ibc_msg = IbcMsg::SendPacket {
         channel_id,
         data: to_json_binary(&kernel_msg)?,
-        timeout: execute_ctx
-            .env
-            .block
-            .time
-            .plus_seconds(PACKET_LIFETIME)
-            .into(),
+        timeout: timeout.into(),
     };
 
     Ok(Response::default()
@@ -257,6 +545,7 @@ pub fn assign_channels(
     direct_channel_id: Option<String>,
     chain: String,
     kernel_address: String,
+    packet_timeout: Option<Milliseconds>,
 ) -> Result<Response, ContractError> {
     let contract = ADOContract::default();
     ensure!(
@@ -268,6 +557,16 @@ pub fn assign_channels(
         .load(execute_ctx.deps.storage, &chain)
         .unwrap_or_default();
     channel_info.kernel_address = kernel_address;
+    if let Some(packet_timeout) = packet_timeout {
+        ensure!(
+            !packet_timeout.is_zero()
+                && packet_timeout.milliseconds() <= MAX_PACKET_LIFETIME * 1000,
+            ContractError::InvalidPacket {
+                error: Some("Packet timeout must be between 1 millisecond and 30 days".to_string())
+            }
+        );
+        channel_info.packet_timeout = Some(packet_timeout);
+    }
     if let Some(channel) = direct_channel_id {
         // Remove old direct channel to chain if it exists
         if let Some(direct_channel_id) = channel_info.direct_channel_id {
@@ -322,6 +621,100 @@ pub fn recover(execute_ctx: ExecuteContext) -> Result<Response, ContractError> {
         .add_submessage(sub_msg))
 }
 
+pub fn retry(
+    ctx: ExecuteContext,
+    channel_id: String,
+    sequence: u64,
+) -> Result<Response, ContractError> {
+    let ExecuteContext {
+        deps, info, env, ..
+    } = ctx;
+    let mut packet = ibc_packets().load(deps.storage, (&channel_id, sequence))?;
+    ensure!(packet.sender == info.sender, ContractError::Unauthorized {});
+    ensure!(
+        matches!(
+            packet.status,
+            IbcPacketStatus::Failed { .. } | IbcPacketStatus::TimedOut
+        ),
+        ContractError::InvalidPacket {
+            error: Some(format!(
+                "Only failed or timed out packets can be retried, packet is {}",
+                packet.status
+            ))
+        }
+    );
+    ensure!(
+        has_coins_merged(info.funds.as_slice(), packet.message.funds.as_slice()),
+        ContractError::InsufficientFunds {}
+    );
+
+    let message = packet.message.clone();
+    packet.status = IbcPacketStatus::Retried;
+    ibc_packets().save(deps.storage, (&channel_id, sequence), &packet)?;
+
+    let amp_ctx = start_trace(deps.storage, None, &info.sender)?;
+    let res = MsgHandler::new(message).handle(deps, info, env, amp_ctx, 0)?;
+    Ok(res.add_attributes(vec![
+        attr("action", "retry"),
+        attr("channel_id", channel_id),
+        attr("sequence", sequence.to_string()),
+    ]))
+}
+
+pub fn set_tracing(execute_ctx: ExecuteContext, enabled: bool) -> Result<Response, ContractError> {
+    let contract = ADOContract::default();
+    ensure!(
+        contract.is_contract_owner(execute_ctx.deps.storage, execute_ctx.info.sender.as_str())?,
+        ContractError::Unauthorized {}
+    );
+    TRACING_ENABLED.save(execute_ctx.deps.storage, &enabled)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "set_tracing"),
+        attr("enabled", enabled.to_string()),
+    ]))
+}
+
+fn is_tracing(storage: &dyn Storage) -> Result<bool, ContractError> {
+    Ok(TRACING_ENABLED.may_load(storage)?.unwrap_or(false))
+}
+
+/// Assigns a packet id to the packet and creates its trace if tracing is enabled and the packet
+/// is not traced yet
+fn trace_packet(storage: &mut dyn Storage, packet: AMPPkt) -> Result<AMPPkt, ContractError> {
+    if packet.ctx.id != 0 || !is_tracing(storage)? {
+        return Ok(packet);
+    }
+    let id = NEXT_TRACE_ID.may_load(storage)?.unwrap_or(1);
+    NEXT_TRACE_ID.save(storage, &(id + 1))?;
+    traces().save(
+        storage,
+        id,
+        &Trace {
+            packet_id: Uint64::new(id),
+            origin: packet.ctx.get_origin(),
+            hops: vec![],
+        },
+    )?;
+    Ok(packet.with_id(id))
+}
+
+/// Starts a trace for messages sent directly to the kernel, which have no packet of their own
+fn start_trace(
+    storage: &mut dyn Storage,
+    ctx: Option<AMPPkt>,
+    sender: &Addr,
+) -> Result<Option<AMPPkt>, ContractError> {
+    match ctx {
+        Some(packet) => Ok(Some(trace_packet(storage, packet)?)),
+        None if is_tracing(storage)? => Ok(Some(trace_packet(
+            storage,
+            AMPPkt::new(sender, sender, vec![]),
+        )?)),
+        None => Ok(None),
+    }
+}
+
 pub fn update_chain_name(
     execute_ctx: ExecuteContext,
     chain_name: String,
@@ -365,7 +758,7 @@ impl MsgHandler {
     #[inline]
     pub fn handle(
         &mut self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         info: MessageInfo,
         env: Env,
         ctx: Option<AMPPkt>,
@@ -384,10 +777,34 @@ impl MsgHandler {
         self.update_recipient(resolved_recipient);
         let protocol = self.message().recipient.get_protocol();
 
-        match protocol {
-            Some("ibc") => self.handle_ibc(deps, info, env, ctx, sequence),
-            _ => self.handle_local(deps, info, env, ctx.map(|ctx| ctx.ctx), sequence),
+        let packet_id = ctx.as_ref().map_or(0, |pkt| pkt.ctx.id);
+        let sender = info.sender.clone();
+        let block_height = env.block.height;
+
+        let res = match protocol {
+            Some("ibc") => self.handle_ibc(deps.branch(), info, env, ctx, sequence),
+            _ => self.handle_local(deps.branch(), info, env, ctx.map(|ctx| ctx.ctx), sequence),
+        };
+
+        if packet_id != 0 && is_tracing(deps.storage)? {
+            let result = match &res {
+                Ok(_) => HopResult::Dispatched,
+                Err(err) => HopResult::Failed {
+                    error: err.to_string(),
+                },
+            };
+            let hop = TraceHop {
+                sender,
+                recipient: self.message().recipient.clone(),
+                funds: self.message().funds.clone(),
+                result,
+                block_height,
+            };
+            if traces().has(deps.storage, packet_id) {
+                add_trace_hop(deps.storage, packet_id, &hop)?;
+            }
         }
+        res
     }
 
     /**
@@ -443,6 +860,11 @@ impl MsgHandler {
                 ))
                 .add_attributes(attrs);
         } else {
+            // Forwarded packets keep their id so that further hops are added to the same trace
+            let packet_id = match &ctx {
+                Some(amp_ctx) if is_tracing(deps.storage)? => amp_ctx.id,
+                _ => 0,
+            };
             let origin = if let Some(amp_ctx) = ctx {
                 amp_ctx.get_origin()
             } else {
@@ -473,13 +895,21 @@ impl MsgHandler {
                 let amp_msg =
                     AMPMsg::new(recipient_addr.clone(), message.clone(), Some(funds.clone()));
 
-                let new_packet = AMPPkt::new(origin, previous_sender, vec![amp_msg]);
-
-                new_packet.to_sub_msg(
+                let new_packet =
+                    AMPPkt::new(origin, previous_sender, vec![amp_msg]).with_id(packet_id);
+                let sub_msg = new_packet.to_sub_msg(
                     recipient_addr.clone(),
                     Some(funds.clone()),
                     ReplyId::AMPMsg.repr(),
-                )?
+                )?;
+                if packet_id != 0 {
+                    // The recipient may only send the packet on with its id until the forward
+                    // completes
+                    let reply_id = add_trace_recipient(deps.storage, packet_id, &recipient_addr)?;
+                    SubMsg::reply_always(sub_msg.msg, reply_id)
+                } else {
+                    sub_msg
+                }
             };
 
             res = res
@@ -526,8 +956,8 @@ impl MsgHandler {
 
     fn handle_ibc_direct(
         &self,
-        _deps: DepsMut,
-        _info: MessageInfo,
+        deps: DepsMut,
+        info: MessageInfo,
         env: Env,
         _ctx: Option<AMPPkt>,
         sequence: u64,
@@ -543,6 +973,7 @@ impl MsgHandler {
             }
         );
         let chain = recipient.get_chain().unwrap();
+        let timeout = packet_timeout(&channel_info, env.block.time)?;
         let channel = if let Some(direct_channel) = channel_info.direct_channel_id {
             Ok::<String, ContractError>(direct_channel)
         } else {
@@ -558,15 +989,29 @@ impl MsgHandler {
         let msg = IbcMsg::SendPacket {
             channel_id: channel.clone(),
             data: to_json_binary(&kernel_msg)?,
-            timeout: env.block.time.plus_seconds(PACKET_LIFETIME).into(),
+            timeout: timeout.into(),
         };
 
+        // The packet is recorded once its sequence is known
+        let mut outgoing_packets = OUTGOING_IBC_DIRECT_PACKETS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        outgoing_packets.push(IBCDirectPacketSendState {
+            channel_id: channel.clone(),
+            sender: info.sender,
+            message: self.message().clone(),
+        });
+        OUTGOING_IBC_DIRECT_PACKETS.save(deps.storage, &outgoing_packets)?;
+
         Ok(Response::default()
             .add_attribute(format!("method:{sequence}"), "execute_send_message")
             .add_attribute(format!("channel:{sequence}"), channel)
             .add_attribute("receiving_kernel_address:{}", channel_info.kernel_address)
             .add_attribute("chain:{}", chain)
-            .add_message(msg))
+            .add_submessage(SubMsg::reply_always(
+                msg,
+                ReplyId::IBCDirectPacketSend.repr(),
+            )))
     }
 
     fn handle_ibc_hooks(
@@ -586,6 +1031,7 @@ impl MsgHandler {
             ..
         } = self.message();
         let chain = recipient.get_chain().unwrap();
+        let timeout = packet_timeout(&channel_info, env.block.time)?;
         let channel = if let Some(ics20_channel) = channel_info.ics20_channel_id {
             Ok::<String, ContractError>(ics20_channel)
         } else {
@@ -603,12 +1049,25 @@ impl MsgHandler {
         } else if let Some(AMPPkt { ctx, .. }) = ctx {
             Ok::<Addr, ContractError>(deps.api.addr_validate(&ctx.get_origin())?)
         } else {
-            Ok::<Addr, ContractError>(info.sender)
+            Ok::<Addr, ContractError>(info.sender.clone())
         }?;
+        let msg = generate_transfer_message(
+            &deps.as_ref(),
+            recipient.clone(),
+            message.clone(),
+            msg_funds.clone(),
+            channel.clone(),
+            env.contract.address.to_string(),
+            channel_info.kernel_address.clone(),
+            timeout,
+        )?;
+
         let outgoing_state = IBCHooksPacketSendState {
             channel_id: channel.clone(),
             amount: msg_funds.clone(),
             recovery_addr,
+            sender: info.sender,
+            message: self.message().clone(),
         };
 
         let mut outgoing_packets = OUTGOING_IBC_HOOKS_PACKETS
@@ -616,17 +1075,6 @@ impl MsgHandler {
             .unwrap_or_default();
         outgoing_packets.push(outgoing_state);
         OUTGOING_IBC_HOOKS_PACKETS.save(deps.storage, &outgoing_packets)?;
-
-        let msg = generate_transfer_message(
-            &deps.as_ref(),
-            recipient.clone(),
-            message.clone(),
-            msg_funds.clone(),
-            channel.clone(),
-            env.contract.address.to_string(),
-            channel_info.kernel_address.clone(),
-            env.block.time,
-        )?;
         Ok(Response::default()
             .add_submessage(SubMsg::reply_always(
                 msg,
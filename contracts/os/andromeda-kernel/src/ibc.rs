@@ -1,14 +1,17 @@
-use crate::ack::{make_ack_fail, make_ack_success};
+use crate::ack::{make_ack_fail, make_ack_success, Ack};
 use crate::execute;
 use crate::proto::{DenomTrace, MsgTransfer, QueryDenomTraceRequest};
-use crate::state::{CHANNEL_TO_CHAIN, KERNEL_ADDRESSES};
+use crate::state::{ibc_packets, CHANNEL_TO_CHAIN, KERNEL_ADDRESSES};
 use andromeda_std::amp::VFS_KEY;
 use andromeda_std::common::context::ExecuteContext;
 use andromeda_std::common::reply::ReplyId;
 use andromeda_std::error::{ContractError, Never};
 use andromeda_std::{
     amp::{messages::AMPMsg, AndrAddr},
-    os::{kernel::IbcExecuteMsg, vfs::ExecuteMsg as VFSExecuteMsg},
+    os::{
+        kernel::{ChannelInfo, IbcExecuteMsg, IbcPacketStatus},
+        vfs::ExecuteMsg as VFSExecuteMsg,
+    },
 };
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
@@ -17,13 +20,30 @@ use cosmwasm_std::{
     ensure, from_json, to_json_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env,
     Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannel, IbcChannelCloseMsg,
     IbcChannelConnectMsg, IbcChannelOpenMsg, IbcOrder, IbcPacketAckMsg, IbcPacketReceiveMsg,
-    IbcPacketTimeoutMsg, IbcReceiveResponse, MessageInfo, SubMsg, Timestamp, WasmMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, MessageInfo, Storage, SubMsg, Timestamp, WasmMsg,
 };
 use itertools::Itertools;
 use sha256::digest;
 
 pub const IBC_VERSION: &str = "andr-kernel-1";
 pub const PACKET_LIFETIME: u64 = 604_800u64;
+pub const MAX_PACKET_LIFETIME: u64 = 2_592_000u64;
+
+/// Returns the timeout for a packet sent at the given time over the channels described by
+/// `channel_info`
+pub fn packet_timeout(
+    channel_info: &ChannelInfo,
+    time: Timestamp,
+) -> Result<Timestamp, ContractError> {
+    let lifetime = match channel_info.packet_timeout {
+        Some(timeout) => timeout.milliseconds().checked_mul(1_000_000),
+        None => PACKET_LIFETIME.checked_mul(1_000_000_000),
+    };
+    let timeout = lifetime
+        .and_then(|lifetime| time.nanos().checked_add(lifetime))
+        .ok_or(ContractError::Overflow {})?;
+    Ok(Timestamp::from_nanos(timeout))
+}
 
 #[cw_serde]
 pub enum IBCLifecycleComplete {
@@ -46,15 +66,21 @@ pub enum SudoMsg {
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_timeout(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _msg: IbcPacketTimeoutMsg,
+    msg: IbcPacketTimeoutMsg,
 ) -> Result<IbcBasicResponse, ContractError> {
-    // As with ack above, nothing to do here. If we cared about
-    // keeping track of state between the two chains then we'd want to
-    // respond to this likely as it means that the packet in question
-    // isn't going anywhere.
-    Ok(IbcBasicResponse::new().add_attribute("method", "ibc_packet_timeout"))
+    let packet = msg.packet;
+    update_ibc_packet_status(
+        deps.storage,
+        &packet.src.channel_id,
+        packet.sequence,
+        IbcPacketStatus::TimedOut,
+    )?;
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_timeout")
+        .add_attribute("channel_id", packet.src.channel_id)
+        .add_attribute("sequence", packet.sequence.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -114,11 +140,43 @@ pub fn ibc_packet_receive(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_ack(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _msg: IbcPacketAckMsg,
+    msg: IbcPacketAckMsg,
 ) -> Result<IbcBasicResponse, ContractError> {
-    Ok(IbcBasicResponse::new())
+    let packet = msg.original_packet;
+    let status = match from_json(&msg.acknowledgement.data) {
+        Ok(Ack::Result(_)) => IbcPacketStatus::Acked,
+        Ok(Ack::Error(error)) => IbcPacketStatus::Failed { error },
+        Err(_) => IbcPacketStatus::Failed {
+            error: format!("Invalid acknowledgement: {}", msg.acknowledgement.data),
+        },
+    };
+    update_ibc_packet_status(
+        deps.storage,
+        &packet.src.channel_id,
+        packet.sequence,
+        status.clone(),
+    )?;
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_ack")
+        .add_attribute("channel_id", packet.src.channel_id)
+        .add_attribute("sequence", packet.sequence.to_string())
+        .add_attribute("status", status.to_string()))
+}
+
+/// Updates the status of a sent AMP packet, packets that were not recorded are ignored
+pub fn update_ibc_packet_status(
+    storage: &mut dyn Storage,
+    channel_id: &str,
+    sequence: u64,
+    status: IbcPacketStatus,
+) -> Result<(), ContractError> {
+    if let Some(mut packet) = ibc_packets().may_load(storage, (channel_id, sequence))? {
+        packet.status = status;
+        ibc_packets().save(storage, (channel_id, sequence), &packet)?;
+    }
+    Ok(())
 }
 
 pub fn do_ibc_packet_receive(
@@ -260,7 +318,7 @@ pub fn generate_transfer_message(
     channel: String,
     from_addr: String,
     to_addr: String,
-    time: Timestamp,
+    timeout: Timestamp,
 ) -> Result<MsgTransfer, ContractError> {
     // Convert funds denom
     let new_denom = if funds.denom.starts_with("ibc/") {
@@ -282,8 +340,6 @@ pub fn generate_transfer_message(
     let msg = AMPMsg::new(recipient.get_raw_path(), message, Some(vec![new_coin]));
     let serialized = msg.to_ibc_hooks_memo(to_addr.clone(), from_addr.clone());
 
-    let ts = time.plus_seconds(PACKET_LIFETIME);
-
     Ok(MsgTransfer {
         source_port: TRANSFER_PORT.into(),
         source_channel: channel,
@@ -291,7 +347,7 @@ pub fn generate_transfer_message(
         sender: from_addr,
         receiver: to_addr,
         timeout_height: None,
-        timeout_timestamp: Some(ts.nanos()),
+        timeout_timestamp: Some(timeout.nanos()),
         memo: serialized,
     })
 }
@@ -6,9 +6,9 @@ use andromeda_std::{
         messages::{AMPMsg, AMPMsgConfig},
         AndrAddr,
     },
-    os::kernel::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    os::kernel::{BatchMode, ExecuteAfter, ExecuteMsg, InstantiateMsg, QueryMsg},
 };
-use cosmwasm_std::{to_json_binary, Coin, Empty};
+use cosmwasm_std::{to_json_binary, Coin, Empty, Uint64};
 use cw_multi_test::{Contract, ContractWrapper};
 use serde::Serialize;
 
@@ -61,6 +61,52 @@ pub fn mock_send(
     }
 }
 
+pub fn mock_send_batch(messages: Vec<AMPMsg>, mode: BatchMode) -> ExecuteMsg {
+    ExecuteMsg::SendBatch { messages, mode }
+}
+
+pub fn mock_schedule(
+    message: AMPMsg,
+    execute_after: ExecuteAfter,
+    tip: Option<Coin>,
+) -> ExecuteMsg {
+    ExecuteMsg::Schedule {
+        message,
+        execute_after,
+        tip,
+    }
+}
+
+pub fn mock_execute_scheduled(limit: Option<u32>, ids: Option<Vec<u64>>) -> ExecuteMsg {
+    ExecuteMsg::ExecuteScheduled {
+        limit,
+        ids: ids.map(|ids| ids.into_iter().map(Uint64::new).collect()),
+    }
+}
+
+pub fn mock_cancel_scheduled(id: u64) -> ExecuteMsg {
+    ExecuteMsg::CancelScheduled {
+        id: Uint64::new(id),
+    }
+}
+
+pub fn mock_get_scheduled_msgs(start_after: Option<u64>, limit: Option<u32>) -> QueryMsg {
+    QueryMsg::ScheduledMsgs {
+        start_after: start_after.map(Uint64::new),
+        limit,
+    }
+}
+
+pub fn mock_set_tracing(enabled: bool) -> ExecuteMsg {
+    ExecuteMsg::SetTracing { enabled }
+}
+
+pub fn mock_get_trace(packet_id: u64) -> QueryMsg {
+    QueryMsg::Trace {
+        packet_id: Uint64::new(packet_id),
+    }
+}
+
 pub fn mock_get_key_address(key: impl Into<String>) -> QueryMsg {
     QueryMsg::KeyAddress { key: key.into() }
 }
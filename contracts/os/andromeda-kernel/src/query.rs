@@ -3,12 +3,18 @@ use andromeda_std::{
     error::ContractError,
     os::{
         aos_querier::AOSQuerier,
-        kernel::{ChainNameResponse, ChannelInfoResponse},
+        kernel::{ChainNameResponse, ChannelInfoResponse, IbcPacket, ScheduledMsg, Trace},
     },
 };
-use cosmwasm_std::{Addr, Coin, Deps};
+use cosmwasm_std::{Addr, BlockInfo, Coin, Deps, Order, Uint64};
+use cw_storage_plus::Bound;
 
-use crate::state::{CHAIN_TO_CHANNEL, CURR_CHAIN, IBC_FUND_RECOVERY, KERNEL_ADDRESSES};
+use crate::state::{
+    ibc_packets, load_trace, traces, CHAIN_TO_CHANNEL, CURR_CHAIN, DEFAULT_IBC_PACKETS_LIMIT,
+    DEFAULT_SCHEDULED_LIMIT, DEFAULT_TRACES_LIMIT, IBC_FUND_RECOVERY, KERNEL_ADDRESSES,
+    MAX_IBC_PACKETS_LIMIT, MAX_SCHEDULED_LIMIT, MAX_TRACES_LIMIT, SCHEDULED_BY_HEIGHT,
+    SCHEDULED_BY_TIME, SCHEDULED_MSGS, TRACING_ENABLED,
+};
 
 pub fn key_address(deps: Deps, key: String) -> Result<Addr, ContractError> {
     Ok(KERNEL_ADDRESSES.load(deps.storage, &key)?)
@@ -39,6 +45,7 @@ pub fn channel_info(
             direct: info.direct_channel_id,
             kernel_address: info.kernel_address,
             supported_modules: info.supported_modules,
+            packet_timeout: info.packet_timeout,
         })
     } else {
         None
@@ -57,3 +64,114 @@ pub fn chain_name(deps: Deps) -> Result<ChainNameResponse, ContractError> {
         chain_name: CURR_CHAIN.may_load(deps.storage)?.unwrap_or_default(),
     })
 }
+
+pub fn scheduled_msg(deps: Deps, id: Uint64) -> Result<ScheduledMsg, ContractError> {
+    Ok(SCHEDULED_MSGS.load(deps.storage, id.u64())?)
+}
+
+pub fn scheduled_msgs(
+    deps: Deps,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<ScheduledMsg>, ContractError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_SCHEDULED_LIMIT)
+        .min(MAX_SCHEDULED_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.u64()));
+    let msgs = SCHEDULED_MSGS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect::<Result<Vec<ScheduledMsg>, ContractError>>()?;
+    Ok(msgs)
+}
+
+/// Returns the ids of up to `limit` scheduled messages that are due, oldest first
+pub fn due_scheduled_ids(
+    deps: Deps,
+    block: &BlockInfo,
+    limit: usize,
+) -> Result<Vec<u64>, ContractError> {
+    let mut ids = SCHEDULED_BY_HEIGHT
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::inclusive((block.height, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|key| Ok(key?.1))
+        .collect::<Result<Vec<u64>, ContractError>>()?;
+    let time = block.time.seconds() * 1000;
+    let time_ids = SCHEDULED_BY_TIME
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::inclusive((time, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(limit - ids.len())
+        .map(|key| Ok(key?.1))
+        .collect::<Result<Vec<u64>, ContractError>>()?;
+    ids.extend(time_ids);
+    Ok(ids)
+}
+
+pub fn ibc_packet(
+    deps: Deps,
+    channel_id: String,
+    sequence: u64,
+) -> Result<IbcPacket, ContractError> {
+    Ok(ibc_packets().load(deps.storage, (&channel_id, sequence))?)
+}
+
+pub fn ibc_packets_by_sender(
+    deps: Deps,
+    sender: Addr,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> Result<Vec<IbcPacket>, ContractError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_IBC_PACKETS_LIMIT)
+        .min(MAX_IBC_PACKETS_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|(channel_id, sequence)| Bound::exclusive((channel_id.as_str(), *sequence)));
+    let packets = ibc_packets()
+        .idx
+        .sender
+        .prefix(sender)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect::<Result<Vec<IbcPacket>, ContractError>>()?;
+    Ok(packets)
+}
+
+pub fn tracing_enabled(deps: Deps) -> Result<bool, ContractError> {
+    Ok(TRACING_ENABLED.may_load(deps.storage)?.unwrap_or(false))
+}
+
+pub fn trace(deps: Deps, packet_id: Uint64) -> Result<Trace, ContractError> {
+    let trace = traces().load(deps.storage, packet_id.u64())?;
+    Ok(load_trace(deps.storage, trace)?)
+}
+
+pub fn traces_by_origin(
+    deps: Deps,
+    origin: String,
+    start_after: Option<Uint64>,
+    limit: Option<u32>,
+) -> Result<Vec<Trace>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_TRACES_LIMIT).min(MAX_TRACES_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.u64()));
+    let traces = traces()
+        .idx
+        .origin
+        .prefix(origin)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(load_trace(deps.storage, item?.1)?))
+        .collect::<Result<Vec<Trace>, ContractError>>()?;
+    Ok(traces)
+}
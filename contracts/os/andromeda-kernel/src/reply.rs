@@ -1,20 +1,26 @@
 use crate::{
     proto::MsgTransferResponse,
     state::{
-        IBCHooksPacketSendState, OutgoingPacket, ADO_OWNER, OUTGOING_IBC_HOOKS_PACKETS,
-        OUTGOING_IBC_PACKETS,
+        add_trace_hop, ibc_packets, remove_trace_recipient, traces, IBCDirectPacketSendState,
+        IBCHooksPacketSendState, OutgoingPacket, PendingBatchMsg, ADO_OWNER,
+        OUTGOING_IBC_DIRECT_PACKETS, OUTGOING_IBC_HOOKS_PACKETS, OUTGOING_IBC_PACKETS,
+        PENDING_BATCH_MSGS,
     },
 };
 use andromeda_std::{
     ado_base::{ownership::OwnershipMessage, AndromedaMsg},
+    amp::messages::AMPMsg,
     common::reply::ReplyId,
     common::response::get_reply_address,
     error::ContractError,
-    os::aos_querier::AOSQuerier,
+    os::{
+        aos_querier::AOSQuerier,
+        kernel::{HopResult, IbcPacket, IbcPacketStatus, TraceHop},
+    },
 };
 use cosmwasm_std::{
-    ensure, wasm_execute, Addr, DepsMut, Empty, Env, Reply, Response, SubMsg, SubMsgResponse,
-    SubMsgResult,
+    ensure, wasm_execute, Addr, BankMsg, Coin, DepsMut, Empty, Env, Event, Reply, Response,
+    Storage, SubMsg, SubMsgResponse, SubMsgResult,
 };
 
 /// Handles the reply from an ADO creation
@@ -75,6 +81,8 @@ pub fn on_reply_ibc_hooks_packet_send(
         channel_id,
         recovery_addr,
         amount,
+        sender,
+        message,
     } = outgoing_packets.remove(0);
 
     OUTGOING_IBC_HOOKS_PACKETS.save(deps.storage, &outgoing_packets)?;
@@ -86,6 +94,7 @@ pub fn on_reply_ibc_hooks_packet_send(
             amount,
         },
     )?;
+    save_ibc_packet(deps.storage, &channel_id, sequence, sender, message)?;
 
     Ok(Response::default()
         .add_attribute("action", "ibc_hooks_packet_send")
@@ -93,3 +102,131 @@ pub fn on_reply_ibc_hooks_packet_send(
         .add_attribute("sequence", sequence.to_string())
         .add_attribute("recovery_addr", recovery_addr))
 }
+
+/// Handles the completion of a traced packet forwarded to an ADO
+pub fn on_reply_traced_amp_msg(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    remove_trace_recipient(deps.storage, msg.id)?;
+    Ok(Response::default())
+}
+
+/// Handles the reply from sending a direct IBC packet and records the packet
+pub fn on_reply_ibc_direct_packet_send(
+    deps: DepsMut,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let SubMsgResult::Ok(SubMsgResponse { data, .. }) = msg.result else {
+        return Err(ContractError::InvalidPacket {
+            error: Some(format!("ibc direct: failed reply: {:?}", msg.result)),
+        });
+    };
+    // The send is reverted if the packet cannot be recorded without its sequence
+    let Some(b) = data else {
+        return Err(ContractError::InvalidPacket {
+            error: Some("ibc direct: no data in reply".to_string()),
+        });
+    };
+
+    let mut outgoing_packets = OUTGOING_IBC_DIRECT_PACKETS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    ensure!(
+        !outgoing_packets.is_empty(),
+        ContractError::InvalidPacket {
+            error: Some("ibc direct: no outgoing packets".to_string())
+        }
+    );
+    let IBCDirectPacketSendState {
+        channel_id,
+        sender,
+        message,
+    } = outgoing_packets.remove(0);
+    OUTGOING_IBC_DIRECT_PACKETS.save(deps.storage, &outgoing_packets)?;
+
+    // The response to sending a packet has the same encoding as `MsgTransferResponse`
+    let MsgTransferResponse { sequence } =
+        MsgTransferResponse::decode(&b[..]).map_err(|_e| ContractError::InvalidPacket {
+            error: Some(format!("ibc direct: could not decode response: {b}")),
+        })?;
+    save_ibc_packet(deps.storage, &channel_id, sequence, sender, message)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "ibc_direct_packet_send")
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("sequence", sequence.to_string()))
+}
+
+fn save_ibc_packet(
+    storage: &mut dyn Storage,
+    channel_id: &str,
+    sequence: u64,
+    sender: Addr,
+    message: AMPMsg,
+) -> Result<(), ContractError> {
+    ibc_packets().save(
+        storage,
+        (channel_id, sequence),
+        &IbcPacket {
+            channel_id: channel_id.to_string(),
+            sequence,
+            sender,
+            message,
+            status: IbcPacketStatus::Pending,
+        },
+    )?;
+    Ok(())
+}
+
+/// Handles the reply from a message of a best-effort batch
+///
+/// A failed message is reported in a `batch_msg_failed` event and its funds are refunded to the
+/// sender of the batch. As everything the message did was reverted, its failure is recorded as
+/// the only hop of the message in its trace.
+pub fn on_reply_batch_msg(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let PendingBatchMsg {
+        sequence,
+        sender,
+        recipient,
+        funds,
+        packet_id,
+    } = PENDING_BATCH_MSGS.load(deps.storage, msg.id)?;
+    PENDING_BATCH_MSGS.remove(deps.storage, msg.id);
+
+    let SubMsgResult::Err(error) = msg.result else {
+        return Ok(Response::default());
+    };
+    if packet_id != 0 && traces().has(deps.storage, packet_id) {
+        let hop = TraceHop {
+            sender: sender.clone(),
+            recipient,
+            funds: funds.clone(),
+            result: HopResult::Failed {
+                error: error.clone(),
+            },
+            block_height: env.block.height,
+        };
+        add_trace_hop(deps.storage, packet_id, &hop)?;
+    }
+    Ok(batch_msg_failed(sequence, error, sender, funds))
+}
+
+/// Reports the failure of a message in a best-effort batch and refunds its funds
+pub(crate) fn batch_msg_failed(
+    sequence: u64,
+    error: String,
+    sender: Addr,
+    funds: Vec<Coin>,
+) -> Response {
+    let res = Response::default().add_event(
+        Event::new("batch_msg_failed")
+            .add_attribute("sequence", sequence.to_string())
+            .add_attribute("error", error),
+    );
+    if funds.is_empty() {
+        res
+    } else {
+        res.add_message(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: funds,
+        })
+    }
+}
@@ -1,13 +1,23 @@
-use andromeda_std::os::kernel::ChannelInfo;
+use andromeda_std::amp::{messages::AMPMsg, AndrAddr};
+use andromeda_std::os::kernel::{ChannelInfo, IbcPacket, ScheduledMsg, Trace, TraceHop};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Order, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[cw_serde]
 pub struct IBCHooksPacketSendState {
     pub channel_id: String,
     pub recovery_addr: Addr,
     pub amount: Coin,
+    pub sender: Addr,
+    pub message: AMPMsg,
+}
+
+#[cw_serde]
+pub struct IBCDirectPacketSendState {
+    pub channel_id: String,
+    pub sender: Addr,
+    pub message: AMPMsg,
 }
 
 #[cw_serde]
@@ -16,6 +26,17 @@ pub struct OutgoingPacket {
     pub amount: Coin,
 }
 
+/// A message of a best-effort batch awaiting its reply
+#[cw_serde]
+pub struct PendingBatchMsg {
+    pub sequence: u64,
+    pub sender: Addr,
+    pub recipient: AndrAddr,
+    pub funds: Vec<Coin>,
+    /// The id of the trace the message belongs to, zero if it is not traced
+    pub packet_id: u64,
+}
+
 pub const KERNEL_ADDRESSES: Map<&str, Addr> = Map::new("kernel_addresses");
 pub const _ENV_VARIABLES: Map<&str, String> = Map::new("kernel_env_variables");
 pub const CURR_CHAIN: Item<String> = Item::new("kernel_curr_chain");
@@ -36,3 +57,153 @@ pub const OUTGOING_IBC_HOOKS_PACKETS: Item<Vec<IBCHooksPacketSendState>> =
 pub const OUTGOING_IBC_PACKETS: Map<(&String, u64), OutgoingPacket> =
     Map::new("outgoing_ibc_packets");
 pub const IBC_FUND_RECOVERY: Map<&Addr, Vec<Coin>> = Map::new("ibc_fund_recovery");
+/// Used to store the direct packets sent in the current transaction
+///
+/// Each entry is removed when a reply is received for its packet
+pub const OUTGOING_IBC_DIRECT_PACKETS: Item<Vec<IBCDirectPacketSendState>> =
+    Item::new("outgoing_ibc_direct_packets");
+
+pub const DEFAULT_IBC_PACKETS_LIMIT: u32 = 10;
+pub const MAX_IBC_PACKETS_LIMIT: u32 = 30;
+
+pub struct IbcPacketIndexes<'a> {
+    /// (sender, (channel_id, sequence))
+    pub sender: MultiIndex<'a, Addr, IbcPacket, (&'a str, u64)>,
+}
+
+impl<'a> IndexList<IbcPacket> for IbcPacketIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<IbcPacket>> + '_> {
+        let v: Vec<&dyn Index<IbcPacket>> = vec![&self.sender];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Every outgoing cross-chain AMP message keyed by the channel and sequence of its packet
+pub fn ibc_packets<'a>() -> IndexedMap<'a, (&'a str, u64), IbcPacket, IbcPacketIndexes<'a>> {
+    let indexes = IbcPacketIndexes {
+        sender: MultiIndex::new(
+            |_pk: &[u8], p| p.sender.clone(),
+            "ibc_packets",
+            "ibc_packets__sender",
+        ),
+    };
+    IndexedMap::new("ibc_packets", indexes)
+}
+
+/// The messages of best-effort batches keyed by the id of the reply from handling them
+///
+/// Each entry is removed when a reply is received for its message
+pub const PENDING_BATCH_MSGS: Map<u64, PendingBatchMsg> = Map::new("pending_batch_msgs");
+
+/// Replies that are matched to stored state use ids from this value upwards
+pub const DYNAMIC_REPLY_ID_START: u64 = 1000;
+pub const NEXT_REPLY_ID: Item<u64> = Item::new("next_reply_id");
+
+/// Returns an unused reply id
+pub fn next_reply_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let reply_id = NEXT_REPLY_ID
+        .may_load(storage)?
+        .unwrap_or(DYNAMIC_REPLY_ID_START);
+    NEXT_REPLY_ID.save(storage, &(reply_id + 1))?;
+    Ok(reply_id)
+}
+
+pub const DEFAULT_SCHEDULED_LIMIT: u32 = 10;
+pub const MAX_SCHEDULED_LIMIT: u32 = 30;
+
+pub const NEXT_SCHEDULED_ID: Item<u64> = Item::new("next_scheduled_id");
+pub const SCHEDULED_MSGS: Map<u64, ScheduledMsg> = Map::new("scheduled_msgs");
+/// Scheduled message ids keyed by the time in milliseconds after which they are due
+pub const SCHEDULED_BY_TIME: Map<(u64, u64), bool> = Map::new("scheduled_by_time");
+/// Scheduled message ids keyed by the block height after which they are due
+pub const SCHEDULED_BY_HEIGHT: Map<(u64, u64), bool> = Map::new("scheduled_by_height");
+
+pub const TRACING_ENABLED: Item<bool> = Item::new("tracing_enabled");
+pub const NEXT_TRACE_ID: Item<u64> = Item::new("next_trace_id");
+
+pub const DEFAULT_TRACES_LIMIT: u32 = 10;
+pub const MAX_TRACES_LIMIT: u32 = 30;
+
+pub struct TraceIndexes<'a> {
+    /// (origin, packet_id)
+    pub origin: MultiIndex<'a, String, Trace, u64>,
+}
+
+impl<'a> IndexList<Trace> for TraceIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Trace>> + '_> {
+        let v: Vec<&dyn Index<Trace>> = vec![&self.origin];
+        Box::new(v.into_iter())
+    }
+}
+
+/// The traces of AMP packets keyed by packet id. Their hops are stored in `TRACE_HOPS`.
+pub fn traces<'a>() -> IndexedMap<'a, u64, Trace, TraceIndexes<'a>> {
+    let indexes = TraceIndexes {
+        origin: MultiIndex::new(|_pk: &[u8], t| t.origin.clone(), "traces", "traces__origin"),
+    };
+    IndexedMap::new("traces", indexes)
+}
+
+/// The recorded hops of AMP packets keyed by packet id and the index of the hop
+pub const TRACE_HOPS: Map<(u64, u32), TraceHop> = Map::new("trace_hops");
+/// The number of forwards of each traced packet to an ADO that have not completed yet. While
+/// any are pending the ADO may send the packet on with its id.
+pub const TRACE_RECIPIENTS: Map<(u64, &Addr), u32> = Map::new("trace_recipients");
+/// The packet id and recipient of each pending forward of a traced packet keyed by reply id
+pub const TRACE_REPLIES: Map<u64, (u64, Addr)> = Map::new("trace_replies");
+
+/// Records that a traced packet is being forwarded to the recipient, returning the id of the
+/// reply that completes the forward
+pub fn add_trace_recipient(
+    storage: &mut dyn Storage,
+    packet_id: u64,
+    recipient: &Addr,
+) -> StdResult<u64> {
+    let pending = TRACE_RECIPIENTS
+        .may_load(storage, (packet_id, recipient))?
+        .unwrap_or_default();
+    TRACE_RECIPIENTS.save(storage, (packet_id, recipient), &(pending + 1))?;
+    let reply_id = next_reply_id(storage)?;
+    TRACE_REPLIES.save(storage, reply_id, &(packet_id, recipient.clone()))?;
+    Ok(reply_id)
+}
+
+/// Completes the forward of a traced packet, after which the recipient can no longer send the
+/// packet on unless another forward to it is pending
+pub fn remove_trace_recipient(storage: &mut dyn Storage, reply_id: u64) -> StdResult<()> {
+    let Some((packet_id, recipient)) = TRACE_REPLIES.may_load(storage, reply_id)? else {
+        return Ok(());
+    };
+    TRACE_REPLIES.remove(storage, reply_id);
+    let pending = TRACE_RECIPIENTS
+        .may_load(storage, (packet_id, &recipient))?
+        .unwrap_or_default();
+    if pending > 1 {
+        TRACE_RECIPIENTS.save(storage, (packet_id, &recipient), &(pending - 1))
+    } else {
+        TRACE_RECIPIENTS.remove(storage, (packet_id, &recipient));
+        Ok(())
+    }
+}
+
+/// Appends a hop to the trace of the given packet
+pub fn add_trace_hop(storage: &mut dyn Storage, packet_id: u64, hop: &TraceHop) -> StdResult<()> {
+    let hop_idx = TRACE_HOPS
+        .prefix(packet_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |idx| idx + 1);
+    TRACE_HOPS.save(storage, (packet_id, hop_idx), hop)
+}
+
+/// Loads the trace of the given packet along with its hops
+pub fn load_trace(storage: &dyn Storage, mut trace: Trace) -> StdResult<Trace> {
+    let hops = TRACE_HOPS
+        .prefix(trace.packet_id.u64())
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<Vec<TraceHop>>>()?;
+    trace.hops.extend(hops);
+    Ok(trace)
+}
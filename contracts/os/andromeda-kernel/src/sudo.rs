@@ -1,6 +1,8 @@
-use andromeda_std::error::ContractError;
+use andromeda_std::{error::ContractError, os::kernel::IbcPacketStatus};
 use cosmwasm_std::{DepsMut, Response};
 
+use crate::ibc::update_ibc_packet_status;
+
 pub mod ibc_lifecycle {
     // As with most IBC Hooks methods these were adapted from:
     // https://github.com/osmosis-labs/osmosis/blob/main/cosmwasm/contracts/crosschain-swaps/src/ibc_lifecycle.rs
@@ -14,10 +16,16 @@ pub mod ibc_lifecycle {
         deps: DepsMut,
         source_channel: String,
         sequence: u64,
-        _ack: String,
+        ack: String,
         success: bool,
     ) -> Result<Response, ContractError> {
         let response = Response::new().add_attribute("action", "receive_ack");
+        let status = if success {
+            IbcPacketStatus::Acked
+        } else {
+            IbcPacketStatus::Failed { error: ack }
+        };
+        update_ibc_packet_status(deps.storage, &source_channel, sequence, status)?;
 
         // Check if there is an inflight packet for the received (channel, sequence)
         let sent_packet =
@@ -55,6 +63,12 @@ pub mod ibc_lifecycle {
         sequence: u64,
     ) -> Result<Response, ContractError> {
         let response = Response::new().add_attribute("action", "receive_timeout");
+        update_ibc_packet_status(
+            deps.storage,
+            &source_channel,
+            sequence,
+            IbcPacketStatus::TimedOut,
+        )?;
 
         // Check if there is an inflight packet for the received (channel, sequence)
         let sent_packet =
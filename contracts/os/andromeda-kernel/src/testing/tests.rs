@@ -1,24 +1,39 @@
 use crate::{
-    contract::{execute, instantiate},
-    ibc::PACKET_LIFETIME,
-    state::{ADO_OWNER, CHAIN_TO_CHANNEL, CHANNEL_TO_CHAIN, CURR_CHAIN, KERNEL_ADDRESSES},
+    ack::make_ack_fail,
+    contract::{execute, instantiate, query, reply},
+    ibc::{ibc_packet_ack, ibc_packet_timeout, MAX_PACKET_LIFETIME, PACKET_LIFETIME},
+    proto::MsgTransferResponse,
+    state::{
+        PendingBatchMsg, ADO_OWNER, CHAIN_TO_CHANNEL, CHANNEL_TO_CHAIN, CURR_CHAIN,
+        DYNAMIC_REPLY_ID_START, KERNEL_ADDRESSES, PENDING_BATCH_MSGS, SCHEDULED_MSGS,
+        TRACE_RECIPIENTS,
+    },
 };
 use andromeda_std::{
     amp::{
         messages::{AMPMsg, AMPPkt},
-        ADO_DB_KEY, VFS_KEY,
+        AndrAddr, ADO_DB_KEY, VFS_KEY,
     },
+    common::{milliseconds::Milliseconds, reply::ReplyId},
     error::ContractError,
-    os::kernel::{ChannelInfo, ExecuteMsg, IbcExecuteMsg, InstantiateMsg, InternalMsg},
+    os::kernel::{
+        BatchMode, ChannelInfo, ExecuteAfter, ExecuteMsg, HopResult, IbcExecuteMsg, IbcPacket,
+        IbcPacketStatus, InstantiateMsg, InternalMsg, QueryMsg, ScheduledMsg, Trace, TraceHop,
+    },
     testing::mock_querier::{
-        mock_dependencies_custom, MOCK_ADODB_CONTRACT, MOCK_FAKE_KERNEL_CONTRACT,
-        MOCK_KERNEL_CONTRACT, MOCK_VFS_CONTRACT,
+        mock_dependencies_custom, MOCK_ADODB_CONTRACT, MOCK_APP_CONTRACT,
+        MOCK_FAKE_KERNEL_CONTRACT, MOCK_KERNEL_CONTRACT, MOCK_VFS_CONTRACT,
     },
 };
 use cosmwasm_std::{
-    testing::{mock_dependencies, mock_env, mock_info},
-    to_json_binary, Addr, Binary, CosmosMsg, IbcMsg,
+    attr, coin, coins, from_json,
+    testing::{
+        mock_dependencies, mock_env, mock_ibc_packet_ack, mock_ibc_packet_timeout, mock_info,
+    },
+    to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, Event, IbcAcknowledgement, IbcMsg,
+    Reply, Response, StdError, SubMsg, SubMsgResponse, SubMsgResult, Uint64, WasmMsg,
 };
+use prost::Message;
 
 #[test]
 fn proper_initialization() {
@@ -137,6 +152,7 @@ fn test_register_user_cross_chain() {
         ics20_channel_id: Some("1".to_string()),
         direct_channel_id: Some("2".to_string()),
         supported_modules: vec![],
+        packet_timeout: None,
     };
     CHAIN_TO_CHANNEL
         .save(deps.as_mut().storage, chain, &channel_info)
@@ -191,12 +207,14 @@ fn test_assign_channels() {
         ics20_channel_id: Some("1".to_string()),
         direct_channel_id: Some("2".to_string()),
         supported_modules: vec![],
+        packet_timeout: None,
     };
     let msg = ExecuteMsg::AssignChannels {
         ics20_channel_id: channel_info.ics20_channel_id.clone(),
         direct_channel_id: channel_info.direct_channel_id.clone(),
         chain: chain.to_string(),
         kernel_address: channel_info.kernel_address,
+        packet_timeout: None,
     };
     execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
@@ -205,6 +223,7 @@ fn test_assign_channels() {
         direct_channel_id: Some("3".to_string()),
         chain: chain.to_string(),
         kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        packet_timeout: None,
     };
     execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
@@ -248,8 +267,30 @@ fn test_assign_channels() {
         direct_channel_id: None,
         chain: chain.to_string(),
         kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        packet_timeout: None,
     };
-    execute(deps.as_mut(), env, info, msg).unwrap();
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Packet timeouts must be non-zero and bounded
+    for packet_timeout in [
+        Milliseconds::zero(),
+        Milliseconds::from_seconds(MAX_PACKET_LIFETIME + 1),
+    ] {
+        let msg = ExecuteMsg::AssignChannels {
+            ics20_channel_id: None,
+            direct_channel_id: None,
+            chain: chain.to_string(),
+            kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+            packet_timeout: Some(packet_timeout),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPacket {
+                error: Some("Packet timeout must be between 1 millisecond and 30 days".to_string())
+            }
+        );
+    }
 
     // The previous ics20 channel was "1"
     let previous_ics20_channel_chain = CHANNEL_TO_CHAIN
@@ -286,6 +327,7 @@ fn test_assign_channels_unauthorized() {
         direct_channel_id: Some("3".to_string()),
         chain: chain.to_string(),
         kernel_address: MOCK_KERNEL_CONTRACT.to_string(),
+        packet_timeout: None,
     };
     let err = execute(deps.as_mut(), env, unauth_info, msg).unwrap_err();
     assert_eq!(err, ContractError::Unauthorized {});
@@ -318,6 +360,7 @@ fn test_send_cross_chain_no_channel() {
         direct_channel_id: None,
         chain: "chain2".to_string(),
         kernel_address: Addr::unchecked("kernal2").to_string(),
+        packet_timeout: None,
     };
     execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
     let internal_msg = InternalMsg::RegisterUserCrossChain {
@@ -364,6 +407,7 @@ fn test_handle_ibc_direct() {
         ics20_channel_id: Some("1".to_string()),
         direct_channel_id: Some("2".to_string()),
         supported_modules: vec![],
+        packet_timeout: None,
     };
     KERNEL_ADDRESSES
         .save(
@@ -390,3 +434,903 @@ fn test_handle_ibc_direct() {
     // * message fails even though it is a non-default binary message
     assert!(res.is_ok());
 }
+
+fn batch_messages() -> Vec<AMPMsg> {
+    vec![
+        AMPMsg::new("recipient", Binary::default(), Some(coins(100, "uandr"))),
+        AMPMsg::new(
+            "ibc://chain2/recipient",
+            to_json_binary(&"message").unwrap(),
+            Some(coins(50, "uandr")),
+        ),
+    ]
+}
+
+fn best_effort_msg(reply_id: u64, message: AMPMsg, sender: &str, sequence: u64) -> SubMsg {
+    let funds = message.funds.clone();
+    let msg = ExecuteMsg::Internal(InternalMsg::BestEffortMsg {
+        message,
+        sender: Addr::unchecked(sender),
+        ctx: None,
+        sequence: Uint64::new(sequence),
+    });
+    SubMsg::reply_always(
+        WasmMsg::Execute {
+            contract_addr: mock_env().contract.address.to_string(),
+            msg: to_json_binary(&msg).unwrap(),
+            funds,
+        },
+        reply_id,
+    )
+}
+
+fn failed_reply(id: u64, error: impl Into<String>) -> Reply {
+    Reply {
+        id,
+        result: SubMsgResult::Err(error.into()),
+    }
+}
+
+#[test]
+fn test_send_batch() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let info = mock_info("user", &[coin(150, "uandr")]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            ADO_DB_KEY,
+            &Addr::unchecked(MOCK_ADODB_CONTRACT),
+        )
+        .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+
+    let msg = ExecuteMsg::SendBatch {
+        messages: batch_messages(),
+        mode: BatchMode::Atomic,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[coin(100, "uandr")]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {});
+
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("Channel not found for chain chain2".to_string())
+        }
+    );
+
+    let msg = ExecuteMsg::SendBatch {
+        messages: batch_messages(),
+        mode: BatchMode::BestEffort,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    // Each message is handled by the kernel calling itself so that a failure reverts it
+    let messages = batch_messages();
+    assert_eq!(
+        res.messages,
+        vec![
+            best_effort_msg(DYNAMIC_REPLY_ID_START, messages[0].clone(), "user", 0),
+            best_effort_msg(DYNAMIC_REPLY_ID_START + 1, messages[1].clone(), "user", 1),
+        ]
+    );
+    assert_eq!(
+        PENDING_BATCH_MSGS
+            .load(deps.as_ref().storage, DYNAMIC_REPLY_ID_START)
+            .unwrap(),
+        PendingBatchMsg {
+            sequence: 0,
+            sender: Addr::unchecked("user"),
+            recipient: AndrAddr::from_string("recipient"),
+            funds: coins(100, "uandr"),
+            packet_id: 0,
+        }
+    );
+
+    // Only the kernel can handle the messages of a best-effort batch
+    let best_effort = |sequence: usize| {
+        ExecuteMsg::Internal(InternalMsg::BestEffortMsg {
+            message: messages[sequence].clone(),
+            sender: Addr::unchecked("user"),
+            ctx: None,
+            sequence: Uint64::new(sequence as u64),
+        })
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &coins(100, "uandr")),
+        best_effort(0),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let kernel = env.contract.address.to_string();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&kernel, &coins(100, "uandr")),
+        best_effort(0),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_error(
+            BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: coins(100, "uandr"),
+            },
+            ReplyId::AMPMsg.repr(),
+        )]
+    );
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&kernel, &coins(50, "uandr")),
+        best_effort(1),
+    )
+    .unwrap_err();
+
+    // The failed message is reported and its funds are refunded
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        failed_reply(DYNAMIC_REPLY_ID_START + 1, err.to_string()),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "user".to_string(),
+            amount: coins(50, "uandr"),
+        })]
+    );
+    assert_eq!(
+        res.events,
+        vec![Event::new("batch_msg_failed")
+            .add_attribute("sequence", "1")
+            .add_attribute(
+                "error",
+                ContractError::InvalidPacket {
+                    error: Some("Channel not found for chain chain2".to_string())
+                }
+                .to_string()
+            )]
+    );
+
+    let succeeded = Reply {
+        id: DYNAMIC_REPLY_ID_START,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = reply(deps.as_mut(), env, succeeded).unwrap();
+    assert_eq!(res, Response::default());
+    assert!(PENDING_BATCH_MSGS.is_empty(deps.as_ref().storage));
+}
+
+#[test]
+fn test_scheduled_msgs() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let mut env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            ADO_DB_KEY,
+            &Addr::unchecked(MOCK_ADODB_CONTRACT),
+        )
+        .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+    let [message, failing_message]: [AMPMsg; 2] = batch_messages().try_into().unwrap();
+
+    // The funds must cover the tip as well
+    let msg = ExecuteMsg::Schedule {
+        message: message.clone(),
+        execute_after: ExecuteAfter::Height(env.block.height + 10),
+        tip: Some(coin(10, "uandr")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[coin(100, "uandr")]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[coin(110, "uandr")]),
+        msg,
+    )
+    .unwrap();
+
+    // Zero amounts are rejected for the tip and the funds of the message
+    let msg = ExecuteMsg::Schedule {
+        message: message.clone(),
+        execute_after: ExecuteAfter::Height(env.block.height + 10),
+        tip: Some(coin(0, "uandr")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[coin(100, "uandr")]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+    let msg = ExecuteMsg::Schedule {
+        message: AMPMsg::new("recipient", Binary::default(), Some(coins(0, "uandr"))),
+        execute_after: ExecuteAfter::Height(env.block.height + 10),
+        tip: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+    let msg = ExecuteMsg::Schedule {
+        message: message.clone(),
+        execute_after: ExecuteAfter::Height(env.block.height),
+        tip: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[coin(100, "uandr")]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ExpirationInPast {});
+
+    let execute_after =
+        ExecuteAfter::Time(Milliseconds::from_seconds(env.block.time.seconds() + 1));
+    let msg = ExecuteMsg::Schedule {
+        message: failing_message.clone(),
+        execute_after: execute_after.clone(),
+        tip: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("other_user", &[coin(50, "uandr")]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "schedule"),
+            attr("id", "2"),
+            attr("sender", "other_user"),
+            attr("execute_after", execute_after.to_string()),
+        ]
+    );
+
+    // Only the sender can cancel a scheduled message
+    let msg = ExecuteMsg::Schedule {
+        message: AMPMsg::new("recipient", Binary::default(), None),
+        execute_after: ExecuteAfter::Height(env.block.height + 5),
+        tip: Some(coin(5, "uandr")),
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[coin(5, "uandr")]),
+        msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::CancelScheduled { id: Uint64::new(3) };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("other_user", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "user".to_string(),
+            amount: coins(5, "uandr"),
+        })]
+    );
+
+    let scheduled_msgs: Vec<ScheduledMsg> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ScheduledMsgs {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        scheduled_msgs,
+        vec![
+            ScheduledMsg {
+                id: Uint64::new(1),
+                sender: Addr::unchecked("user"),
+                message: message.clone(),
+                execute_after: ExecuteAfter::Height(env.block.height + 10),
+                tip: Some(coin(10, "uandr")),
+            },
+            ScheduledMsg {
+                id: Uint64::new(2),
+                sender: Addr::unchecked("other_user"),
+                message: failing_message.clone(),
+                execute_after,
+                tip: None,
+            },
+        ]
+    );
+
+    let msg = ExecuteMsg::ExecuteScheduled {
+        limit: None,
+        ids: Some(vec![Uint64::new(1)]),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Scheduled message 1 is not due"))
+    );
+
+    let msg = ExecuteMsg::ExecuteScheduled {
+        limit: None,
+        ids: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("No scheduled messages are due"))
+    );
+
+    env.block.height += 10;
+    env.block.time = env.block.time.plus_seconds(1);
+
+    // Due messages can be executed by id, out of order
+    let ids_msg = ExecuteMsg::ExecuteScheduled {
+        limit: None,
+        ids: Some(vec![Uint64::new(2)]),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper", &[]),
+        ids_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![best_effort_msg(
+            DYNAMIC_REPLY_ID_START,
+            failing_message,
+            "other_user",
+            2
+        )]
+    );
+    assert!(res.attributes.contains(&attr("ids", "2")));
+
+    // The funds of the failed message are refunded to its sender
+    let error = ContractError::InvalidPacket {
+        error: Some("Channel not found for chain chain2".to_string()),
+    };
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        failed_reply(DYNAMIC_REPLY_ID_START, error.to_string()),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "other_user".to_string(),
+            amount: coins(50, "uandr"),
+        })]
+    );
+    assert_eq!(
+        res.events,
+        vec![Event::new("batch_msg_failed")
+            .add_attribute("sequence", "2")
+            .add_attribute(
+                "error",
+                ContractError::InvalidPacket {
+                    error: Some("Channel not found for chain chain2".to_string())
+                }
+                .to_string()
+            )]
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            best_effort_msg(DYNAMIC_REPLY_ID_START + 1, message, "user", 1),
+            // The tips are paid to the executor
+            SubMsg::new(BankMsg::Send {
+                to_address: "keeper".to_string(),
+                amount: coins(10, "uandr"),
+            }),
+        ]
+    );
+    assert!(PENDING_BATCH_MSGS.has(deps.as_ref().storage, DYNAMIC_REPLY_ID_START + 1));
+    assert!(SCHEDULED_MSGS.is_empty(deps.as_ref().storage));
+}
+
+fn ibc_packets(deps: Deps, sender: &str) -> Vec<IbcPacket> {
+    from_json(
+        query(
+            deps,
+            mock_env(),
+            QueryMsg::IbcPackets {
+                sender: Addr::unchecked(sender),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn ibc_direct_packet_sent(sequence: u64) -> Reply {
+    Reply {
+        id: ReplyId::IBCDirectPacketSend.repr(),
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: Some(MsgTransferResponse { sequence }.encode_to_vec().into()),
+        }),
+    }
+}
+
+#[test]
+fn test_ibc_packet_lifecycle() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+    let msg = ExecuteMsg::AssignChannels {
+        ics20_channel_id: None,
+        direct_channel_id: Some("2".to_string()),
+        chain: "chain2".to_string(),
+        kernel_address: MOCK_FAKE_KERNEL_CONTRACT.to_string(),
+        packet_timeout: Some(Milliseconds::from_seconds(60)),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let message = AMPMsg::new(
+        "ibc://chain2/recipient",
+        to_json_binary(&"message").unwrap(),
+        None,
+    );
+    let msg = ExecuteMsg::Send {
+        message: message.clone(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            IbcMsg::SendPacket {
+                channel_id: "2".to_string(),
+                data: to_json_binary(&IbcExecuteMsg::SendMessage {
+                    recipient: AndrAddr::from_string("/recipient"),
+                    message: message.message.clone(),
+                })
+                .unwrap(),
+                timeout: env.block.time.plus_seconds(60).into(),
+            },
+            ReplyId::IBCDirectPacketSend.repr(),
+        )]
+    );
+
+    // The packet is recorded once its sequence is received
+    reply(deps.as_mut(), env.clone(), ibc_direct_packet_sent(29)).unwrap();
+    let mut expected = IbcPacket {
+        channel_id: "2".to_string(),
+        sequence: 29,
+        sender: Addr::unchecked("user"),
+        message,
+        status: IbcPacketStatus::Pending,
+    };
+    assert_eq!(ibc_packets(deps.as_ref(), "user"), vec![expected.clone()]);
+    assert!(ibc_packets(deps.as_ref(), "other_user").is_empty());
+
+    // Pending packets cannot be retried
+    let msg = ExecuteMsg::Retry {
+        channel_id: "2".to_string(),
+        sequence: 29,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some(
+                "Only failed or timed out packets can be retried, packet is pending".to_string()
+            )
+        }
+    );
+
+    let ack = mock_ibc_packet_ack(
+        "2",
+        &"message",
+        IbcAcknowledgement::new(make_ack_fail("error".to_string())),
+    )
+    .unwrap();
+    ibc_packet_ack(deps.as_mut(), env.clone(), ack).unwrap();
+    expected.status = IbcPacketStatus::Failed {
+        error: "error".to_string(),
+    };
+    assert_eq!(ibc_packets(deps.as_ref(), "user"), vec![expected.clone()]);
+
+    // Only the sender can retry a packet
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("other_user", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, ReplyId::IBCDirectPacketSend.repr());
+    expected.status = IbcPacketStatus::Retried;
+    assert_eq!(ibc_packets(deps.as_ref(), "user"), vec![expected.clone()]);
+
+    reply(deps.as_mut(), env.clone(), ibc_direct_packet_sent(30)).unwrap();
+    let mut timeout = mock_ibc_packet_timeout("2", &"message").unwrap();
+    timeout.packet.sequence = 30;
+    ibc_packet_timeout(deps.as_mut(), env, timeout).unwrap();
+    let retried = IbcPacket {
+        sequence: 30,
+        status: IbcPacketStatus::TimedOut,
+        ..expected.clone()
+    };
+    assert_eq!(
+        ibc_packets(deps.as_ref(), "user"),
+        vec![expected.clone(), retried.clone()]
+    );
+
+    // Packets cannot be sent when the reply does not include their sequence
+    let msg = ExecuteMsg::Send {
+        message: expected.message.clone(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("user", &[]), msg).unwrap();
+    let no_data = Reply {
+        id: ReplyId::IBCDirectPacketSend.repr(),
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let err = reply(deps.as_mut(), mock_env(), no_data).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("ibc direct: no data in reply".to_string())
+        }
+    );
+    assert_eq!(ibc_packets(deps.as_ref(), "user"), vec![expected, retried]);
+}
+
+fn query_trace(deps: Deps, packet_id: u64) -> Trace {
+    from_json(
+        query(
+            deps,
+            mock_env(),
+            QueryMsg::Trace {
+                packet_id: Uint64::new(packet_id),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_tracing() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: None,
+            chain_name: "andromeda".to_string(),
+        },
+    )
+    .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            ADO_DB_KEY,
+            &Addr::unchecked(MOCK_ADODB_CONTRACT),
+        )
+        .unwrap();
+    KERNEL_ADDRESSES
+        .save(
+            deps.as_mut().storage,
+            VFS_KEY,
+            &Addr::unchecked(MOCK_VFS_CONTRACT),
+        )
+        .unwrap();
+
+    // Only the owner can enable tracing
+    let msg = ExecuteMsg::SetTracing { enabled: true };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+    let enabled: bool =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::TracingEnabled {}).unwrap()).unwrap();
+    assert!(enabled);
+
+    // Packets forwarded to ADOs carry the id of their trace
+    let message = to_json_binary(&true).unwrap();
+    let msg = ExecuteMsg::Send {
+        message: AMPMsg::new(MOCK_APP_CONTRACT, message.clone(), None),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), msg).unwrap();
+    let expected_packet = AMPPkt::new(
+        "user",
+        "user",
+        vec![AMPMsg::new(MOCK_APP_CONTRACT, message, Some(vec![]))],
+    )
+    .with_id(1);
+    let expected_msg = expected_packet
+        .to_sub_msg(MOCK_APP_CONTRACT, Some(vec![]), ReplyId::AMPMsg.repr())
+        .unwrap()
+        .msg;
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(expected_msg, DYNAMIC_REPLY_ID_START)]
+    );
+
+    // The ADO sends the packet on, adding a hop to the trace
+    let packet = AMPPkt::new(
+        "user",
+        MOCK_APP_CONTRACT,
+        vec![AMPMsg::new(
+            "recipient",
+            Binary::default(),
+            Some(coins(100, "uandr")),
+        )],
+    )
+    .with_id(1);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_APP_CONTRACT, &coins(100, "uandr")),
+        ExecuteMsg::AMPReceive(packet.clone()),
+    )
+    .unwrap();
+
+    // Packet ids must have been issued by the kernel
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_APP_CONTRACT, &coins(100, "uandr")),
+        ExecuteMsg::AMPReceive(packet.clone().with_id(5)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("Packet ID cannot be provided from outside the Kernel".to_string())
+        }
+    );
+
+    assert_eq!(
+        query_trace(deps.as_ref(), 1),
+        Trace {
+            packet_id: Uint64::new(1),
+            origin: "user".to_string(),
+            hops: vec![
+                TraceHop {
+                    sender: Addr::unchecked("user"),
+                    recipient: AndrAddr::from_string(MOCK_APP_CONTRACT),
+                    funds: vec![],
+                    result: HopResult::Dispatched,
+                    block_height: env.block.height,
+                },
+                TraceHop {
+                    sender: Addr::unchecked(MOCK_APP_CONTRACT),
+                    recipient: AndrAddr::from_string("recipient"),
+                    funds: coins(100, "uandr"),
+                    result: HopResult::Dispatched,
+                    block_height: env.block.height,
+                },
+            ],
+        }
+    );
+
+    // Failures of best-effort batches are recorded
+    let msg = ExecuteMsg::SendBatch {
+        messages: batch_messages(),
+        mode: BatchMode::BestEffort,
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &coins(150, "uandr")),
+        msg,
+    )
+    .unwrap();
+    // The failed message reverts any hops it recorded, so its failure is recorded on its reply
+    let error = ContractError::InvalidPacket {
+        error: Some("Channel not found for chain chain2".to_string()),
+    }
+    .to_string();
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        failed_reply(DYNAMIC_REPLY_ID_START + 2, error.clone()),
+    )
+    .unwrap();
+    let trace = query_trace(deps.as_ref(), 2);
+    assert_eq!(
+        trace.hops,
+        vec![TraceHop {
+            sender: Addr::unchecked("user"),
+            recipient: AndrAddr::from_string("ibc://chain2/recipient"),
+            funds: coins(50, "uandr"),
+            result: HopResult::Failed { error },
+            block_height: env.block.height,
+        }]
+    );
+
+    let traces: Vec<Trace> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::TracesByOrigin {
+                origin: "user".to_string(),
+                start_after: Some(Uint64::one()),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(traces, vec![trace]);
+
+    // Ids of existing traces are rejected from ADOs the packet was not forwarded to
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_APP_CONTRACT, &coins(100, "uandr")),
+        ExecuteMsg::AMPReceive(packet.clone().with_id(2)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("Packet ID cannot be provided from outside the Kernel".to_string())
+        }
+    );
+    assert_eq!(query_trace(deps.as_ref(), 2).hops.len(), 1);
+
+    // Once the forward to the ADO completes it can no longer send the packet on with its id
+    let completed = Reply {
+        id: DYNAMIC_REPLY_ID_START,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    reply(deps.as_mut(), env.clone(), completed).unwrap();
+    assert!(!TRACE_RECIPIENTS.has(
+        deps.as_ref().storage,
+        (1, &Addr::unchecked(MOCK_APP_CONTRACT))
+    ));
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_APP_CONTRACT, &coins(100, "uandr")),
+        ExecuteMsg::AMPReceive(packet.with_id(1)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPacket {
+            error: Some("Packet ID cannot be provided from outside the Kernel".to_string())
+        }
+    );
+
+    // Nothing is recorded once tracing is disabled
+    let msg = ExecuteMsg::SetTracing { enabled: false };
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Send {
+        message: AMPMsg::new("recipient", Binary::default(), Some(coins(100, "uandr"))),
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &coins(100, "uandr")),
+        msg,
+    )
+    .unwrap();
+    assert!(query(
+        deps.as_ref(),
+        env,
+        QueryMsg::Trace {
+            packet_id: Uint64::new(3)
+        }
+    )
+    .is_err());
+}
@@ -9,7 +9,10 @@ use cosmwasm_std::{
     entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
 };
 
-use crate::{execute, query};
+use crate::{
+    execute, query,
+    state::{index_symlink, paths, PathInfo},
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:andromeda-vfs";
@@ -75,6 +78,24 @@ pub fn execute(
             name,
             parent_address,
         } => execute::add_child(execute_env, name, parent_address),
+        ExecuteMsg::RemovePath {
+            name,
+            parent_address,
+        } => execute::remove_path(execute_env, name, parent_address),
+        ExecuteMsg::RenamePath {
+            name,
+            new_name,
+            parent_address,
+        } => execute::rename_path(execute_env, name, new_name, parent_address),
+        ExecuteMsg::MovePath {
+            name,
+            parent_address,
+            new_parent_address,
+        } => execute::move_path(execute_env, name, parent_address, new_parent_address),
+        ExecuteMsg::TransferUsername { new_owner } => {
+            execute::transfer_username(execute_env, new_owner)
+        }
+        ExecuteMsg::ReleaseUsername {} => execute::release_username(execute_env),
         ExecuteMsg::RegisterLibrary {
             lib_name,
             lib_address,
@@ -94,6 +115,24 @@ pub fn execute(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Index symlinks stored before symlinks were indexed by the paths they resolve through
+    let symlinks: Vec<PathInfo> = paths()
+        .idx
+        .parent
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .filter_map(|r| r.ok())
+        .map(|(_, info)| info)
+        .filter(|info| info.symlink.is_some())
+        .collect();
+    for info in symlinks {
+        // Symlinks that no longer resolve are left unindexed
+        let _ = index_symlink(
+            deps.storage,
+            deps.api,
+            (info.parent_address, info.name),
+            info.symlink.unwrap(),
+        );
+    }
     ADOContract::default().migrate(deps, CONTRACT_NAME, CONTRACT_VERSION)
 }
 
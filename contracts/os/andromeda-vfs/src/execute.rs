@@ -12,8 +12,8 @@ use cosmwasm_std::{
 };
 
 use crate::state::{
-    add_path_symlink, add_pathname, paths, resolve_pathname, ADDRESS_LIBRARY, ADDRESS_USERNAME,
-    LIBRARIES, USERS,
+    add_path_symlink, add_pathname, move_pathname, paths, remove_pathname, resolve_pathname,
+    ADDRESS_LIBRARY, ADDRESS_USERNAME, LIBRARIES, USERS,
 };
 
 pub struct ExecuteEnv<'a> {
@@ -139,6 +139,121 @@ pub fn add_child(
     Ok(Response::default())
 }
 
+/// Resolves the parent directory of a path being modified by the sender, with the same ownership
+/// rules as `add_path`: the sender's home directory is used if no parent is provided, any other
+/// parent may only be used by the kernel or the VFS owner.
+fn resolve_parent_address(
+    env: &ExecuteEnv,
+    parent_address: Option<AndrAddr>,
+) -> Result<Addr, ContractError> {
+    ensure!(
+        parent_address.is_none() || is_kernel_or_owner(env)?,
+        ContractError::Unauthorized {}
+    );
+    let parent_andr_addr = parent_address.unwrap_or(AndrAddr::from_string(env.info.sender.clone()));
+    resolve_pathname(
+        env.deps.storage,
+        env.deps.api,
+        parent_andr_addr,
+        &mut vec![],
+    )
+}
+
+fn is_kernel_or_owner(env: &ExecuteEnv) -> Result<bool, ContractError> {
+    let kernel_address = ADOContract::default().get_kernel_address(env.deps.storage)?;
+    Ok(env.info.sender == kernel_address
+        || ADOContract::default().is_contract_owner(env.deps.storage, env.info.sender.as_str())?)
+}
+
+pub fn remove_path(
+    env: ExecuteEnv,
+    name: String,
+    parent_address: Option<AndrAddr>,
+) -> Result<Response, ContractError> {
+    let name = name.to_lowercase();
+    let parent_addr = resolve_parent_address(&env, parent_address)?;
+    // Only the kernel and owner can add paths to another address's directory, so only they can
+    // remove them along with the path to that directory
+    let cascade = is_kernel_or_owner(&env)?;
+    let removed = remove_pathname(
+        env.deps.storage,
+        env.deps.api,
+        parent_addr.clone(),
+        name.clone(),
+        cascade,
+    )?;
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "remove_path"),
+        attr("name", name),
+        attr("parent", parent_addr),
+        attr("removed", removed.len().to_string()),
+    ]))
+}
+
+pub fn rename_path(
+    env: ExecuteEnv,
+    name: String,
+    new_name: String,
+    parent_address: Option<AndrAddr>,
+) -> Result<Response, ContractError> {
+    let name = name.to_lowercase();
+    let new_name = new_name.to_lowercase();
+    let parent_addr = resolve_parent_address(&env, parent_address)?;
+    validate_component_name(new_name.clone())?;
+    let removed = move_pathname(
+        env.deps.storage,
+        env.deps.api,
+        parent_addr.clone(),
+        name.clone(),
+        parent_addr.clone(),
+        new_name.clone(),
+    )?;
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "rename_path"),
+        attr("name", name),
+        attr("new_name", new_name),
+        attr("parent", parent_addr),
+        attr("removed_symlinks", removed.len().to_string()),
+    ]))
+}
+
+pub fn move_path(
+    env: ExecuteEnv,
+    name: String,
+    parent_address: Option<AndrAddr>,
+    new_parent_address: AndrAddr,
+) -> Result<Response, ContractError> {
+    let name = name.to_lowercase();
+    let parent_addr = resolve_parent_address(&env, parent_address)?;
+    let new_parent_addr = resolve_pathname(
+        env.deps.storage,
+        env.deps.api,
+        new_parent_address,
+        &mut vec![],
+    )?;
+    // Paths can only be moved into the sender's home directory, unless sent by the kernel or the
+    // VFS owner
+    ensure!(
+        is_kernel_or_owner(&env)? || new_parent_addr == resolve_parent_address(&env, None)?,
+        ContractError::Unauthorized {}
+    );
+    let removed = move_pathname(
+        env.deps.storage,
+        env.deps.api,
+        parent_addr.clone(),
+        name.clone(),
+        new_parent_addr.clone(),
+        name.clone(),
+    )?;
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "move_path"),
+        attr("name", name),
+        attr("parent", parent_addr),
+        attr("new_parent", new_parent_addr),
+        attr("removed_symlinks", removed.len().to_string()),
+    ]))
+}
+
 const MAX_USERNAME_LENGTH: u64 = 30;
 
 pub fn register_user(
@@ -219,6 +334,53 @@ pub fn register_user(
     ]))
 }
 
+pub fn transfer_username(env: ExecuteEnv, new_owner: Addr) -> Result<Response, ContractError> {
+    #[cfg(not(test))]
+    ensure!(false, ContractError::TemporarilyDisabled {});
+    let kernel = &ADOContract::default().get_kernel_address(env.deps.storage)?;
+    let curr_chain = AOSQuerier::get_current_chain(&env.deps.querier, kernel)?;
+    // Can only transfer usernames on Andromeda chain
+    ensure!(curr_chain == "andromeda", ContractError::Unauthorized {});
+    let username = ADDRESS_USERNAME.load(env.deps.storage, env.info.sender.as_str())?;
+    let new_owner = env.deps.api.addr_validate(new_owner.as_str())?;
+    // Usernames that are valid addresses belong to that address
+    ensure!(
+        env.deps.api.addr_validate(&username).is_err(),
+        ContractError::InvalidUsername {
+            error: Some("Usernames that are valid addresses cannot be transferred".to_string())
+        }
+    );
+    ensure!(
+        !ADDRESS_USERNAME.has(env.deps.storage, new_owner.as_str()),
+        ContractError::InvalidUsername {
+            error: Some("Address already has a username".to_string())
+        }
+    );
+
+    ADDRESS_USERNAME.remove(env.deps.storage, env.info.sender.as_str());
+    USERS.save(env.deps.storage, username.as_str(), &new_owner)?;
+    ADDRESS_USERNAME.save(env.deps.storage, new_owner.as_str(), &username)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "transfer_username"),
+        attr("from", env.info.sender),
+        attr("to", new_owner),
+        attr("username", username),
+    ]))
+}
+
+pub fn release_username(env: ExecuteEnv) -> Result<Response, ContractError> {
+    let username = ADDRESS_USERNAME.load(env.deps.storage, env.info.sender.as_str())?;
+    USERS.remove(env.deps.storage, username.as_str());
+    ADDRESS_USERNAME.remove(env.deps.storage, env.info.sender.as_str());
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "release_username"),
+        attr("addr", env.info.sender),
+        attr("username", username),
+    ]))
+}
+
 pub fn register_library(
     env: ExecuteEnv,
     lib_name: String,
@@ -38,6 +38,33 @@ pub fn mock_add_path(name: impl Into<String>, address: Addr) -> ExecuteMsg {
     }
 }
 
+pub fn mock_remove_path(name: impl Into<String>) -> ExecuteMsg {
+    ExecuteMsg::RemovePath {
+        name: name.into(),
+        parent_address: None,
+    }
+}
+
+pub fn mock_rename_path(name: impl Into<String>, new_name: impl Into<String>) -> ExecuteMsg {
+    ExecuteMsg::RenamePath {
+        name: name.into(),
+        new_name: new_name.into(),
+        parent_address: None,
+    }
+}
+
+pub fn mock_move_path(name: impl Into<String>, new_parent_address: AndrAddr) -> ExecuteMsg {
+    ExecuteMsg::MovePath {
+        name: name.into(),
+        parent_address: None,
+        new_parent_address,
+    }
+}
+
+pub fn mock_transfer_username(new_owner: Addr) -> ExecuteMsg {
+    ExecuteMsg::TransferUsername { new_owner }
+}
+
 pub fn mock_resolve_path_query(path: impl Into<String>) -> QueryMsg {
     QueryMsg::ResolvePath {
         path: AndrAddr::from_string(path.into()),
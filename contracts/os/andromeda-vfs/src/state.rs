@@ -50,6 +50,11 @@ pub const USERS: Map<&str, Addr> = Map::new("users");
 pub const LIBRARIES: Map<&str, Addr> = Map::new("libraries");
 pub const ADDRESS_USERNAME: Map<&str, String> = Map::new("address_username");
 pub const ADDRESS_LIBRARY: Map<&str, String> = Map::new("address_library");
+/// Key of a path: parent_address + component_name
+pub type PathKey = (Addr, String);
+
+/// PK: path a symlink resolves through + path of the symlink
+pub const SYMLINK_DEPENDENTS: Map<(PathKey, PathKey), bool> = Map::new("symlink_dependents");
 
 /**
    Splits a pathname into its components.
@@ -300,12 +305,37 @@ pub fn add_path_symlink(
             symlink: Some(symlink.clone()),
         },
     )?;
-    if symlink.get_protocol().is_none() {
-        // Ensure that the symlink resolves to a valid address
-        let pathname = AndrAddr::from_string(format!("~{}/{}", parent_addr, name));
-        resolve_pathname(storage, api, pathname, &mut vec![])?;
-    }
+    index_symlink(storage, api, (parent_addr, name), symlink)
+}
+
+/**
+   Records the paths a symlink resolves through so that it can be removed along with them.
+
+   Symlinks to other chains cannot be resolved locally and are not indexed.
 
+    * **storage**: CosmWasm storage struct
+    * **api**: CosmWasm API struct
+    * **key**: The key of the symlink
+    * **symlink**: The path the symlink points to
+*/
+pub fn index_symlink(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    key: (Addr, String),
+    symlink: AndrAddr,
+) -> Result<(), ContractError> {
+    if symlink.get_protocol().is_some() {
+        return Ok(());
+    }
+    // Ensure that the symlink resolves to a valid address
+    let pathname = AndrAddr::from_string(format!("~{}/{}", key.0, key.1));
+    let mut resolved_paths = vec![];
+    resolve_pathname(storage, api, pathname, &mut resolved_paths)?;
+    for path in resolved_paths {
+        if path != key {
+            SYMLINK_DEPENDENTS.save(storage, (path, key.clone()), &true)?;
+        }
+    }
     Ok(())
 }
 
@@ -334,6 +364,207 @@ pub fn resolve_symlink(
     }
 }
 
+/**
+   Removes a path along with everything that depends on it, returning the keys of the removed paths.
+
+   When cascading, children of a removed path are removed too, unless its address is a user or
+   library home directory or is still reachable through another path. Symlinks that resolve
+   through any of the removed paths are removed as well.
+
+    * **storage**: CosmWasm storage struct
+    * **api**: CosmWasm API struct
+    * **parent_addr**: The address of the directory containing the path
+    * **name**: The name of the path to remove
+    * **cascade**: Whether the children of the removed path should be removed
+*/
+pub fn remove_pathname(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    parent_addr: Addr,
+    name: String,
+    cascade: bool,
+) -> Result<Vec<(Addr, String)>, ContractError> {
+    let info = paths().load(storage, &(parent_addr.clone(), name.clone()))?;
+    let mut removed = vec![(parent_addr, name)];
+    let mut pending = if cascade { vec![info] } else { vec![] };
+    while let Some(info) = pending.pop() {
+        // Symlinks do not have children of their own
+        if info.symlink.is_some()
+            || is_home_directory(storage, &info.address)
+            || is_reachable(storage, &info.address, &removed)?
+        {
+            continue;
+        }
+        let children: Vec<PathInfo> = paths()
+            .idx
+            .parent
+            .prefix(info.address)
+            .range(storage, None, None, cosmwasm_std::Order::Ascending)
+            .map(|r| r.map(|(_, child)| child))
+            .collect::<Result<_, _>>()?;
+        for child in children {
+            let key = (child.parent_address.clone(), child.name.clone());
+            if !removed.contains(&key) {
+                removed.push(key);
+                pending.push(child);
+            }
+        }
+    }
+    removed.extend(get_dependent_symlinks(storage, api, &removed)?);
+
+    for key in removed.iter() {
+        paths().remove(storage, key)?;
+    }
+    Ok(removed)
+}
+
+/**
+   Moves a path to a new parent and name, returning the keys of the symlinks that were removed
+   because they resolved through the old path.
+
+    * **storage**: CosmWasm storage struct
+    * **api**: CosmWasm API struct
+    * **parent_addr**: The address of the directory containing the path
+    * **name**: The name of the path to move
+    * **new_parent_addr**: The address of the directory to move the path to
+    * **new_name**: The name of the path after it has been moved
+*/
+pub fn move_pathname(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    parent_addr: Addr,
+    name: String,
+    new_parent_addr: Addr,
+    new_name: String,
+) -> Result<Vec<(Addr, String)>, ContractError> {
+    let key = (parent_addr, name);
+    let info = paths().load(storage, &key)?;
+    let new_key = (new_parent_addr.clone(), new_name.clone());
+    ensure!(
+        !paths().has(storage, &new_key),
+        ContractError::InvalidPathname {
+            error: Some("Path already exists".to_string())
+        }
+    );
+    // A directory cannot be moved into itself or one of its subdirectories
+    ensure!(
+        info.symlink.is_some() || !is_descendant(storage, &info.address, &new_parent_addr)?,
+        ContractError::InvalidPathname {
+            error: Some("Cannot move a path into itself".to_string())
+        }
+    );
+
+    let symlinks = get_dependent_symlinks(storage, api, std::slice::from_ref(&key))?;
+    for symlink in symlinks.iter() {
+        paths().remove(storage, symlink)?;
+    }
+    paths().remove(storage, &key)?;
+    paths().save(
+        storage,
+        &new_key,
+        &PathInfo {
+            name: new_name,
+            parent_address: new_parent_addr,
+            ..info
+        },
+    )?;
+    Ok(symlinks)
+}
+
+/// Returns whether the address is the home directory of a registered user or library
+fn is_home_directory(storage: &dyn Storage, address: &Addr) -> bool {
+    ADDRESS_USERNAME.has(storage, address.as_str())
+        || ADDRESS_LIBRARY.has(storage, address.as_str())
+}
+
+/// Returns whether the address is the target of any path other than the excluded ones
+fn is_reachable(
+    storage: &dyn Storage,
+    address: &Addr,
+    excluded: &[(Addr, String)],
+) -> Result<bool, ContractError> {
+    for key in paths().idx.address.prefix(address.clone()).keys(
+        storage,
+        None,
+        None,
+        cosmwasm_std::Order::Ascending,
+    ) {
+        if !excluded.contains(&key?) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns whether the address is, or is contained within, the given directory
+fn is_descendant(
+    storage: &dyn Storage,
+    directory: &Addr,
+    address: &Addr,
+) -> Result<bool, ContractError> {
+    let mut visited = vec![];
+    let mut pending = vec![directory.clone()];
+    while let Some(current) = pending.pop() {
+        if current == *address {
+            return Ok(true);
+        }
+        if visited.contains(&current) {
+            continue;
+        }
+        let children = paths()
+            .idx
+            .parent
+            .prefix(current.clone())
+            .range(storage, None, None, cosmwasm_std::Order::Ascending)
+            .filter_map(|r| r.ok())
+            .filter(|(_, child)| child.symlink.is_none())
+            .map(|(_, child)| child.address);
+        pending.extend(children);
+        visited.push(current);
+    }
+    Ok(false)
+}
+
+/**
+   Returns the keys of any symlinks that resolve through one of the given paths, clearing their
+   entries in the symlink index as the paths are about to be removed or moved.
+
+   Index entries are not removed when a symlink is overwritten, so each indexed symlink is
+   resolved again to confirm it still depends on the path.
+
+    * **storage**: CosmWasm storage struct
+    * **api**: CosmWasm API struct
+    * **keys**: The keys of the paths the symlinks may resolve through
+*/
+fn get_dependent_symlinks(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    keys: &[(Addr, String)],
+) -> Result<Vec<(Addr, String)>, ContractError> {
+    let mut dependents = vec![];
+    for path in keys {
+        let symlinks: Vec<(Addr, String)> = SYMLINK_DEPENDENTS
+            .prefix(path.clone())
+            .keys(storage, None, None, cosmwasm_std::Order::Ascending)
+            .collect::<Result<_, _>>()?;
+        for key in symlinks {
+            SYMLINK_DEPENDENTS.remove(storage, (path.clone(), key.clone()));
+            if keys.contains(&key) || dependents.contains(&key) {
+                continue;
+            }
+            if let Some(symlink) = paths().may_load(storage, &key)?.and_then(|i| i.symlink) {
+                let mut resolved_paths = vec![];
+                // Symlinks that already fail to resolve are left untouched
+                let _ = resolve_pathname(storage, api, symlink, &mut resolved_paths);
+                if resolved_paths.contains(path) {
+                    dependents.push(key);
+                }
+            }
+        }
+    }
+    Ok(dependents)
+}
+
 #[cfg(test)]
 mod test {
     use cosmwasm_std::{testing::mock_dependencies, DepsMut};
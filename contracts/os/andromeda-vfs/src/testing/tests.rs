@@ -1,6 +1,9 @@
 use crate::{
     contract::{execute, instantiate, query},
-    state::{add_pathname, resolve_pathname, PathInfo, ADDRESS_LIBRARY, ADDRESS_USERNAME, USERS},
+    state::{
+        add_pathname, resolve_pathname, PathInfo, ADDRESS_LIBRARY, ADDRESS_USERNAME,
+        SYMLINK_DEPENDENTS, USERS,
+    },
 };
 
 use andromeda_std::{
@@ -16,9 +19,9 @@ use andromeda_std::{
 };
 use andromeda_std::{error::ContractError, os::vfs::QueryMsg};
 use cosmwasm_std::{
-    from_json,
+    attr, from_json,
     testing::{mock_dependencies, mock_env, mock_info},
-    to_json_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, WasmMsg,
+    to_json_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, WasmMsg,
 };
 
 fn instantiate_contract(deps: DepsMut, env: Env, info: MessageInfo) {
@@ -277,7 +280,14 @@ fn test_register_user_foreign_chain() {
         address: None,
     };
     let info = mock_info(MOCK_FAKE_KERNEL_CONTRACT, &[]);
-    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Usernames can only be transferred on Andromeda chain
+    let msg = ExecuteMsg::TransferUsername {
+        new_owner: Addr::unchecked("recipient"),
+    };
+    let err = execute(deps.as_mut(), env, mock_info(sender, &[]), msg).unwrap_err();
     assert_eq!(err, ContractError::Unauthorized {});
 }
 
@@ -840,3 +850,326 @@ fn test_get_paths() {
     let val: Vec<String> = from_json(res).unwrap();
     assert_eq!(val.len(), 2);
 }
+
+fn add_path(deps: DepsMut, sender: &str, name: &str, address: &str, parent: Option<&str>) {
+    let msg = ExecuteMsg::AddPath {
+        name: name.to_string(),
+        address: Addr::unchecked(address),
+        parent_address: parent.map(AndrAddr::from_string),
+    };
+    execute(deps, mock_env(), mock_info(sender, &[]), msg).unwrap();
+}
+
+fn resolve(deps: Deps, path: &str) -> Result<Addr, ContractError> {
+    resolve_pathname(
+        deps.storage,
+        deps.api,
+        AndrAddr::from_string(path),
+        &mut vec![],
+    )
+}
+
+#[test]
+fn test_remove_path() {
+    let mut deps = mock_dependencies();
+    let sender = "sender";
+    let info = mock_info(sender, &[]);
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+    add_path(deps.as_mut(), sender, "dir", "diraddr", None);
+    add_path(deps.as_mut(), sender, "child", "childaddr", Some("diraddr"));
+    add_path(
+        deps.as_mut(),
+        sender,
+        "grandchild",
+        "gcaddr",
+        Some("childaddr"),
+    );
+    add_path(deps.as_mut(), sender, "other", "otheraddr", None);
+    let msg = ExecuteMsg::AddSymlink {
+        name: "link".to_string(),
+        symlink: AndrAddr::from_string(format!("~{sender}/dir/child")),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::AddSymlink {
+        name: "other_link".to_string(),
+        symlink: AndrAddr::from_string(format!("~{sender}/other")),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    // A symlink that no longer resolves through the removed path is kept
+    for target in ["dir", "other"] {
+        let msg = ExecuteMsg::AddSymlink {
+            name: "retargeted".to_string(),
+            symlink: AndrAddr::from_string(format!("~{sender}/{target}")),
+            parent_address: None,
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    }
+
+    // Only the owner of the parent directory can remove a path from it
+    let msg = ExecuteMsg::RemovePath {
+        name: "child".to_string(),
+        parent_address: Some(AndrAddr::from_string("diraddr")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_the_owner", &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let msg = ExecuteMsg::RemovePath {
+        name: "dir".to_string(),
+        parent_address: None,
+    };
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "remove_path"),
+            attr("name", "dir"),
+            attr("parent", sender),
+            attr("removed", "4"),
+        ]
+    );
+
+    for path in ["dir", "dir/child", "dir/child/grandchild", "link"] {
+        assert!(resolve(deps.as_ref(), &format!("~{sender}/{path}")).is_err());
+    }
+    for path in ["other_link", "retargeted"] {
+        assert_eq!(
+            resolve(deps.as_ref(), &format!("~{sender}/{path}")).unwrap(),
+            Addr::unchecked("otheraddr")
+        );
+    }
+    // The index entries for the removed paths are cleared
+    let dir_key = (Addr::unchecked(sender), "dir".to_string());
+    assert!(SYMLINK_DEPENDENTS
+        .prefix(dir_key)
+        .keys(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending
+        )
+        .next()
+        .is_none());
+}
+
+#[test]
+fn test_remove_path_other_home_directory() {
+    let mut deps = mock_dependencies();
+    let owner = "owner";
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info(owner, &[]));
+
+    let victim = "victim";
+    add_path(deps.as_mut(), victim, "wallet", "walletaddr", None);
+
+    // Removing a path to another user's home directory does not remove its contents
+    let attacker = "attacker";
+    add_path(deps.as_mut(), attacker, "evil", victim, None);
+    let msg = ExecuteMsg::RemovePath {
+        name: "evil".to_string(),
+        parent_address: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(attacker, &[]), msg).unwrap();
+    assert!(res.attributes.contains(&attr("removed", "1")));
+    assert_eq!(
+        resolve(deps.as_ref(), &format!("~{victim}/wallet")).unwrap(),
+        Addr::unchecked("walletaddr")
+    );
+
+    // Not even the owner cascades into a registered user's home directory
+    USERS
+        .save(
+            deps.as_mut().storage,
+            "victim_user",
+            &Addr::unchecked(victim),
+        )
+        .unwrap();
+    ADDRESS_USERNAME
+        .save(deps.as_mut().storage, victim, &"victim_user".to_string())
+        .unwrap();
+    add_path(deps.as_mut(), owner, "victim_home", victim, None);
+    let msg = ExecuteMsg::RemovePath {
+        name: "victim_home".to_string(),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env, mock_info(owner, &[]), msg).unwrap();
+    assert_eq!(
+        resolve(deps.as_ref(), &format!("~{victim}/wallet")).unwrap(),
+        Addr::unchecked("walletaddr")
+    );
+}
+
+#[test]
+fn test_remove_path_reachable_children() {
+    let mut deps = mock_dependencies();
+    let sender = "sender";
+    let info = mock_info(sender, &[]);
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+    add_path(deps.as_mut(), sender, "dir", "diraddr", None);
+    add_path(deps.as_mut(), sender, "alias", "diraddr", None);
+    add_path(deps.as_mut(), sender, "child", "childaddr", Some("diraddr"));
+
+    let msg = ExecuteMsg::RemovePath {
+        name: "dir".to_string(),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env, info, msg).unwrap();
+
+    // The children are still reachable through the other path to the directory
+    assert!(resolve(deps.as_ref(), &format!("~{sender}/dir")).is_err());
+    assert_eq!(
+        resolve(deps.as_ref(), &format!("~{sender}/alias/child")).unwrap(),
+        Addr::unchecked("childaddr")
+    );
+}
+
+#[test]
+fn test_rename_and_move_path() {
+    let mut deps = mock_dependencies();
+    let owner = "owner";
+    let sender = "sender";
+    let recipient = "recipient";
+    let info = mock_info(sender, &[]);
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), mock_info(owner, &[]));
+
+    add_path(deps.as_mut(), sender, "dir", "diraddr", None);
+    add_path(deps.as_mut(), owner, "child", "childaddr", Some("diraddr"));
+    add_path(deps.as_mut(), sender, "taken", "takenaddr", None);
+    let msg = ExecuteMsg::AddSymlink {
+        name: "link".to_string(),
+        symlink: AndrAddr::from_string(format!("~{sender}/dir/child")),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::RenamePath {
+        name: "dir".to_string(),
+        new_name: "taken".to_string(),
+        parent_address: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPathname {
+            error: Some("Path already exists".to_string())
+        }
+    );
+
+    let msg = ExecuteMsg::RenamePath {
+        name: "dir".to_string(),
+        new_name: "renamed".to_string(),
+        parent_address: None,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert!(resolve(deps.as_ref(), &format!("~{sender}/dir")).is_err());
+    assert!(resolve(deps.as_ref(), &format!("~{sender}/link")).is_err());
+    assert_eq!(
+        resolve(deps.as_ref(), &format!("~{sender}/renamed/child")).unwrap(),
+        Addr::unchecked("childaddr")
+    );
+
+    // A directory cannot be moved into one of its own subdirectories
+    let msg = ExecuteMsg::MovePath {
+        name: "renamed".to_string(),
+        parent_address: Some(AndrAddr::from_string(format!("~{sender}"))),
+        new_parent_address: AndrAddr::from_string(format!("~{sender}/renamed/child")),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPathname {
+            error: Some("Cannot move a path into itself".to_string())
+        }
+    );
+
+    // Only the kernel or the VFS owner can move paths out of the sender's home directory
+    let msg = ExecuteMsg::MovePath {
+        name: "renamed".to_string(),
+        parent_address: None,
+        new_parent_address: AndrAddr::from_string(format!("~{recipient}")),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    assert!(resolve(deps.as_ref(), &format!("~{recipient}/renamed")).is_err());
+
+    let msg = ExecuteMsg::MovePath {
+        name: "renamed".to_string(),
+        parent_address: Some(AndrAddr::from_string(format!("~{sender}"))),
+        new_parent_address: AndrAddr::from_string(format!("~{recipient}")),
+    };
+    execute(deps.as_mut(), env, mock_info(owner, &[]), msg).unwrap();
+    assert!(resolve(deps.as_ref(), &format!("~{sender}/renamed")).is_err());
+    assert_eq!(
+        resolve(deps.as_ref(), &format!("~{recipient}/renamed/child")).unwrap(),
+        Addr::unchecked("childaddr")
+    );
+}
+
+#[test]
+fn test_transfer_and_release_username() {
+    let mut deps = mock_dependencies_custom(&[]);
+    let username = "u1";
+    let sender = "sender";
+    let recipient = "recipient";
+    let info = mock_info(sender, &[]);
+    let env = mock_env();
+    instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+    let msg = ExecuteMsg::RegisterUser {
+        username: username.to_string(),
+        address: None,
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::RegisterUser {
+        username: "u2".to_string(),
+        address: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("other", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::TransferUsername {
+        new_owner: Addr::unchecked("other"),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUsername {
+            error: Some("Address already has a username".to_string())
+        }
+    );
+
+    let msg = ExecuteMsg::TransferUsername {
+        new_owner: Addr::unchecked(recipient),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        USERS.load(deps.as_ref().storage, username).unwrap(),
+        Addr::unchecked(recipient)
+    );
+    assert_eq!(
+        ADDRESS_USERNAME
+            .load(deps.as_ref().storage, recipient)
+            .unwrap(),
+        username
+    );
+    assert!(!ADDRESS_USERNAME.has(deps.as_ref().storage, sender));
+
+    // The previous owner no longer has a username to release
+    let msg = ExecuteMsg::ReleaseUsername {};
+    execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+
+    execute(deps.as_mut(), env, mock_info(recipient, &[]), msg).unwrap();
+    assert!(!USERS.has(deps.as_ref().storage, username));
+    assert!(!ADDRESS_USERNAME.has(deps.as_ref().storage, recipient));
+}
@@ -1,5 +1,6 @@
 use andromeda_std::{andr_exec, andr_instantiate, andr_query};
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_utils::Expiration;
 
 #[andr_instantiate]
 #[cw_serde]
@@ -11,11 +12,29 @@ pub struct InstantiateMsg {
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Add an address to the address list
-    AddAddress { address: String },
+    AddAddress {
+        address: String,
+        /// When the address stops being included in the address list
+        expiration: Option<Expiration>,
+        /// An arbitrary tag or role attached to the address
+        tag: Option<String>,
+    },
     /// Remove an address from the address list
     RemoveAddress { address: String },
-    /// Add multiple addresses to the address list
-    AddAddresses { addresses: Vec<String> },
+    /// Add multiple addresses to the address list, each with the given expiration and tag
+    AddAddresses {
+        addresses: Vec<String>,
+        expiration: Option<Expiration>,
+        tag: Option<String>,
+    },
+    /// Remove multiple addresses from the address list
+    RemoveAddresses { addresses: Vec<String> },
+    /// Register the hex encoded Merkle root of a list of addresses. Addresses in the list can add
+    /// themselves to the address list with `RegisterWithProof`. Removes the Merkle root if `None`.
+    /// Addresses that have already registered remain in the address list.
+    RegisterMerkleRoot { merkle_root: Option<String> },
+    /// Add the sender to the address list by proving that it is part of the registered Merkle root
+    RegisterWithProof { proof: Vec<String> },
 }
 
 #[andr_query]
@@ -27,6 +46,17 @@ pub enum QueryMsg {
     IncludesAddress { address: String },
     #[returns(bool)]
     IsInclusive {},
+    /// Query the entry of an address, including its expiration and tag
+    #[returns(AddressEntry)]
+    AddressInfo { address: String },
+    /// Paginated list of the entries in the address list, ordered by address
+    #[returns(AddressesResponse)]
+    Addresses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(MerkleRootResponse)]
+    MerkleRoot {},
 }
 
 #[cw_serde]
@@ -34,3 +64,30 @@ pub struct IncludesAddressResponse {
     /// Whether the address is included in the address list
     pub included: bool,
 }
+
+/// The metadata stored alongside an address in the address list.
+#[cw_serde]
+#[derive(Default)]
+pub struct AddressInfo {
+    pub expiration: Option<Expiration>,
+    pub tag: Option<String>,
+}
+
+#[cw_serde]
+pub struct AddressEntry {
+    pub address: String,
+    /// Whether the address is in the address list and has not expired
+    pub included: bool,
+    pub expiration: Option<Expiration>,
+    pub tag: Option<String>,
+}
+
+#[cw_serde]
+pub struct AddressesResponse {
+    pub addresses: Vec<AddressEntry>,
+}
+
+#[cw_serde]
+pub struct MerkleRootResponse {
+    pub merkle_root: Option<String>,
+}
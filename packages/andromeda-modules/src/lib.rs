@@ -1,3 +1,3 @@
 pub mod address_list;
 pub mod rates;
-// pub mod receipt;
+pub mod receipt;
@@ -1,8 +1,13 @@
+use andromeda_data_storage::primitive::{
+    GetValueResponse, Primitive, QueryMsg as PrimitiveQueryMsg,
+};
 use andromeda_std::{
-    amp::recipient::Recipient, andr_exec, andr_instantiate, andr_query, error::ContractError,
+    amp::{recipient::Recipient, AndrAddr},
+    andr_exec, andr_instantiate, andr_query,
+    error::ContractError,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{ensure, Coin, Decimal, Fraction, QuerierWrapper};
+use cosmwasm_std::{ensure, Coin, Decimal, Deps, Fraction, Uint128};
 
 #[andr_instantiate]
 #[cw_serde]
@@ -40,70 +45,140 @@ pub struct RateInfo {
 #[cw_serde]
 /// An enum used to define various types of fees
 pub enum Rate {
-    /// A flat rate fee
+    /// A flat rate fee. Fees charged in a CW20 use the CW20 contract address as the denom.
     Flat(Coin),
     /// A percentage fee
     Percent(PercentRate),
-    // External(PrimitivePointer),
+    /// A fee stored in a primitive contract, either a coin for a flat rate or a decimal for a
+    /// percentage
+    External(PrimitivePointer),
+    /// A percentage fee that depends on the amount paid
+    Tiered(TieredRate),
 }
 
 #[cw_serde] // This is added such that both Rate::Flat and Rate::Percent have the same level of nesting which
             // makes it easier to work with on the frontend.
 pub struct PercentRate {
     pub percent: Decimal,
+    /// The minimum fee, in the denom of the payment. The fee never exceeds the payment itself.
+    pub min: Option<Uint128>,
+    /// The maximum fee, in the denom of the payment.
+    pub max: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct TieredRate {
+    /// The brackets ordered by ascending threshold. A payment is charged the percentage of the
+    /// bracket with the highest threshold it reaches and nothing if it reaches none.
+    pub brackets: Vec<RateBracket>,
+    /// The minimum fee, in the denom of the payment. The fee never exceeds the payment itself.
+    pub min: Option<Uint128>,
+    /// The maximum fee, in the denom of the payment.
+    pub max: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct RateBracket {
+    /// The payment amount from which the bracket applies.
+    pub threshold: Uint128,
+    pub percent: Decimal,
+}
+
+#[cw_serde]
+pub struct PrimitivePointer {
+    /// The address of the primitive contract.
+    pub address: AndrAddr,
+    /// The key the rate is stored under, the default key if not provided.
+    pub key: Option<String>,
+}
+
+impl PrimitivePointer {
+    /// Queries the primitive contract for the value stored under the key.
+    pub fn into_value(self, deps: &Deps) -> Result<Primitive, ContractError> {
+        let address = self.address.get_raw_address(deps)?;
+        let response: GetValueResponse = deps
+            .querier
+            .query_wasm_smart(address, &PrimitiveQueryMsg::GetValue { key: self.key })?;
+        Ok(response.value)
+    }
 }
 
 impl From<Decimal> for Rate {
     fn from(decimal: Decimal) -> Self {
-        Rate::Percent(PercentRate { percent: decimal })
+        Rate::Percent(PercentRate {
+            percent: decimal,
+            min: None,
+            max: None,
+        })
     }
 }
 
+/// Ensures the minimum fee is not greater than the maximum.
+fn validate_caps(min: Option<Uint128>, max: Option<Uint128>) -> Result<(), ContractError> {
+    if let (Some(min), Some(max)) = (min, max) {
+        ensure!(min <= max, ContractError::InvalidRate {});
+    }
+    Ok(())
+}
+
 impl Rate {
     /// Validates that a given rate is non-zero. It is expected that the Rate is not an
     /// External Rate.
     pub fn is_non_zero(&self) -> Result<bool, ContractError> {
         match self {
             Rate::Flat(coin) => Ok(!coin.amount.is_zero()),
-            Rate::Percent(PercentRate { percent }) => Ok(!percent.is_zero()),
-            // Rate::External(_) => Err(ContractError::UnexpectedExternalRate {}),
+            Rate::Percent(PercentRate { percent, .. }) => Ok(!percent.is_zero()),
+            Rate::Tiered(TieredRate { brackets, .. }) => {
+                Ok(brackets.iter().any(|bracket| !bracket.percent.is_zero()))
+            }
+            Rate::External(_) => Err(ContractError::UnexpectedExternalRate {}),
         }
     }
 
     /// Validates `self` and returns an "unwrapped" version of itself wherein if it is an External
     /// Rate, the actual rate value is retrieved from the Primitive Contract.
-    pub fn validate(&self, querier: &QuerierWrapper) -> Result<Rate, ContractError> {
-        let rate = self.clone().get_rate(querier)?;
+    pub fn validate(&self, deps: &Deps) -> Result<Rate, ContractError> {
+        let rate = self.clone().get_rate(deps)?;
         ensure!(rate.is_non_zero()?, ContractError::InvalidRate {});
 
-        if let Rate::Percent(PercentRate { percent }) = rate {
-            ensure!(percent <= Decimal::one(), ContractError::InvalidRate {});
+        match &rate {
+            Rate::Percent(PercentRate { percent, min, max }) => {
+                ensure!(*percent <= Decimal::one(), ContractError::InvalidRate {});
+                validate_caps(*min, *max)?;
+            }
+            Rate::Tiered(TieredRate { brackets, min, max }) => {
+                ensure!(
+                    brackets
+                        .windows(2)
+                        .all(|pair| pair[0].threshold < pair[1].threshold),
+                    ContractError::InvalidRate {}
+                );
+                ensure!(
+                    brackets
+                        .iter()
+                        .all(|bracket| bracket.percent <= Decimal::one()),
+                    ContractError::InvalidRate {}
+                );
+                validate_caps(*min, *max)?;
+            }
+            _ => {}
         }
 
         Ok(rate)
     }
 
-    /// If `self` is Flat or Percent it returns itself. Otherwise it queries the primitive contract
+    /// If `self` is not External it returns itself. Otherwise it queries the primitive contract
     /// and retrieves the actual Flat or Percent rate.
-    fn get_rate(self, _querier: &QuerierWrapper) -> Result<Rate, ContractError> {
+    fn get_rate(self, deps: &Deps) -> Result<Rate, ContractError> {
         match self {
-            Rate::Flat(_) => Ok(self),
-            Rate::Percent(_) => Ok(self),
-            // Rate::External(primitive_pointer) => {
-            //     let primitive = primitive_pointer.into_value(querier)?;
-            //     match primitive {
-            //         None => Err(ContractError::ParsingError {
-            //             err: "Stored primitive is None".to_string(),
-            //         }),
-            //         Some(primitive) => match primitive {
-            //             Primitive::Coin(coin) => Ok(Rate::Flat(coin)),
-            //             Primitive::Decimal(value) => Ok(Rate::from(value)),
-            //             _ => Err(ContractError::ParsingError {
-            //                 err: "Stored rate is not a coin or Decimal".to_string(),
-            //             }),
-            //         },
-            //     }
-            // }
+            Rate::External(primitive_pointer) => match primitive_pointer.into_value(deps)? {
+                Primitive::Coin(coin) => Ok(Rate::Flat(coin)),
+                Primitive::Decimal(value) => Ok(Rate::from(value)),
+                _ => Err(ContractError::ParsingError {
+                    err: "Stored rate is not a coin or Decimal".to_string(),
+                }),
+            },
+            _ => Ok(self),
         }
     }
 }
@@ -132,25 +207,61 @@ impl ToString for PaymentAttribute {
 pub fn calculate_fee(fee_rate: Rate, payment: &Coin) -> Result<Coin, ContractError> {
     match fee_rate {
         Rate::Flat(rate) => Ok(Coin::new(rate.amount.u128(), rate.denom)),
-        Rate::Percent(PercentRate { percent }) => {
-            // [COM-03] Make sure that fee_rate between 0 and 100.
-            ensure!(
-                // No need for rate >=0 due to type limits (Question: Should add or remove?)
-                percent <= Decimal::one() && !percent.is_zero(),
-                ContractError::InvalidRate {}
-            );
-            let mut fee_amount = payment.amount * percent;
-
-            // Always round any remainder up and prioritise the fee receiver.
-            // Inverse of percent will always exist.
-            let reversed_fee = fee_amount * percent.inv().unwrap();
-            if payment.amount > reversed_fee {
-                // [COM-1] Added checked add to fee_amount rather than direct increment
-                fee_amount = fee_amount.checked_add(1u128.into())?;
-            }
+        Rate::Percent(PercentRate { percent, min, max }) => {
+            let fee_amount = calculate_percent_fee(percent, payment.amount)?;
+            let fee_amount = apply_caps(fee_amount, min, max, payment.amount);
             Ok(Coin::new(fee_amount.u128(), payment.denom.clone()))
-        } // Rate::External(_) => Err(ContractError::UnexpectedExternalRate {}),
+        }
+        Rate::Tiered(TieredRate { brackets, min, max }) => {
+            let bracket = brackets
+                .iter()
+                .rev()
+                .find(|bracket| bracket.threshold <= payment.amount);
+            // Payments below the lowest bracket are not charged, regardless of the caps
+            let Some(bracket) = bracket else {
+                return Ok(Coin::new(0, payment.denom.clone()));
+            };
+            let fee_amount = if bracket.percent.is_zero() {
+                Uint128::zero()
+            } else {
+                calculate_percent_fee(bracket.percent, payment.amount)?
+            };
+            let fee_amount = apply_caps(fee_amount, min, max, payment.amount);
+            Ok(Coin::new(fee_amount.u128(), payment.denom.clone()))
+        }
+        Rate::External(_) => Err(ContractError::UnexpectedExternalRate {}),
+    }
+}
+
+fn calculate_percent_fee(percent: Decimal, amount: Uint128) -> Result<Uint128, ContractError> {
+    // [COM-03] Make sure that fee_rate between 0 and 100.
+    ensure!(
+        // No need for rate >=0 due to type limits (Question: Should add or remove?)
+        percent <= Decimal::one() && !percent.is_zero(),
+        ContractError::InvalidRate {}
+    );
+    let mut fee_amount = amount * percent;
+
+    // Always round any remainder up and prioritise the fee receiver.
+    // Inverse of percent will always exist.
+    let reversed_fee = fee_amount * percent.inv().unwrap();
+    if amount > reversed_fee {
+        // [COM-1] Added checked add to fee_amount rather than direct increment
+        fee_amount = fee_amount.checked_add(1u128.into())?;
     }
+    Ok(fee_amount)
+}
+
+/// Keeps the fee between `min` and `max`, without exceeding the payment.
+fn apply_caps(
+    fee_amount: Uint128,
+    min: Option<Uint128>,
+    max: Option<Uint128>,
+    payment: Uint128,
+) -> Uint128 {
+    let fee_amount = min.map_or(fee_amount, |min| fee_amount.max(min));
+    let fee_amount = max.map_or(fee_amount, |max| fee_amount.min(max));
+    fee_amount.min(payment)
 }
 
 #[cfg(test)]
@@ -160,28 +271,6 @@ mod tests {
 
     use super::*;
 
-    // #[test]
-    // fn test_validate_external_rate() {
-    //     let deps = mock_dependencies_custom(&[]);
-
-    //     let rate = Rate::External(PrimitivePointer {
-    //         address: MOCK_PRIMITIVE_CONTRACT.to_owned(),
-
-    //         key: Some("percent".to_string()),
-    //     });
-    //     let validated_rate = rate.validate(&deps.as_ref().querier).unwrap();
-    //     let expected_rate = Rate::from(Decimal::percent(1));
-    //     assert_eq!(expected_rate, validated_rate);
-
-    //     let rate = Rate::External(PrimitivePointer {
-    //         address: MOCK_PRIMITIVE_CONTRACT.to_owned(),
-    //         key: Some("flat".to_string()),
-    //     });
-    //     let validated_rate = rate.validate(&deps.as_ref().querier).unwrap();
-    //     let expected_rate = Rate::Flat(coin(1u128, "uusd"));
-    //     assert_eq!(expected_rate, validated_rate);
-    // }
-
     #[test]
     fn test_calculate_fee() {
         let payment = coin(101, "uluna");
@@ -204,4 +293,72 @@ mod tests {
 
         assert_eq!(expected, received);
     }
+
+    #[test]
+    fn test_calculate_fee_capped() {
+        let fee = Rate::Percent(PercentRate {
+            percent: Decimal::percent(10),
+            min: Some(Uint128::new(5)),
+            max: Some(Uint128::new(20)),
+        });
+
+        let received = calculate_fee(fee.clone(), &coin(100, "uluna"));
+        assert_eq!(Ok(coin(10, "uluna")), received);
+
+        let received = calculate_fee(fee.clone(), &coin(10, "uluna"));
+        assert_eq!(Ok(coin(5, "uluna")), received);
+
+        let received = calculate_fee(fee.clone(), &coin(1000, "uluna"));
+        assert_eq!(Ok(coin(20, "uluna")), received);
+
+        // The minimum fee does not exceed the payment.
+        let received = calculate_fee(fee, &coin(3, "uluna"));
+        assert_eq!(Ok(coin(3, "uluna")), received);
+    }
+
+    #[test]
+    fn test_calculate_fee_tiered() {
+        let fee = Rate::Tiered(TieredRate {
+            brackets: vec![
+                RateBracket {
+                    threshold: Uint128::new(100),
+                    percent: Decimal::percent(5),
+                },
+                RateBracket {
+                    threshold: Uint128::new(1000),
+                    percent: Decimal::percent(2),
+                },
+            ],
+            min: None,
+            max: Some(Uint128::new(30)),
+        });
+
+        let received = calculate_fee(fee.clone(), &coin(50, "uluna"));
+        assert_eq!(Ok(coin(0, "uluna")), received);
+
+        let received = calculate_fee(fee.clone(), &coin(500, "uluna"));
+        assert_eq!(Ok(coin(25, "uluna")), received);
+
+        let received = calculate_fee(fee.clone(), &coin(1000, "uluna"));
+        assert_eq!(Ok(coin(20, "uluna")), received);
+
+        let received = calculate_fee(fee, &coin(5000, "uluna"));
+        assert_eq!(Ok(coin(30, "uluna")), received);
+
+        let fee = Rate::Tiered(TieredRate {
+            brackets: vec![RateBracket {
+                threshold: Uint128::new(100),
+                percent: Decimal::percent(5),
+            }],
+            min: Some(Uint128::new(10)),
+            max: None,
+        });
+
+        // Below the lowest bracket nothing is charged, not even the minimum
+        let received = calculate_fee(fee.clone(), &coin(50, "uluna"));
+        assert_eq!(Ok(coin(0, "uluna")), received);
+
+        let received = calculate_fee(fee, &coin(100, "uluna"));
+        assert_eq!(Ok(coin(10, "uluna")), received);
+    }
 }
@@ -0,0 +1,68 @@
+use andromeda_std::{amp::AndrAddr, andr_exec, andr_instantiate, andr_query, common::Funds};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Event, Timestamp, Uint64};
+
+#[andr_instantiate]
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The ADOs allowed to store receipts, usually the ADOs the module is registered with.
+    /// Further hosts can be authorised through the `STORE_RECEIPT` action permission.
+    pub authorized_hosts: Option<Vec<AndrAddr>>,
+}
+
+#[andr_exec]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Stores a receipt for the sending ADO. Sent by the host ADO when it dispatches the messages
+    /// returned by the module hooks.
+    StoreReceipt {
+        sender: String,
+        amount: Option<Funds>,
+        events: Vec<Event>,
+    },
+}
+
+#[andr_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ReceiptResponse)]
+    Receipt { receipt_id: Uint64 },
+    /// Paginated list of all receipts, ordered by id
+    #[returns(ReceiptsResponse)]
+    Receipts {
+        start_after: Option<Uint64>,
+        limit: Option<u32>,
+    },
+    /// Paginated list of the receipts of the given sender, ordered by id
+    #[returns(ReceiptsResponse)]
+    ReceiptsBySender {
+        sender: String,
+        start_after: Option<Uint64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct Receipt {
+    pub id: Uint64,
+    /// The ADO that stored the receipt
+    pub host: Addr,
+    /// The sender of the message that was executed on the host
+    pub sender: String,
+    /// The funds sent to the host, if any
+    pub amount: Option<Funds>,
+    pub events: Vec<Event>,
+    pub block_height: u64,
+    pub block_time: Timestamp,
+}
+
+#[cw_serde]
+pub struct ReceiptResponse {
+    pub receipt: Receipt,
+}
+
+#[cw_serde]
+pub struct ReceiptsResponse {
+    pub receipts: Vec<Receipt>,
+}
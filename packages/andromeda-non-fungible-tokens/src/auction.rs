@@ -4,6 +4,7 @@ use andromeda_std::{andr_exec, andr_instantiate, andr_instantiate_modules, andr_
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
 use cw721::{Cw721ReceiveMsg, Expiration};
 
 #[andr_instantiate]
@@ -17,6 +18,8 @@ pub struct InstantiateMsg {
 #[cw_serde]
 pub enum ExecuteMsg {
     ReceiveNft(Cw721ReceiveMsg),
+    /// Places a bid with CW20 tokens when the auction is in a CW20.
+    Receive(Cw20ReceiveMsg),
     /// Places a bid on the current auction for the given token_id. The previous largest bid gets
     /// automatically sent back to the bidder when they are outbid.
     PlaceBid {
@@ -34,6 +37,8 @@ pub enum ExecuteMsg {
         start_time: Option<Milliseconds>,
         end_time: Milliseconds,
         coin_denom: String,
+        /// Whether `coin_denom` is the address of a CW20 token. Defaults to false.
+        uses_cw20: Option<bool>,
         whitelist: Option<Vec<Addr>>,
         min_bid: Option<Uint128>,
         recipient: Option<Recipient>,
@@ -63,11 +68,22 @@ pub enum Cw721HookMsg {
         /// Duration in milliseconds
         end_time: Milliseconds,
         coin_denom: String,
+        /// Whether `coin_denom` is the address of a CW20 token. Defaults to false.
+        uses_cw20: Option<bool>,
         min_bid: Option<Uint128>,
         whitelist: Option<Vec<Addr>>,
         recipient: Option<Recipient>,
     },
 }
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Places a bid with the sent CW20 tokens. Works the same as `ExecuteMsg::PlaceBid`.
+    PlaceBid {
+        token_id: String,
+        token_address: String,
+    },
+}
 #[andr_query]
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -158,6 +174,7 @@ impl From<TokenAuctionState> for AuctionStateResponse {
             high_bidder_addr: token_auction_state.high_bidder_addr.to_string(),
             high_bidder_amount: token_auction_state.high_bidder_amount,
             coin_denom: token_auction_state.coin_denom,
+            uses_cw20: token_auction_state.uses_cw20,
             auction_id: token_auction_state.auction_id,
             whitelist: token_auction_state.whitelist,
             is_cancelled: token_auction_state.is_cancelled,
@@ -174,6 +191,8 @@ pub struct TokenAuctionState {
     pub high_bidder_addr: Addr,
     pub high_bidder_amount: Uint128,
     pub coin_denom: String,
+    #[serde(default)]
+    pub uses_cw20: bool,
     pub auction_id: Uint128,
     pub whitelist: Option<Vec<Addr>>,
     pub min_bid: Option<Uint128>,
@@ -199,6 +218,7 @@ pub struct AuctionStateResponse {
     pub high_bidder_amount: Uint128,
     pub auction_id: Uint128,
     pub coin_denom: String,
+    pub uses_cw20: bool,
     pub whitelist: Option<Vec<Addr>>,
     pub min_bid: Option<Uint128>,
     pub is_cancelled: bool,
@@ -1,9 +1,11 @@
 use crate::cw721::TokenExtension;
 use andromeda_std::amp::{addresses::AndrAddr, recipient::Recipient};
-use andromeda_std::common::Milliseconds;
+use andromeda_std::common::{Funds, Milliseconds};
+use andromeda_std::error::ContractError;
 use andromeda_std::{andr_exec, andr_instantiate, andr_instantiate_modules, andr_query};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Coin, Uint128};
+use cosmwasm_std::{ensure, Coin, Decimal, Uint128, Uint64};
+use cw20::Cw20ReceiveMsg;
 use cw721::Expiration;
 
 #[andr_instantiate]
@@ -16,36 +18,218 @@ pub struct InstantiateMsg {
 
 #[andr_exec]
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Mints a new token to be sold in a future sale. Only possible when the sale is not ongoing.
     Mint(Vec<CrowdfundMintMsg>),
+    /// Sets the range of tokens minted to the purchasers once a sale succeeds, in place of the
+    /// pre-minted tokens, or removes it if `None`. Only accessible by owner while no sale is
+    /// ongoing and no pre-minted tokens are available.
+    SetLazyMint { lazy_mint: Option<LazyMint> },
     /// Starts the sale if one is not already ongoing.
     StartSale {
         /// When the sale start. Defaults to current time.
         start_time: Option<Milliseconds>,
         /// When the sale ends.
         end_time: Milliseconds,
-        /// The price per token.
-        price: Coin,
-        /// The minimum amount of tokens sold to go through with the sale.
-        min_tokens_sold: Uint128,
+        /// The accepted prices per token, one per native denom or CW20 token. The first price is
+        /// the base price that the caps and tier prices are denominated in, the others are
+        /// considered equivalent to it.
+        prices: Vec<Funds>,
+        /// How the prices change over the course of the sale, they are fixed if not provided.
+        pricing: Option<Pricing>,
+        /// The minimum amount that has to be raised to go through with the sale, in the base denom.
+        /// For CW20 tokens the denom is the token address.
+        soft_cap: Coin,
+        /// The maximum amount that can be raised, in the base denom. Purchases going over it are
+        /// truncated and the excess is refunded. Unlimited if not provided.
+        hard_cap: Option<Coin>,
+        /// The share of the volume they referred paid to each referrer out of the raised funds if
+        /// the sale succeeds. No referral rewards are paid if not provided.
+        referral_rate: Option<Decimal>,
         /// The amount of tokens a wallet can purchase, default is 1.
         max_amount_per_wallet: Option<u32>,
         /// The recipient of the funds if the sale met the minimum sold.
         recipient: Recipient,
+        /// An optional presale window before `start_time` in which only whitelisted addresses can
+        /// purchase tokens.
+        presale: Option<PresaleMsg>,
+        /// If provided, the raised funds are held in escrow once the sale succeeds and released to
+        /// the recipient of each milestone as it is reached, instead of being sent to `recipient`.
+        milestones: Option<Vec<MilestoneMsg>>,
     },
     /// Updates the token address to a new one.
     /// Only accessible by owner
     UpdateTokenContract { address: AndrAddr },
-    /// Puchases tokens in an ongoing sale.
-    Purchase { number_of_tokens: Option<u32> },
+    /// Adds a reward tier. Only accessible by owner while no sale is ongoing.
+    AddTier { tier: Tier },
+    /// Replaces an existing reward tier. Only accessible by owner while no sale is ongoing.
+    UpdateTier { tier: Tier },
+    /// Removes a reward tier that has not sold any tokens. Only accessible by owner while no sale
+    /// is ongoing.
+    RemoveTier { tier_id: Uint64 },
+    /// Puchases tokens in an ongoing sale. If `tier_id` is provided the tokens are purchased from
+    /// the given tier and minted to the purchaser once the sale succeeds, otherwise they are taken
+    /// from the pre-minted tokens.
+    Purchase {
+        number_of_tokens: Option<u32>,
+        tier_id: Option<Uint64>,
+        /// Where refunds are sent if the sale fails, defaults to the purchaser. Can be a VFS path
+        /// or a cross-chain address but cannot have a message, the latest one provided is used.
+        refund_recipient: Option<Recipient>,
+        /// The address that referred the purchaser, credited with the purchase.
+        referrer: Option<String>,
+    },
     /// Purchases the token with the given id.
-    PurchaseByTokenId { token_id: String },
-    /// Allow a user to claim their own refund if the minimum number of tokens are not sold.
-    ClaimRefund {},
+    PurchaseByTokenId {
+        token_id: String,
+        refund_recipient: Option<Recipient>,
+        referrer: Option<String>,
+    },
+    /// Purchases tokens with CW20 tokens when the sale is priced in a CW20.
+    Receive(Cw20ReceiveMsg),
+    /// Allow a user to claim their own refund if the minimum number of tokens are not sold, or
+    /// their share of the escrow once its milestones are cancelled.
+    ClaimRefund {
+        /// Claims the escrow refund of a previous campaign, whose refunds had not all been claimed
+        /// when the next sale started.
+        campaign_id: Option<Uint64>,
+    },
+    /// Claims the refunds that could not be delivered to the refund recipient chosen by the
+    /// purchaser. They are sent to the purchaser directly.
+    ClaimFailedRefund {},
     /// Ends the ongoing sale by completing `limit` number of operations depending on if the minimum number
     /// of tokens was sold.
     EndSale { limit: Option<u32> },
+    /// Cancels the ongoing sale, marking it as failed right away. All purchases, including their
+    /// taxes, are then refunded through `EndSale` or `ClaimRefund`. Unsold tokens are burned
+    /// unless `return_unsold_to` is provided, in which case they are transferred to it.
+    /// Only accessible by owner before any tokens have been transferred to purchasers.
+    CancelSale { return_unsold_to: Option<AndrAddr> },
+    /// Releases the escrowed funds of a milestone to its recipient. The owner can release a
+    /// milestone at any time to attest it was reached, anyone can release it once its deadline has
+    /// passed.
+    ReleaseMilestone { milestone_id: Uint64 },
+    /// Cancels all milestones that have not been released. The funds left in escrow can then be
+    /// claimed by the purchasers through `ClaimRefund`, pro rata to what they paid.
+    /// Only accessible by owner.
+    CancelMilestones {},
+}
+
+#[cw_serde]
+pub struct PresaleMsg {
+    /// When the presale starts, has to be before the start of the sale. Defaults to current time.
+    pub start_time: Option<Milliseconds>,
+    /// The addresses that can purchase tokens during the presale.
+    pub whitelist: Whitelist,
+    /// The accepted prices during the presale, defaults to the sale prices. The base denom has to
+    /// be the same as that of the sale. Tier prices are not affected.
+    pub prices: Option<Vec<Funds>>,
+    /// The amount of tokens a wallet can purchase during the presale, defaults to the sale's
+    /// `max_amount_per_wallet`.
+    pub max_amount_per_wallet: Option<u32>,
+}
+
+#[cw_serde]
+pub enum Whitelist {
+    /// Only the given addresses are whitelisted.
+    Addresses(Vec<String>),
+    /// The addresses permitted by the given `andromeda-address-list` ADO are whitelisted.
+    AddressList(AndrAddr),
+}
+
+/// Dynamic pricing of a sale. The base price changes as described below and the other accepted
+/// prices change in proportion to it. Presale and tier prices are not affected.
+#[cw_serde]
+pub enum Pricing {
+    /// The price decreases linearly from the base price when the sale starts to `floor_price` when
+    /// it ends, as in a Dutch auction.
+    LinearDecay { floor_price: Uint128 },
+    /// The price increases by `increment` every `step` tokens sold, starting at the base price.
+    /// Purchases that would go over a step are truncated and the excess is refunded.
+    Step { step: Uint128, increment: Uint128 },
+}
+
+#[cw_serde]
+pub struct Presale {
+    /// The expiration denoting when the presale starts.
+    pub start_time: Expiration,
+    /// The addresses that can purchase tokens during the presale.
+    pub whitelist: Whitelist,
+    /// The accepted prices of each token during the presale.
+    pub prices: Vec<Funds>,
+    /// The max number of tokens allowed per wallet during the presale.
+    pub max_amount_per_wallet: u32,
+}
+
+#[cw_serde]
+pub struct MilestoneMsg {
+    /// What has to be achieved for the milestone to be released.
+    pub description: String,
+    /// The share of the escrowed funds released for the milestone. The shares of all milestones
+    /// have to add up to one.
+    pub percent: Decimal,
+    /// The recipient of the milestone's funds, defaults to the recipient of the sale.
+    pub recipient: Option<Recipient>,
+    /// When anyone can release the milestone without the owner attesting to it, has to be after
+    /// the end of the sale.
+    pub deadline: Option<Milliseconds>,
+}
+
+#[cw_serde]
+pub enum MilestoneStatus {
+    Pending,
+    Released,
+    Cancelled,
+}
+
+#[cw_serde]
+pub struct Milestone {
+    /// The id of the milestone, starting from 1 in the order they were provided.
+    pub id: Uint64,
+    /// What has to be achieved for the milestone to be released.
+    pub description: String,
+    /// The share of the escrowed funds released for the milestone.
+    pub percent: Decimal,
+    /// The recipient of the milestone's funds.
+    pub recipient: Recipient,
+    /// The expiration after which anyone can release the milestone.
+    pub deadline: Option<Expiration>,
+    pub status: MilestoneStatus,
+    /// The funds that were released for the milestone.
+    pub released: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct Escrow {
+    /// The campaign the funds were raised in.
+    pub campaign_id: Uint64,
+    /// The funds of the sale held for the milestones per denom. CW20 tokens are denoted by their
+    /// address.
+    pub total: Vec<Coin>,
+    /// The funds released to the milestone recipients so far.
+    pub released: Vec<Coin>,
+    /// The amount paid by all purchasers per denom, excluding taxes.
+    pub contributions: Vec<Coin>,
+    /// The funds that were not released when the milestones were cancelled. They are refunded to
+    /// the purchasers pro rata to their contributions.
+    pub refundable: Vec<Coin>,
+    /// The accepted prices of the sale, used to determine how each denom is sent.
+    pub prices: Vec<Funds>,
+    /// Whether the milestones were cancelled.
+    pub cancelled: bool,
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Puchases tokens in an ongoing sale priced in the sent CW20 token. Works the same as
+    /// `ExecuteMsg::Purchase`.
+    Purchase {
+        number_of_tokens: Option<u32>,
+        tier_id: Option<Uint64>,
+        refund_recipient: Option<Recipient>,
+        referrer: Option<String>,
+    },
 }
 
 #[andr_query]
@@ -63,6 +247,73 @@ pub enum QueryMsg {
     },
     #[returns(bool)]
     IsTokenAvailable { id: String },
+    /// The lazily minted token range, if any.
+    #[returns(Option<LazyMintResponse>)]
+    LazyMint {},
+    #[returns(TierResponse)]
+    Tier { tier_id: Uint64 },
+    #[returns(Vec<TierResponse>)]
+    Tiers {
+        start_after: Option<Uint64>,
+        limit: Option<u32>,
+    },
+    #[returns(Milestone)]
+    Milestone { milestone_id: Uint64 },
+    #[returns(Vec<Milestone>)]
+    Milestones {
+        start_after: Option<Uint64>,
+        limit: Option<u32>,
+    },
+    /// The purchases of the given address in a campaign, defaults to the latest campaign.
+    #[returns(Vec<PurchaseResponse>)]
+    Purchases {
+        purchaser: String,
+        campaign_id: Option<Uint64>,
+        /// The token id to start after.
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The contributors of a campaign ordered by address, defaults to the latest campaign.
+    #[returns(Vec<Contributor>)]
+    Contributors {
+        campaign_id: Option<Uint64>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The contributors of a campaign who contributed the most in the base denom, defaults to the
+    /// latest campaign.
+    #[returns(Vec<Contributor>)]
+    TopContributors {
+        campaign_id: Option<Uint64>,
+        limit: Option<u32>,
+    },
+    /// The referrers of a campaign ordered by address, defaults to the latest campaign.
+    #[returns(Vec<Referral>)]
+    Referrals {
+        campaign_id: Option<Uint64>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(SummaryResponse)]
+    Summary {},
+    /// The accepted prices of each token at the current block, the first one being the base price.
+    #[returns(Vec<Funds>)]
+    CurrentPrice {},
+    /// The campaign with the given id, defaults to the latest campaign.
+    #[returns(Campaign)]
+    Campaign { campaign_id: Option<Uint64> },
+    #[returns(Vec<Campaign>)]
+    Campaigns {
+        start_after: Option<Uint64>,
+        limit: Option<u32>,
+    },
+    /// The escrowed funds of the last successful sale with milestones, if any. Previous campaigns
+    /// are only returned while some of the refunds of their cancelled milestones are unclaimed.
+    #[returns(Option<Escrow>)]
+    Escrow { campaign_id: Option<Uint64> },
+    /// The refunds of the purchaser that could not be delivered to their refund recipient.
+    #[returns(Vec<Funds>)]
+    FailedRefunds { purchaser: String },
 }
 
 #[cw_serde]
@@ -75,23 +326,71 @@ pub struct Config {
 
 #[cw_serde]
 pub struct State {
+    /// The id of the campaign, incremented with every sale.
+    pub campaign_id: Uint64,
+    /// The expiration denoting when the sale starts.
+    pub start_time: Expiration,
     /// The expiration denoting when the sale ends.
     pub end_time: Expiration,
-    /// The price of each token.
-    pub price: Coin,
-    /// The minimum number of tokens sold for the sale to go through.
-    pub min_tokens_sold: Uint128,
+    /// The accepted prices of each token, the first one being the base price.
+    pub prices: Vec<Funds>,
+    /// How the prices change over the course of the sale, they are fixed if not set.
+    pub pricing: Option<Pricing>,
+    /// The minimum amount raised for the sale to go through.
+    pub soft_cap: Coin,
+    /// The maximum amount that can be raised.
+    pub hard_cap: Option<Coin>,
+    /// The share of the referred volume paid to referrers if the sale succeeds.
+    pub referral_rate: Option<Decimal>,
     /// The max number of tokens allowed per wallet.
     pub max_amount_per_wallet: u32,
     /// Number of tokens sold.
     pub amount_sold: Uint128,
-    /// The amount of funds to send to recipient if sale successful. This already
+    /// The total amount raised by the sale valued in the base denom, excluding taxes.
+    pub amount_raised: Uint128,
+    /// The amount raised in each accepted denom, excluding taxes. CW20 tokens are denoted by their
+    /// address.
+    pub amounts_raised: Vec<Coin>,
+    /// The taxes paid by purchasers in each accepted denom.
+    pub taxes_paid: Vec<Coin>,
+    /// The amount of funds to send to recipient per denom if sale successful. This already
     /// takes into account the royalties and taxes.
-    pub amount_to_send: Uint128,
+    pub amount_to_send: Vec<Coin>,
     /// Number of tokens transferred to purchasers if sale was successful.
     pub amount_transferred: Uint128,
     /// The recipient of the raised funds if the sale is successful.
     pub recipient: Recipient,
+    /// The presale preceding the sale, if any.
+    pub presale: Option<Presale>,
+    /// Set if the sale was cancelled by the owner.
+    pub cancellation: Option<Cancellation>,
+}
+
+#[cw_serde]
+pub enum CampaignOutcome {
+    Ongoing,
+    /// The soft cap was reached and the tokens were transferred to the purchasers.
+    Succeeded,
+    /// The soft cap was not reached and the purchasers were refunded.
+    Failed,
+    /// The sale was cancelled by the owner and the purchasers were refunded.
+    Cancelled,
+}
+
+#[cw_serde]
+pub struct Campaign {
+    pub id: Uint64,
+    /// The state of the sale, as it was when the sale ended for past campaigns.
+    pub state: State,
+    pub outcome: CampaignOutcome,
+}
+
+#[cw_serde]
+pub struct Cancellation {
+    /// When the sale was cancelled.
+    pub cancelled_at: Milliseconds,
+    /// The address unsold tokens are returned to, they are burned if not provided.
+    pub return_unsold_to: Option<AndrAddr>,
 }
 
 #[cw_serde]
@@ -107,3 +406,202 @@ pub struct CrowdfundMintMsg {
     /// Any custom extension used by this contract
     pub extension: TokenExtension,
 }
+
+/// A range of tokens that are only minted once purchased in a successful sale.
+#[cw_serde]
+pub struct LazyMint {
+    /// The id of each token, where `{id}` is replaced by the number of the token, e.g.
+    /// "edition-{id}".
+    pub token_id_template: String,
+    /// The number of the first token.
+    pub first_id: Uint128,
+    /// The number of the last token.
+    pub last_id: Uint128,
+    /// The token uri of each token, where every `{id}` is replaced by the number of the token.
+    pub token_uri_template: Option<String>,
+    /// The extension given to every token.
+    pub extension: TokenExtension,
+}
+
+impl LazyMint {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(
+            self.token_id_template
+                .matches(LAZY_MINT_ID_PLACEHOLDER)
+                .count()
+                == 1,
+            ContractError::InvalidLazyMint {
+                msg: format!("Token id template must contain {LAZY_MINT_ID_PLACEHOLDER} once"),
+            }
+        );
+        ensure!(
+            self.token_uri_template
+                .as_ref()
+                .map_or(true, |uri| uri.contains(LAZY_MINT_ID_PLACEHOLDER)),
+            ContractError::InvalidLazyMint {
+                msg: format!("Token uri template must contain {LAZY_MINT_ID_PLACEHOLDER}"),
+            }
+        );
+        ensure!(
+            self.first_id <= self.last_id,
+            ContractError::InvalidLazyMint {
+                msg: "First id cannot be greater than the last id".to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    /// The total number of tokens in the range.
+    pub fn supply(&self) -> Uint128 {
+        self.last_id - self.first_id + Uint128::one()
+    }
+
+    pub fn token_id(&self, number: Uint128) -> String {
+        self.token_id_template
+            .replace(LAZY_MINT_ID_PLACEHOLDER, &number.to_string())
+    }
+
+    pub fn token_uri(&self, number: Uint128) -> Option<String> {
+        self.token_uri_template
+            .as_ref()
+            .map(|uri| uri.replace(LAZY_MINT_ID_PLACEHOLDER, &number.to_string()))
+    }
+
+    /// Returns the number of the token with the given id, if it is part of the range.
+    pub fn token_number(&self, token_id: &str) -> Option<Uint128> {
+        let (prefix, suffix) = self
+            .token_id_template
+            .split_once(LAZY_MINT_ID_PLACEHOLDER)?;
+        let number: Uint128 = token_id
+            .strip_prefix(prefix)?
+            .strip_suffix(suffix)?
+            .parse()
+            .ok()?;
+        (self.first_id <= number && number <= self.last_id).then_some(number)
+    }
+}
+
+pub const LAZY_MINT_ID_PLACEHOLDER: &str = "{id}";
+
+#[cw_serde]
+pub struct LazyMintResponse {
+    pub lazy_mint: LazyMint,
+    /// The number of tokens sold from the range.
+    pub sold_amount: Uint128,
+    /// The number of tokens left in the range.
+    pub remaining: Uint128,
+}
+
+#[cw_serde]
+pub struct Tier {
+    /// Unique ID of the tier.
+    pub id: Uint64,
+    /// Human readable name of the tier, e.g. "Gold".
+    pub label: String,
+    /// The price of a single token of this tier, denominated in the base denom. Prices in other
+    /// accepted denoms are derived from the sale prices.
+    pub price: Uint128,
+    /// The maximum number of tokens that can be sold for this tier. Unlimited if not provided.
+    pub limit: Option<Uint128>,
+    /// The metadata given to every token minted for this tier.
+    pub metadata: TierMetaData,
+}
+
+impl Tier {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(
+            !self.label.is_empty(),
+            ContractError::InvalidTier {
+                operation: "validate".to_string(),
+                msg: "Tier label cannot be empty".to_string(),
+            }
+        );
+        ensure!(
+            !self.price.is_zero(),
+            ContractError::InvalidTier {
+                operation: "validate".to_string(),
+                msg: "Tier price must be greater than zero".to_string(),
+            }
+        );
+        ensure!(
+            self.limit.map_or(true, |limit| !limit.is_zero()),
+            ContractError::InvalidTier {
+                operation: "validate".to_string(),
+                msg: "Tier limit must be greater than zero".to_string(),
+            }
+        );
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct TierMetaData {
+    /// Universal resource identifier for the tier's tokens.
+    pub token_uri: Option<String>,
+    /// The extension used for the tier's tokens.
+    pub extension: TokenExtension,
+}
+
+#[cw_serde]
+pub struct PurchaseResponse {
+    /// The token id being purchased.
+    pub token_id: String,
+    /// The tier the token was purchased from, `None` if it is a pre-minted token.
+    pub tier_id: Option<Uint64>,
+    /// The denom the token was paid for in, CW20 tokens are denoted by their address.
+    pub denom: String,
+    /// The price paid for the token, excluding taxes.
+    pub price: Uint128,
+    /// Amount of tax paid.
+    pub tax_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct Contributor {
+    pub purchaser: String,
+    /// The number of tokens purchased.
+    pub number_of_tokens: u32,
+    /// The value of the purchases in the base denom, excluding taxes.
+    pub amount: Uint128,
+    /// The amount paid in each denom, excluding taxes.
+    pub amounts_paid: Vec<Coin>,
+    /// The taxes paid in each denom.
+    pub taxes_paid: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct Referral {
+    pub referrer: String,
+    /// The number of tokens purchased through the referrer.
+    pub number_of_tokens: u32,
+    /// The amount referred in each denom, excluding taxes.
+    pub volume: Vec<Coin>,
+    /// The rewards paid to the referrer in each denom.
+    pub rewards_paid: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct SummaryResponse {
+    /// The total amount raised valued in the base denom, excluding taxes.
+    pub amount_raised: Uint128,
+    /// The amount raised in each accepted denom, excluding taxes.
+    pub amounts_raised: Vec<Coin>,
+    /// The taxes paid by purchasers in each accepted denom.
+    pub taxes_paid: Vec<Coin>,
+    /// Number of tokens sold.
+    pub amount_sold: Uint128,
+    /// Number of tokens left for sale, `None` if some tier is unlimited.
+    pub tokens_remaining: Option<Uint128>,
+    pub soft_cap_reached: bool,
+    /// The time left until the sale ends, `None` if the end is not time based.
+    pub time_left: Option<Milliseconds>,
+}
+
+#[cw_serde]
+pub struct TierResponse {
+    pub tier: Tier,
+    /// The number of tokens sold for this tier.
+    pub sold_amount: Uint128,
+    /// The number of tokens left for this tier, `None` if the tier is unlimited.
+    pub remaining: Option<Uint128>,
+}
@@ -0,0 +1,124 @@
+use andromeda_std::amp::recipient::Recipient;
+use andromeda_std::common::Milliseconds;
+use andromeda_std::{andr_exec, andr_instantiate, andr_instantiate_modules, andr_query};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw721::Expiration;
+
+#[andr_instantiate]
+#[andr_instantiate_modules]
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The native denom accepted for contributions.
+    pub denom: String,
+    /// The default recipient of withdrawn funds. Defaults to the contract owner.
+    pub recipient: Option<Recipient>,
+    /// The minimum amount accepted for a single contribution.
+    pub min_contribution: Option<Uint128>,
+    /// When contributions close. Contributions are accepted indefinitely if not provided.
+    pub end_time: Option<Milliseconds>,
+}
+
+#[andr_exec]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Contributes the attached funds to the campaign.
+    Contribute {},
+    /// Withdraws raised funds from the contract. Withdraws everything raised so far if `amount`
+    /// is not provided. Only accessible by owner.
+    Withdraw {
+        amount: Option<Uint128>,
+        /// Overrides the configured recipient for this withdrawal.
+        recipient: Option<Recipient>,
+    },
+    /// Deposits the attached funds to back the campaign with `amount_per_period` every `period`.
+    /// The first period is due right away. Pledging again tops up the deposit and replaces the
+    /// pledged amount and period.
+    Pledge {
+        amount_per_period: Uint128,
+        period: Milliseconds,
+    },
+    /// Collects up to `limit` due pledges and sends them to the campaign recipient. Pledges that
+    /// run out of funds are removed.
+    CollectPledges { limit: Option<u32> },
+    /// Cancels the sender's pledge. Any periods that are due are collected first and the unused
+    /// balance is returned to the backer.
+    CancelPledge {},
+}
+
+#[andr_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    #[returns(State)]
+    State {},
+    /// The total amount contributed by the given address.
+    #[returns(ContributionResponse)]
+    Contribution { address: String },
+    #[returns(Vec<ContributionResponse>)]
+    Contributions {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<Pledge>)]
+    Pledge { address: String },
+    #[returns(Vec<Pledge>)]
+    Pledges {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct Config {
+    /// The native denom accepted for contributions.
+    pub denom: String,
+    /// The default recipient of withdrawn funds, the contract owner is used if not set.
+    pub recipient: Option<Recipient>,
+    /// The minimum amount accepted for a single contribution.
+    pub min_contribution: Uint128,
+    /// When contributions close.
+    pub end_time: Option<Expiration>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct State {
+    /// The total amount contributed to the campaign.
+    pub total_raised: Uint128,
+    /// The total amount withdrawn by the owner.
+    pub total_withdrawn: Uint128,
+    /// The number of unique contributors.
+    pub number_of_contributors: u64,
+    /// The total amount collected from pledges and sent to the recipient.
+    pub total_collected: Uint128,
+}
+
+impl State {
+    /// The amount raised that has not been withdrawn yet.
+    pub fn available(&self) -> Uint128 {
+        self.total_raised - self.total_withdrawn
+    }
+}
+
+#[cw_serde]
+pub struct ContributionResponse {
+    pub contributor: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct Pledge {
+    pub backer: String,
+    /// The amount collected every period.
+    pub amount_per_period: Uint128,
+    pub period: Milliseconds,
+    /// The deposited funds that have not been collected yet.
+    pub balance: Uint128,
+    /// When the next period is due.
+    pub next_collection: Milliseconds,
+    /// The total amount collected from this pledge.
+    pub total_collected: Uint128,
+}
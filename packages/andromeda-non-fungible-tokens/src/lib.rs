@@ -1,6 +1,7 @@
 pub mod auction;
 pub mod crowdfund;
 pub mod cw721;
+pub mod flexi_crowdfund;
 // pub mod cw721_bid;
 // pub mod cw721_staking;
 // pub mod cw721_timelock;
@@ -4,6 +4,7 @@ use andromeda_std::{
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
 use cw721::{Cw721ReceiveMsg, Expiration};
 use std::fmt::{Display, Formatter, Result};
 
@@ -17,6 +18,8 @@ pub struct InstantiateMsg {}
 #[cw_serde]
 pub enum ExecuteMsg {
     ReceiveNft(Cw721ReceiveMsg),
+    /// Buys with CW20 tokens when the sale is priced in a CW20.
+    Receive(Cw20ReceiveMsg),
     /// Transfers NFT to buyer and sends funds to seller
     Buy {
         token_id: String,
@@ -28,6 +31,8 @@ pub enum ExecuteMsg {
         token_address: String,
         price: Uint128,
         coin_denom: String,
+        /// Whether `coin_denom` is the address of a CW20 token. Defaults to false.
+        uses_cw20: Option<bool>,
         recipient: Option<Recipient>,
     },
     CancelSale {
@@ -43,11 +48,22 @@ pub enum Cw721HookMsg {
     StartSale {
         price: Uint128,
         coin_denom: String,
+        /// Whether `coin_denom` is the address of a CW20 token. Defaults to false.
+        uses_cw20: Option<bool>,
         start_time: Option<Milliseconds>,
         duration: Option<Milliseconds>,
         recipient: Option<Recipient>,
     },
 }
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Buys the token with the sent CW20 tokens. Works the same as `ExecuteMsg::Buy`.
+    Buy {
+        token_id: String,
+        token_address: String,
+    },
+}
 #[cw_serde]
 pub enum Status {
     Open,
@@ -106,6 +122,7 @@ pub enum QueryMsg {
 pub struct SaleStateResponse {
     pub sale_id: Uint128,
     pub coin_denom: String,
+    pub uses_cw20: bool,
     pub price: Uint128,
     pub status: Status,
     pub start_time: Expiration,
@@ -2,8 +2,11 @@ use crate::mock::MockApp;
 use crate::mock_contract::ExecuteResult;
 
 use andromeda_kernel::mock::*;
-use andromeda_std::amp::{messages::AMPMsgConfig, AndrAddr};
-use andromeda_std::os::kernel::{ExecuteMsg, QueryMsg};
+use andromeda_std::amp::{
+    messages::{AMPMsg, AMPMsgConfig},
+    AndrAddr,
+};
+use andromeda_std::os::kernel::{BatchMode, ExecuteMsg, QueryMsg, Trace};
 use cosmwasm_std::{Addr, Coin};
 use cw_multi_test::Executor;
 use serde::Serialize;
@@ -74,6 +77,36 @@ impl MockKernel {
         self.execute(app, &msg, sender, &funds)
     }
 
+    pub fn execute_send_batch(
+        &self,
+        app: &mut MockApp,
+        sender: Addr,
+        messages: Vec<AMPMsg>,
+        mode: BatchMode,
+        funds: &[Coin],
+    ) -> ExecuteResult {
+        let msg = mock_send_batch(messages, mode);
+
+        self.execute(app, &msg, sender, funds)
+    }
+
+    pub fn execute_set_tracing(
+        &self,
+        app: &mut MockApp,
+        sender: Addr,
+        enabled: bool,
+    ) -> ExecuteResult {
+        let msg = mock_set_tracing(enabled);
+
+        self.execute(app, &msg, sender, &[])
+    }
+
+    pub fn query_trace(&self, app: &MockApp, packet_id: u64) -> Trace {
+        let msg = mock_get_trace(packet_id);
+
+        self.query(app, msg)
+    }
+
     pub fn query_key_address(&self, app: &MockApp, key: impl Into<String>) -> String {
         let msg = mock_get_key_address(key);
 
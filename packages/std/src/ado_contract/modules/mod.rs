@@ -6,7 +6,8 @@ use crate::{
     common::Funds,
 };
 use cosmwasm_std::{
-    ensure, Binary, Deps, Event, Order, QuerierWrapper, Response, StdError, Storage, SubMsg, Uint64,
+    ensure, Binary, CosmosMsg, Deps, Event, Order, QuerierWrapper, Response, StdError, Storage,
+    SubMsg, Uint64, WasmMsg,
 };
 use cw_storage_plus::Bound;
 use serde::de::DeserializeOwned;
@@ -234,6 +235,63 @@ impl<'a> ADOContract<'a> {
 
         Ok((msgs, events, remainder))
     }
+
+    /// Sends a `OnExecute` hook message to all registered modules.
+    ///
+    /// Returns the sub messages and events of the modules' responses, which the host must add to
+    /// its own response. As these are sent with the host's authority, a module may only return
+    /// messages executing itself without any funds.
+    pub fn on_execute(
+        &self,
+        deps: &Deps,
+        sender: String,
+        payload: Binary,
+    ) -> Result<Response, ContractError> {
+        let mut resp = Response::new();
+
+        let vfs_address = self.get_vfs_address(deps.storage, &deps.querier)?;
+        let modules: Vec<Module> = self.load_modules(deps.storage)?;
+        for module in modules {
+            let module_address = module
+                .address
+                .get_raw_address_from_vfs(deps, vfs_address.clone())?;
+            let mod_resp: Option<Response> = hook_query(
+                &deps.querier,
+                AndromedaHook::OnExecute {
+                    sender: sender.clone(),
+                    payload: payload.clone(),
+                },
+                module_address.clone(),
+            )?;
+
+            if let Some(mod_resp) = mod_resp {
+                for sub_msg in mod_resp.messages.iter() {
+                    ensure!(
+                        is_self_execute(&sub_msg.msg, module_address.as_str()),
+                        ContractError::InvalidModule {
+                            msg: Some(format!(
+                                "Module {module_address} may only execute itself without funds"
+                            ))
+                        }
+                    );
+                }
+                resp = resp
+                    .add_submessages(mod_resp.messages)
+                    .add_events(mod_resp.events);
+            }
+        }
+
+        Ok(resp)
+    }
+}
+
+/// Whether the given message executes the given contract without sending it any funds.
+fn is_self_execute(msg: &CosmosMsg, addr: &str) -> bool {
+    matches!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, funds, .. })
+            if contract_addr == addr && funds.is_empty()
+    )
 }
 
 /// Processes the given module response by hiding the error if it is `UnsupportedOperation` and
@@ -586,6 +644,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_self_execute() {
+        let execute = |contract_addr: &str, funds: Vec<Coin>| {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_json_binary(&true).unwrap(),
+                funds,
+            })
+        };
+
+        assert!(is_self_execute(&execute("module", vec![]), "module"));
+        assert!(!is_self_execute(&execute("other", vec![]), "module"));
+        assert!(!is_self_execute(
+            &execute("module", vec![Coin::new(100u128, "uandr")]),
+            "module"
+        ));
+        assert!(!is_self_execute(
+            &CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                to_address: "module".to_string(),
+                amount: vec![Coin::new(100u128, "uandr")],
+            }),
+            "module"
+        ));
+    }
+
     #[test]
     fn test_validate_modules() {
         let mut modules = vec![];
@@ -1,10 +1,11 @@
-use cosmwasm_std::{BankMsg, CosmosMsg, SubMsg, Uint128};
+use cosmwasm_std::{from_json, BankMsg, CosmosMsg, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
 
 /// Gets the amount of tax paid by iterating over the `msgs` and comparing it to the
 /// difference between the base amount and the amount left over after royalties.
 /// It is assumed that each bank message has a single Coin to send as transfer
-/// agreements only accept a single Coin. It is also assumed that the result will always be
-/// non-negative.
+/// agreements only accept a single Coin. CW20 `Transfer` and `Send` messages are counted as well.
+/// It is also assumed that the result will always be non-negative.
 ///
 /// # Arguments
 ///
@@ -20,12 +21,14 @@ pub fn get_tax_amount(
 ) -> Uint128 {
     let deducted_amount = base_amount - remaining_amount_after_royalties;
     msgs.iter()
-        .map(|msg| {
-            if let CosmosMsg::Bank(BankMsg::Send { amount, .. }) = &msg.msg {
-                amount[0].amount
-            } else {
-                Uint128::zero()
-            }
+        .map(|msg| match &msg.msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount[0].amount,
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_json(msg) {
+                Ok(Cw20ExecuteMsg::Transfer { amount, .. })
+                | Ok(Cw20ExecuteMsg::Send { amount, .. }) => amount,
+                _ => Uint128::zero(),
+            },
+            _ => Uint128::zero(),
         })
         .reduce(|total, amount| total + amount)
         .unwrap_or_else(Uint128::zero)
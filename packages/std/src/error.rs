@@ -493,6 +493,33 @@ pub enum ContractError {
     #[error("Sale has already started")]
     SaleStarted {},
 
+    #[error("Invalid tier for {operation} operation: {msg}")]
+    InvalidTier { operation: String, msg: String },
+
+    #[error("Invalid funding goal: {msg}")]
+    InvalidFundingGoal { msg: String },
+
+    #[error("Invalid prices: {msg}")]
+    InvalidPrices { msg: String },
+
+    #[error("Invalid presale: {msg}")]
+    InvalidPresale { msg: String },
+
+    #[error("Invalid milestone: {msg}")]
+    InvalidMilestone { msg: String },
+
+    #[error("Invalid pledge: {msg}")]
+    InvalidPledge { msg: String },
+
+    #[error("Invalid referrer: {msg}")]
+    InvalidReferrer { msg: String },
+
+    #[error("Invalid lazy mint: {msg}")]
+    InvalidLazyMint { msg: String },
+
+    #[error("Hard cap reached")]
+    HardCapReached {},
+
     #[error("No purchases")]
     NoPurchases {},
 
@@ -523,6 +550,9 @@ pub enum ContractError {
     #[error("Verification Failed")]
     VerificationFailed {},
 
+    #[error("Merkle root not registered")]
+    MerkleRootNotRegistered {},
+
     #[error("Invalid Asset: {asset}")]
     InvalidAsset { asset: String },
 
@@ -2,9 +2,10 @@ use crate::ado_base::ownership::OwnershipMessage;
 use crate::amp::messages::AMPMsg;
 use crate::amp::messages::AMPPkt;
 use crate::amp::AndrAddr;
+use crate::common::milliseconds::Milliseconds;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
 use cosmwasm_std::Binary;
+use cosmwasm_std::{Addr, BlockInfo, Coin, Uint64};
 
 #[cw_serde]
 pub struct ChannelInfo {
@@ -12,6 +13,8 @@ pub struct ChannelInfo {
     pub ics20_channel_id: Option<String>,
     pub direct_channel_id: Option<String>,
     pub supported_modules: Vec<String>,
+    /// How long packets sent over the channels remain valid, at most 30 days and defaults to a week
+    pub packet_timeout: Option<Milliseconds>,
 }
 
 impl Default for ChannelInfo {
@@ -21,6 +24,7 @@ impl Default for ChannelInfo {
             ics20_channel_id: None,
             direct_channel_id: None,
             supported_modules: vec![],
+            packet_timeout: None,
         }
     }
 }
@@ -40,6 +44,32 @@ pub enum ExecuteMsg {
     Send {
         message: AMPMsg,
     },
+    /// Sends each of the given AMPMsgs as with `Send`, in the given order
+    SendBatch {
+        messages: Vec<AMPMsg>,
+        mode: BatchMode,
+    },
+    /// Stores the given AMPMsg to be sent on behalf of the sender once it is due. The attached
+    /// funds must cover the funds of the message and the optional tip.
+    Schedule {
+        message: AMPMsg,
+        execute_after: ExecuteAfter,
+        /// Paid to whoever executes the message
+        tip: Option<Coin>,
+    },
+    /// Sends up to `limit` due scheduled messages, paying their tips to the sender. Failed
+    /// messages are reported as with a best-effort `SendBatch`, using the id of the scheduled
+    /// message as the sequence.
+    ExecuteScheduled {
+        limit: Option<u32>,
+        /// Sends the scheduled messages with these ids instead of the oldest due ones, all of
+        /// which must be due
+        ids: Option<Vec<Uint64>>,
+    },
+    /// Cancels a scheduled message and refunds its funds and tip, restricted to its sender
+    CancelScheduled {
+        id: Uint64,
+    },
     /// Upserts a key address to the kernel, restricted to the owner of the kernel
     UpsertKeyAddress {
         key: String,
@@ -58,19 +88,84 @@ pub enum ExecuteMsg {
         direct_channel_id: Option<String>,
         chain: String,
         kernel_address: String,
+        packet_timeout: Option<Milliseconds>,
     },
     /// Recovers funds from failed IBC messages
     Recover {},
+    /// Resends a failed or timed out IBC packet, restricted to the sender of the packet. The
+    /// funds of the original message must be attached again as they are refunded through
+    /// `Recover`.
+    Retry {
+        channel_id: String,
+        sequence: u64,
+    },
     /// Update Current Chain
     UpdateChainName {
         chain_name: String,
     },
+    /// Enables or disables recording the hops of AMP packets, restricted to the owner of the
+    /// kernel
+    SetTracing {
+        enabled: bool,
+    },
     // Only accessible to key contracts
     Internal(InternalMsg),
     // Base message
     Ownership(OwnershipMessage),
 }
 
+#[cw_serde]
+pub enum BatchMode {
+    /// Any failing message reverts the whole batch
+    Atomic,
+    /// Failing messages are reverted and reported in a `batch_msg_failed` event, with their funds
+    /// refunded to the sender
+    BestEffort,
+}
+
+impl std::fmt::Display for BatchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchMode::Atomic => write!(f, "atomic"),
+            BatchMode::BestEffort => write!(f, "best_effort"),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum ExecuteAfter {
+    /// A time in milliseconds since the epoch
+    Time(Milliseconds),
+    Height(u64),
+}
+
+impl ExecuteAfter {
+    pub fn is_due(&self, block: &BlockInfo) -> bool {
+        match self {
+            ExecuteAfter::Time(time) => time.is_expired(block),
+            ExecuteAfter::Height(height) => *height <= block.height,
+        }
+    }
+}
+
+impl std::fmt::Display for ExecuteAfter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecuteAfter::Time(time) => write!(f, "time:{time}"),
+            ExecuteAfter::Height(height) => write!(f, "height:{height}"),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct ScheduledMsg {
+    pub id: Uint64,
+    pub sender: Addr,
+    pub message: AMPMsg,
+    pub execute_after: ExecuteAfter,
+    pub tip: Option<Coin>,
+}
+
 #[cw_serde]
 pub enum InternalMsg {
     // Restricted to VFS
@@ -79,6 +174,14 @@ pub enum InternalMsg {
         address: String,
         chain: String,
     },
+    // Restricted to the Kernel itself, handles a message of a best-effort batch so that its
+    // failure reverts everything it did
+    BestEffortMsg {
+        message: AMPMsg,
+        sender: Addr,
+        ctx: Option<AMPPkt>,
+        sequence: Uint64,
+    },
 }
 
 #[cw_serde]
@@ -87,6 +190,67 @@ pub struct ChannelInfoResponse {
     pub direct: Option<String>,
     pub kernel_address: String,
     pub supported_modules: Vec<String>,
+    pub packet_timeout: Option<Milliseconds>,
+}
+
+#[cw_serde]
+pub enum IbcPacketStatus {
+    Pending,
+    Acked,
+    Failed {
+        error: String,
+    },
+    TimedOut,
+    /// The packet failed or timed out and has been sent again
+    Retried,
+}
+
+impl std::fmt::Display for IbcPacketStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IbcPacketStatus::Pending => write!(f, "pending"),
+            IbcPacketStatus::Acked => write!(f, "acked"),
+            IbcPacketStatus::Failed { .. } => write!(f, "failed"),
+            IbcPacketStatus::TimedOut => write!(f, "timed_out"),
+            IbcPacketStatus::Retried => write!(f, "retried"),
+        }
+    }
+}
+
+/// An outgoing cross-chain AMP message and the status of its packet
+#[cw_serde]
+pub struct IbcPacket {
+    pub channel_id: String,
+    pub sequence: u64,
+    pub sender: Addr,
+    pub message: AMPMsg,
+    pub status: IbcPacketStatus,
+}
+
+#[cw_serde]
+pub enum HopResult {
+    /// The kernel dispatched the message to its recipient
+    Dispatched,
+    /// The kernel could not dispatch the message, only recorded for best-effort sends
+    Failed { error: String },
+}
+
+/// A message handled by the kernel as part of an AMP packet
+#[cw_serde]
+pub struct TraceHop {
+    pub sender: Addr,
+    pub recipient: AndrAddr,
+    pub funds: Vec<Coin>,
+    pub result: HopResult,
+    pub block_height: u64,
+}
+
+/// The hops of an AMP packet, in the order they were handled by the kernel
+#[cw_serde]
+pub struct Trace {
+    pub packet_id: Uint64,
+    pub origin: String,
+    pub hops: Vec<TraceHop>,
 }
 
 #[cw_serde]
@@ -107,6 +271,35 @@ pub enum QueryMsg {
     Recoveries { addr: Addr },
     #[returns(ChainNameResponse)]
     ChainName {},
+    #[returns(ScheduledMsg)]
+    ScheduledMsg { id: Uint64 },
+    /// Paginated list of the scheduled messages, ordered by id
+    #[returns(Vec<ScheduledMsg>)]
+    ScheduledMsgs {
+        start_after: Option<Uint64>,
+        limit: Option<u32>,
+    },
+    #[returns(IbcPacket)]
+    IbcPacket { channel_id: String, sequence: u64 },
+    /// Paginated list of the IBC packets sent by the given address, ordered by channel and
+    /// sequence
+    #[returns(Vec<IbcPacket>)]
+    IbcPackets {
+        sender: Addr,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    #[returns(bool)]
+    TracingEnabled {},
+    #[returns(Trace)]
+    Trace { packet_id: Uint64 },
+    /// Paginated list of the traces of packets from the given origin, ordered by packet id
+    #[returns(Vec<Trace>)]
+    TracesByOrigin {
+        origin: String,
+        start_after: Option<Uint64>,
+        limit: Option<u32>,
+    },
     // Base queries
     #[returns(crate::ado_base::version::VersionResponse)]
     Version {},
@@ -178,11 +178,37 @@ pub enum ExecuteMsg {
         name: String,
         parent_address: AndrAddr,
     },
+    // Removes a path and any symlinks that resolve through a removed path. When sent by the kernel
+    // or owner the path's children are removed too, unless the path is a user or library home
+    // directory or its children are still reachable through another path
+    RemovePath {
+        name: String,
+        parent_address: Option<AndrAddr>,
+    },
+    // Symlinks that resolve through the old path are removed
+    RenamePath {
+        name: String,
+        #[schemars(regex = "COMPONENT_NAME_REGEX")]
+        new_name: String,
+        parent_address: Option<AndrAddr>,
+    },
+    // Moves a path, and any children, under a new parent. Only the kernel or the VFS owner can
+    // move paths out of the sender's home directory
+    MovePath {
+        name: String,
+        parent_address: Option<AndrAddr>,
+        new_parent_address: AndrAddr,
+    },
     RegisterUser {
         #[schemars(regex = "USERNAME_REGEX", length(min = 3, max = 30))]
         username: String,
         address: Option<Addr>,
     },
+    // Transfers the sender's username to an address that has not registered one
+    TransferUsername {
+        new_owner: Addr,
+    },
+    ReleaseUsername {},
     // Restricted to VFS owner/Kernel
     RegisterLibrary {
         lib_name: String,
@@ -476,6 +476,7 @@ impl MockAndromedaQuerier {
                     ics20_channel_id: Some("1".to_string()),
                     direct_channel_id: Some("2".to_string()),
                     supported_modules: vec![],
+                    packet_timeout: None,
                 })
                 .unwrap(),
             ))
@@ -3,7 +3,11 @@
 use andromeda_app::app::AppComponent;
 use andromeda_app_contract::mock::{mock_andromeda_app, mock_claim_ownership_msg, MockAppContract};
 use andromeda_auction::mock::{
-    mock_andromeda_auction, mock_auction_instantiate_msg, mock_start_auction, MockAuction,
+    mock_andromeda_auction, mock_auction_instantiate_msg, mock_place_bid_cw20, mock_start_auction,
+    MockAuction,
+};
+use andromeda_cw20::mock::{
+    mock_andromeda_cw20, mock_cw20_instantiate_msg, mock_cw20_send, mock_get_cw20_balance,
 };
 use andromeda_cw721::mock::{mock_andromeda_cw721, mock_cw721_instantiate_msg, MockCW721};
 
@@ -18,6 +22,7 @@ use andromeda_testing::{
     mock::mock_app, mock_builder::MockAndromedaBuilder, mock_contract::MockContract,
 };
 use cosmwasm_std::{coin, to_json_binary, Addr, BlockInfo, Decimal, Uint128};
+use cw20::{BalanceResponse, Cw20Coin};
 use cw_multi_test::Executor;
 
 #[test]
@@ -66,6 +71,8 @@ fn test_auction_app_modules() {
             description: None,
             rate: Rate::Percent(PercentRate {
                 percent: Decimal::from_ratio(1u32, 2u32),
+                min: None,
+                max: None,
             }),
             recipients: vec![
                 Recipient::from_string("./splitter").with_msg(mock_splitter_send_msg())
@@ -150,6 +157,7 @@ fn test_auction_app_modules() {
         Some(start_time),
         start_time.plus_milliseconds(Milliseconds(1000)),
         "uandr".to_string(),
+        false,
         None,
         None,
         None,
@@ -348,6 +356,7 @@ fn test_auction_app_recipient() {
         Some(start_time),
         start_time.plus_milliseconds(Milliseconds(1000)),
         "uandr".to_string(),
+        false,
         None,
         None,
         Some(Recipient::from_string("./splitter").with_msg(mock_splitter_send_msg())),
@@ -438,3 +447,209 @@ fn test_auction_app_recipient() {
     let recipient_two_balance = router.wrap().query_balance(recipient_two, "uandr").unwrap();
     assert_eq!(recipient_two_balance.amount, Uint128::from(50u128));
 }
+
+#[test]
+fn test_auction_app_cw20() {
+    let mut router = mock_app(None);
+    let andr = MockAndromedaBuilder::new(&mut router, "admin")
+        .with_wallets(vec![
+            ("owner", vec![]),
+            ("buyer_one", vec![]),
+            ("buyer_two", vec![]),
+            ("rates_receiver", vec![]),
+        ])
+        .with_contracts(vec![
+            ("cw721", mock_andromeda_cw721()),
+            ("cw20", mock_andromeda_cw20()),
+            ("auction", mock_andromeda_auction()),
+            ("app-contract", mock_andromeda_app()),
+            ("rates", mock_andromeda_rates()),
+        ])
+        .build(&mut router);
+    let owner = andr.get_wallet("owner");
+    let buyer_one = andr.get_wallet("buyer_one");
+    let buyer_two = andr.get_wallet("buyer_two");
+    let rates_receiver = andr.get_wallet("rates_receiver");
+
+    // Generate App Components
+    let cw721_init_msg = mock_cw721_instantiate_msg(
+        "Test Tokens".to_string(),
+        "TT".to_string(),
+        owner.to_string(),
+        None,
+        andr.kernel.addr().to_string(),
+        None,
+    );
+    let cw721_component = AppComponent::new(
+        "cw721".to_string(),
+        "cw721".to_string(),
+        to_json_binary(&cw721_init_msg).unwrap(),
+    );
+
+    let cw20_init_msg = mock_cw20_instantiate_msg(
+        None,
+        "Test Coins".to_string(),
+        "TTC".to_string(),
+        6,
+        vec![
+            Cw20Coin {
+                address: buyer_one.to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            Cw20Coin {
+                address: buyer_two.to_string(),
+                amount: Uint128::from(1000u128),
+            },
+        ],
+        None,
+        None,
+        andr.kernel.addr().to_string(),
+    );
+    let cw20_component = AppComponent::new(
+        "cw20".to_string(),
+        "cw20".to_string(),
+        to_json_binary(&cw20_init_msg).unwrap(),
+    );
+
+    // A 10% royalty deducted from the winning bid.
+    let rates_init_msg = mock_rates_instantiate_msg(
+        vec![RateInfo {
+            is_additive: false,
+            description: None,
+            rate: Rate::from(Decimal::percent(10)),
+            recipients: vec![Recipient::from_string(rates_receiver.to_string())],
+        }],
+        andr.kernel.addr(),
+        None,
+    );
+    let rates_component =
+        AppComponent::new("rates", "rates", to_json_binary(&rates_init_msg).unwrap());
+
+    let auction_init_msg = mock_auction_instantiate_msg(
+        Some(vec![Module::new("rates", "./rates", false)]),
+        andr.kernel.addr().to_string(),
+        None,
+        None,
+    );
+    let auction_component = AppComponent::new(
+        "auction".to_string(),
+        "auction".to_string(),
+        to_json_binary(&auction_init_msg).unwrap(),
+    );
+
+    // Create App
+    let app_components = vec![
+        cw721_component.clone(),
+        cw20_component.clone(),
+        auction_component.clone(),
+        rates_component,
+    ];
+    let app = MockAppContract::instantiate(
+        andr.get_code_id(&mut router, "app-contract"),
+        owner,
+        &mut router,
+        "Auction App",
+        app_components,
+        andr.kernel.addr(),
+        None,
+    );
+
+    // Mint Tokens
+    let cw721: MockCW721 = app.query_ado_by_component_name(&router, cw721_component.name);
+    cw721
+        .execute_quick_mint(&mut router, owner.clone(), 1, owner.to_string())
+        .unwrap();
+    let cw20_addr = andr.vfs.query_resolve_path(
+        &mut router,
+        format!("/home/{}/{}", app.addr(), cw20_component.name),
+    );
+
+    // Send Token to Auction
+    let auction: MockAuction = app.query_ado_by_component_name(&router, auction_component.name);
+    let start_time = Milliseconds::from_nanos(router.block_info().time.nanos())
+        .plus_milliseconds(Milliseconds(100));
+    let receive_msg = mock_start_auction(
+        Some(start_time),
+        start_time.plus_milliseconds(Milliseconds(1000)),
+        cw20_addr.to_string(),
+        true,
+        None,
+        None,
+        None,
+    );
+    cw721
+        .execute_send_nft(
+            &mut router,
+            owner.clone(),
+            auction.addr(),
+            "0",
+            &receive_msg,
+        )
+        .unwrap();
+
+    router.set_block(BlockInfo {
+        height: router.block_info().height,
+        time: start_time.into(),
+        chain_id: router.block_info().chain_id,
+    });
+
+    // Place Bids
+    let bid_msg = to_json_binary(&mock_place_bid_cw20(
+        "0".to_string(),
+        cw721.addr().to_string(),
+    ))
+    .unwrap();
+    router
+        .execute_contract(
+            buyer_one.clone(),
+            cw20_addr.clone(),
+            &mock_cw20_send(auction.addr(), Uint128::from(50u128), bid_msg.clone()),
+            &[],
+        )
+        .unwrap();
+    router
+        .execute_contract(
+            buyer_two.clone(),
+            cw20_addr.clone(),
+            &mock_cw20_send(auction.addr(), Uint128::from(100u128), bid_msg),
+            &[],
+        )
+        .unwrap();
+
+    // End Auction
+    router.set_block(BlockInfo {
+        height: router.block_info().height,
+        time: start_time.plus_milliseconds(Milliseconds(1000)).into(),
+        chain_id: router.block_info().chain_id,
+    });
+    auction
+        .execute_claim_auction(
+            &mut router,
+            buyer_two.clone(),
+            "0".to_string(),
+            cw721.addr().to_string(),
+        )
+        .unwrap();
+
+    // Check Final State
+    let token_owner = cw721.query_owner_of(&router, "0");
+    assert_eq!(token_owner, buyer_two);
+
+    let balances = [
+        (owner, 90u128),
+        (rates_receiver, 10u128),
+        // The outbid bidder was refunded.
+        (buyer_one, 1000u128),
+        (buyer_two, 900u128),
+    ];
+    for (address, expected) in balances {
+        let balance: BalanceResponse = router
+            .wrap()
+            .query_wasm_smart(
+                cw20_addr.clone(),
+                &mock_get_cw20_balance(address.to_string()),
+            )
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::from(expected));
+    }
+}
@@ -7,7 +7,7 @@ use andromeda_cw721::mock::{mock_andromeda_cw721, mock_cw721_instantiate_msg, Mo
 use andromeda_finance::splitter::AddressPercent;
 use andromeda_std::{
     amp::{AndrAddr, Recipient},
-    common::Milliseconds,
+    common::{Funds, Milliseconds},
 };
 
 use andromeda_modules::rates::{Rate, RateInfo};
@@ -176,14 +176,25 @@ fn test_crowdfund_app() {
             &mut router,
             Some(start_time),
             end_time,
-            token_price.clone(),
-            Uint128::from(3u128),
+            vec![Funds::Native(token_price.clone())],
+            None,
+            coin(300, "uandr"),
+            None,
+            None,
             Some(1),
             sale_recipient,
+            None,
+            None,
         )
         .unwrap();
 
-    // Buy Tokens
+    // Buy Tokens once the sale has started
+    let block_info = router.block_info();
+    router.set_block(BlockInfo {
+        height: block_info.height,
+        time: start_time.into(),
+        chain_id: block_info.chain_id,
+    });
     let buyers = vec![buyer_one, buyer_two, buyer_three];
     for buyer in buyers.clone() {
         crowdfund_contract
@@ -196,6 +207,10 @@ fn test_crowdfund_app() {
         .unwrap();
     assert_eq!(crowdfund_balance.amount, Uint128::from(300u128));
 
+    let summary = crowdfund_contract.query_summary(&mut router);
+    assert_eq!(summary.amount_sold, Uint128::from(3u128));
+    assert_eq!(summary.amount_raised, Uint128::from(300u128));
+
     // End Sale
     let block_info = router.block_info();
     router.set_block(BlockInfo {
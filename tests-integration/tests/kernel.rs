@@ -2,14 +2,15 @@ use andromeda_finance::splitter::AddressPercent;
 use andromeda_splitter::mock::{
     mock_andromeda_splitter, mock_splitter_instantiate_msg, mock_splitter_send_msg, MockSplitter,
 };
-use andromeda_std::amp::{AndrAddr, Recipient};
+use andromeda_std::amp::{messages::AMPMsg, AndrAddr, Recipient};
+use andromeda_std::os::kernel::{BatchMode, HopResult, TraceHop};
 use andromeda_testing::{
     mock::mock_app,
     mock_builder::MockAndromedaBuilder,
     mock_contract::{MockADO, MockContract},
 };
 
-use cosmwasm_std::{coin, Addr, Decimal};
+use cosmwasm_std::{coin, to_json_binary, Addr, Binary, Decimal};
 
 #[test]
 fn kernel() {
@@ -101,3 +102,103 @@ fn kernel() {
 
     assert!(res.data.is_none());
 }
+
+#[test]
+fn kernel_send_batch() {
+    let mut router = mock_app(None);
+    let andr = MockAndromedaBuilder::new(&mut router, "admin")
+        .with_wallets(vec![
+            ("owner", vec![coin(1000, "uandr")]),
+            ("user1", vec![]),
+        ])
+        .with_contracts(vec![("splitter", mock_andromeda_splitter())])
+        .build(&mut router);
+
+    let owner = andr.get_wallet("owner");
+    let user1 = andr.get_wallet("user1");
+
+    let splitter_code_id = andr.get_code_id(&mut router, "splitter");
+    let splitter = MockSplitter::instantiate(
+        &mut router,
+        splitter_code_id,
+        owner.clone(),
+        vec![AddressPercent::new(
+            Recipient::from_string(user1.to_string()),
+            Decimal::one(),
+        )],
+        andr.kernel.addr().clone(),
+        None,
+        None,
+    );
+
+    let messages = vec![
+        AMPMsg::new(
+            user1.to_string(),
+            Binary::default(),
+            Some(vec![coin(100, "uandr")]),
+        ),
+        // The splitter does not accept this message
+        AMPMsg::new(
+            splitter.addr().to_string(),
+            to_json_binary(&"invalid").unwrap(),
+            Some(vec![coin(50, "uandr")]),
+        ),
+        AMPMsg::new(
+            splitter.addr().to_string(),
+            to_json_binary(&mock_splitter_send_msg()).unwrap(),
+            Some(vec![coin(100, "uandr")]),
+        ),
+    ];
+
+    // An atomic batch reverts all messages
+    andr.kernel
+        .execute_send_batch(
+            &mut router,
+            owner.clone(),
+            messages.clone(),
+            BatchMode::Atomic,
+            &[coin(250, "uandr")],
+        )
+        .unwrap_err();
+    let owner_balance = router.wrap().query_balance(owner.clone(), "uandr").unwrap();
+    assert_eq!(owner_balance, coin(1000, "uandr"));
+
+    andr.kernel
+        .execute_set_tracing(&mut router, andr.admin_address.clone(), true)
+        .unwrap();
+
+    // A best-effort batch skips the failed message and refunds its funds
+    let res = andr
+        .kernel
+        .execute_send_batch(
+            &mut router,
+            owner.clone(),
+            messages,
+            BatchMode::BestEffort,
+            &[coin(250, "uandr")],
+        )
+        .unwrap();
+    let failed_event = res
+        .events
+        .iter()
+        .find(|ev| ev.ty == "wasm-batch_msg_failed")
+        .unwrap();
+    assert_eq!(failed_event.attributes[1].key, "sequence");
+    assert_eq!(failed_event.attributes[1].value, "1");
+
+    let user1_balance = router.wrap().query_balance(user1, "uandr").unwrap();
+    assert_eq!(user1_balance, coin(200, "uandr"));
+    let owner_balance = router.wrap().query_balance(owner, "uandr").unwrap();
+    assert_eq!(owner_balance, coin(800, "uandr"));
+
+    // Everything the failed message recorded is reverted, leaving only its failure in the trace
+    let failed_hops: Vec<TraceHop> = andr
+        .kernel
+        .query_trace(&router, 1)
+        .hops
+        .into_iter()
+        .filter(|hop| hop.funds == vec![coin(50, "uandr")])
+        .collect();
+    assert_eq!(failed_hops.len(), 1);
+    assert!(matches!(failed_hops[0].result, HopResult::Failed { .. }));
+}
@@ -5,25 +5,34 @@ use andromeda_address_list::mock::{
 };
 use andromeda_app::app::AppComponent;
 use andromeda_app_contract::mock::{mock_andromeda_app, MockAppContract};
+use andromeda_cw20::mock::{
+    mock_andromeda_cw20, mock_cw20_instantiate_msg, mock_cw20_send, mock_get_cw20_balance,
+};
 use andromeda_cw721::mock::{mock_andromeda_cw721, mock_cw721_instantiate_msg, MockCW721};
 use andromeda_finance::splitter::AddressPercent;
 use andromeda_marketplace::mock::{
-    mock_andromeda_marketplace, mock_buy_token, mock_marketplace_instantiate_msg,
-    mock_receive_packet, mock_start_sale, MockMarketplace,
+    mock_andromeda_marketplace, mock_buy_token, mock_buy_token_cw20,
+    mock_marketplace_instantiate_msg, mock_receive_packet, mock_start_sale, MockMarketplace,
 };
 use andromeda_modules::rates::{Rate, RateInfo};
+use andromeda_modules::receipt::ReceiptsResponse;
 
 use andromeda_rates::mock::{mock_andromeda_rates, mock_rates_instantiate_msg};
+use andromeda_receipt::mock::{
+    mock_andromeda_receipt, mock_receipt_instantiate_msg, mock_receipts_by_sender_msg,
+};
 use andromeda_splitter::mock::{
     mock_andromeda_splitter, mock_splitter_instantiate_msg, mock_splitter_send_msg,
 };
 use andromeda_std::ado_base::modules::Module;
 use andromeda_std::amp::messages::{AMPMsg, AMPPkt};
-use andromeda_std::amp::Recipient;
+use andromeda_std::amp::{AndrAddr, Recipient};
+use andromeda_std::common::Funds;
 use andromeda_testing::mock::mock_app;
 use andromeda_testing::mock_builder::MockAndromedaBuilder;
 use andromeda_testing::MockContract;
-use cosmwasm_std::{coin, to_json_binary, Addr, BlockInfo, Decimal, Uint128};
+use cosmwasm_std::{coin, to_json_binary, Addr, BlockInfo, Decimal, Event, Uint128};
+use cw20::{BalanceResponse, Cw20Coin};
 use cw_multi_test::Executor;
 
 #[test]
@@ -144,7 +153,7 @@ fn test_marketplace_app() {
             owner.clone(),
             marketplace.addr().clone(),
             token_id,
-            &mock_start_sale(Uint128::from(100u128), "uandr", None, None, None),
+            &mock_start_sale(Uint128::from(100u128), "uandr", false, None, None, None),
         )
         .unwrap();
 
@@ -285,6 +294,7 @@ fn test_marketplace_app_recipient() {
             &mock_start_sale(
                 Uint128::from(100u128),
                 "uandr",
+                false,
                 None,
                 None,
                 Some(
@@ -329,3 +339,310 @@ fn test_marketplace_app_recipient() {
     let balance = router.wrap().query_balance(receiver, "uandr").unwrap();
     assert_eq!(balance.amount, Uint128::from(100u128));
 }
+
+#[test]
+fn test_marketplace_app_cw20() {
+    let mut router = mock_app(None);
+    let andr = MockAndromedaBuilder::new(&mut router, "admin")
+        .with_wallets(vec![
+            ("owner", vec![]),
+            ("buyer", vec![]),
+            ("receiver", vec![]),
+        ])
+        .with_contracts(vec![
+            ("app-contract", mock_andromeda_app()),
+            ("cw721", mock_andromeda_cw721()),
+            ("cw20", mock_andromeda_cw20()),
+            ("marketplace", mock_andromeda_marketplace()),
+            ("rates", mock_andromeda_rates()),
+        ])
+        .build(&mut router);
+    let owner = andr.get_wallet("owner");
+    let buyer = andr.get_wallet("buyer");
+    let rates_receiver = andr.get_wallet("receiver");
+
+    // Generate App Components
+    let cw721_init_msg = mock_cw721_instantiate_msg(
+        "Test Tokens".to_string(),
+        "TT".to_string(),
+        owner.to_string(),
+        None,
+        andr.kernel.addr().to_string(),
+        None,
+    );
+    let cw721_component = AppComponent::new(
+        "tokens".to_string(),
+        "cw721".to_string(),
+        to_json_binary(&cw721_init_msg).unwrap(),
+    );
+
+    let cw20_init_msg = mock_cw20_instantiate_msg(
+        None,
+        "Test Coins".to_string(),
+        "TTC".to_string(),
+        6,
+        vec![Cw20Coin {
+            address: buyer.to_string(),
+            amount: Uint128::from(100u128),
+        }],
+        None,
+        None,
+        andr.kernel.addr().to_string(),
+    );
+    let cw20_component = AppComponent::new(
+        "cw20".to_string(),
+        "cw20".to_string(),
+        to_json_binary(&cw20_init_msg).unwrap(),
+    );
+
+    // A 10% royalty deducted from the sale price.
+    let rates: Vec<RateInfo> = vec![RateInfo {
+        rate: Rate::from(Decimal::percent(10)),
+        is_additive: false,
+        description: None,
+        recipients: vec![Recipient::from_string(rates_receiver.to_string())],
+    }];
+    let rates_init_msg = mock_rates_instantiate_msg(rates, andr.kernel.addr().to_string(), None);
+    let rates_component =
+        AppComponent::new("rates", "rates", to_json_binary(&rates_init_msg).unwrap());
+
+    let modules: Vec<Module> = vec![Module::new(
+        "rates",
+        format!("./{}", rates_component.name),
+        false,
+    )];
+    let marketplace_init_msg =
+        mock_marketplace_instantiate_msg(andr.kernel.addr().to_string(), Some(modules), None);
+    let marketplace_component = AppComponent::new(
+        "marketplace".to_string(),
+        "marketplace".to_string(),
+        to_json_binary(&marketplace_init_msg).unwrap(),
+    );
+
+    // Create App
+    let app_components = vec![
+        cw721_component.clone(),
+        cw20_component.clone(),
+        rates_component,
+        marketplace_component.clone(),
+    ];
+    let app_code_id = andr.get_code_id(&mut router, "app-contract");
+    let app = MockAppContract::instantiate(
+        app_code_id,
+        owner,
+        &mut router,
+        "Marketplace App",
+        app_components,
+        andr.kernel.addr(),
+        None,
+    );
+
+    let cw721: MockCW721 = app.query_ado_by_component_name(&router, cw721_component.name);
+    let marketplace: MockMarketplace =
+        app.query_ado_by_component_name(&router, marketplace_component.name);
+    let cw20_addr = andr.vfs.query_resolve_path(
+        &mut router,
+        format!("/home/{}/{}", app.addr(), cw20_component.name),
+    );
+
+    // Mint Tokens
+    cw721
+        .execute_quick_mint(&mut router, owner.clone(), 1, owner.to_string())
+        .unwrap();
+    let token_id = "0";
+
+    // Send Token to Marketplace
+    cw721
+        .execute_send_nft(
+            &mut router,
+            owner.clone(),
+            marketplace.addr().clone(),
+            token_id,
+            &mock_start_sale(
+                Uint128::from(100u128),
+                cw20_addr.to_string(),
+                true,
+                None,
+                None,
+                None,
+            ),
+        )
+        .unwrap();
+
+    let block_info = router.block_info();
+    router.set_block(BlockInfo {
+        height: block_info.height,
+        time: block_info.time.plus_minutes(1),
+        chain_id: block_info.chain_id,
+    });
+
+    // Buy Token
+    let buy_msg = mock_cw20_send(
+        marketplace.addr().to_string(),
+        Uint128::from(100u128),
+        to_json_binary(&mock_buy_token_cw20(cw721.addr().clone(), token_id)).unwrap(),
+    );
+    router
+        .execute_contract(buyer.clone(), cw20_addr.clone(), &buy_msg, &[])
+        .unwrap();
+
+    // Check final state
+    let owner_of_token = cw721.query_owner_of(&router, token_id);
+    assert_eq!(owner_of_token, buyer.to_string());
+
+    let rates_balance: BalanceResponse = router
+        .wrap()
+        .query_wasm_smart(
+            cw20_addr.clone(),
+            &mock_get_cw20_balance(rates_receiver.to_string()),
+        )
+        .unwrap();
+    assert_eq!(rates_balance.balance, Uint128::from(10u128));
+
+    let owner_balance: BalanceResponse = router
+        .wrap()
+        .query_wasm_smart(cw20_addr, &mock_get_cw20_balance(owner.to_string()))
+        .unwrap();
+    assert_eq!(owner_balance.balance, Uint128::from(90u128));
+}
+
+#[test]
+fn test_marketplace_app_receipt() {
+    let mut router = mock_app(None);
+    let andr = MockAndromedaBuilder::new(&mut router, "admin")
+        .with_wallets(vec![("owner", vec![]), ("buyer", vec![coin(100, "uandr")])])
+        .with_contracts(vec![
+            ("app-contract", mock_andromeda_app()),
+            ("cw721", mock_andromeda_cw721()),
+            ("marketplace", mock_andromeda_marketplace()),
+            ("receipt", mock_andromeda_receipt()),
+        ])
+        .build(&mut router);
+    let owner = andr.get_wallet("owner");
+    let buyer = andr.get_wallet("buyer");
+
+    // Generate App Components
+    let cw721_init_msg = mock_cw721_instantiate_msg(
+        "Test Tokens".to_string(),
+        "TT".to_string(),
+        owner.to_string(),
+        None,
+        andr.kernel.addr().to_string(),
+        None,
+    );
+    let cw721_component = AppComponent::new(
+        "tokens".to_string(),
+        "cw721".to_string(),
+        to_json_binary(&cw721_init_msg).unwrap(),
+    );
+
+    let modules: Vec<Module> = vec![Module::new("receipt", "./receipt", false)];
+    let marketplace_init_msg =
+        mock_marketplace_instantiate_msg(andr.kernel.addr().to_string(), Some(modules), None);
+    let marketplace_component = AppComponent::new(
+        "marketplace".to_string(),
+        "marketplace".to_string(),
+        to_json_binary(&marketplace_init_msg).unwrap(),
+    );
+
+    // The marketplace is authorised to store receipts.
+    let receipt_init_msg = mock_receipt_instantiate_msg(
+        Some(vec![AndrAddr::from_string(format!(
+            "./{}",
+            marketplace_component.name
+        ))]),
+        andr.kernel.addr().to_string(),
+        None,
+    );
+    let receipt_component = AppComponent::new(
+        "receipt",
+        "receipt",
+        to_json_binary(&receipt_init_msg).unwrap(),
+    );
+
+    // Create App
+    let app_components = vec![
+        cw721_component.clone(),
+        marketplace_component.clone(),
+        receipt_component.clone(),
+    ];
+    let app_code_id = andr.get_code_id(&mut router, "app-contract");
+    let app = MockAppContract::instantiate(
+        app_code_id,
+        owner,
+        &mut router,
+        "Marketplace App",
+        app_components,
+        andr.kernel.addr(),
+        None,
+    );
+
+    let cw721: MockCW721 = app.query_ado_by_component_name(&router, cw721_component.name);
+    let marketplace: MockMarketplace =
+        app.query_ado_by_component_name(&router, marketplace_component.name);
+    let receipt_addr = andr.vfs.query_resolve_path(
+        &mut router,
+        format!("/home/{}/{}", app.addr(), receipt_component.name),
+    );
+
+    // Mint Tokens
+    cw721
+        .execute_quick_mint(&mut router, owner.clone(), 1, owner.to_string())
+        .unwrap();
+    let token_id = "0";
+
+    // Send Token to Marketplace
+    cw721
+        .execute_send_nft(
+            &mut router,
+            owner.clone(),
+            marketplace.addr().clone(),
+            token_id,
+            &mock_start_sale(Uint128::from(100u128), "uandr", false, None, None, None),
+        )
+        .unwrap();
+
+    let block_info = router.block_info();
+    router.set_block(BlockInfo {
+        height: block_info.height,
+        time: block_info.time.plus_minutes(1),
+        chain_id: block_info.chain_id,
+    });
+
+    // Buy Token
+    router
+        .execute_contract(
+            buyer.clone(),
+            marketplace.addr().clone(),
+            &mock_buy_token(cw721.addr().clone(), token_id),
+            &[coin(100, "uandr")],
+        )
+        .unwrap();
+
+    // Check final state
+    let owner_of_token = cw721.query_owner_of(&router, token_id);
+    assert_eq!(owner_of_token, buyer.to_string());
+
+    let receipts: ReceiptsResponse = router
+        .wrap()
+        .query_wasm_smart(
+            receipt_addr,
+            &mock_receipts_by_sender_msg(buyer.to_string(), None, None),
+        )
+        .unwrap();
+    assert_eq!(receipts.receipts.len(), 2);
+    let receipt = &receipts.receipts[0];
+    assert_eq!(&receipt.host, marketplace.addr());
+    assert_eq!(receipt.amount, Some(Funds::Native(coin(100, "uandr"))));
+    // The marketplace does not include the executed message in the funds transfer hook payload.
+    assert_eq!(receipt.events, vec![Event::new("receipt")]);
+
+    // The execute hook stores a receipt of the executed message.
+    let receipt = &receipts.receipts[1];
+    assert_eq!(&receipt.host, marketplace.addr());
+    assert_eq!(receipt.amount, None);
+    assert_eq!(
+        receipt.events,
+        vec![Event::new("receipt").add_attribute("action", "buy")]
+    );
+}